@@ -1,13 +1,16 @@
 use crate::okx::datastore::brc20::redb::table::{
-  get_balance, get_balances, get_token_info, get_tokens_info, get_transaction_receipts,
-  get_transferable_assets_by_account, get_transferable_assets_by_account_ticker,
-  get_transferable_assets_by_outpoint,
+  get_address_receipts, get_balance, get_balance_at, get_balances, get_block_checksum,
+  get_tick_burns, get_tick_holders, get_tick_receipts, get_token_info, get_tokens_info,
+  get_transaction_receipts, get_transferable_assets_by_account,
+  get_transferable_assets_by_account_ticker, get_transferable_assets_by_outpoint,
 };
 use crate::okx::datastore::ord::redb::table::{
-  get_collection_inscription_id, get_collections_of_inscription, get_transaction_operations,
-  get_txout_by_outpoint,
+  get_collection_inscription_id, get_collections_of_inscription, get_district_inscription_id,
+  get_district_owner, get_districts, get_districts_by_owner, get_inscription_district,
+  get_transaction_operations, get_txout_by_outpoint,
 };
 use crate::okx::datastore::{brc20, ScriptKey};
+use crate::okx::protocol::ProtocolRegistry;
 use bitcoin::address::NetworkChecked;
 use bitcoin::PrivateKey;
 use bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, ImportMultiResult};
@@ -47,10 +50,14 @@ pub use self::entry::RuneEntry;
 pub(super) use self::entry::{
   InscriptionEntry, InscriptionEntryValue, InscriptionIdValue, OutPointValue, TxidValue,
 };
-pub(super) use self::updater::BlockData;
+pub(super) use self::{reorg::ReorgRecord, updater::BlockData};
 
+mod blk_files;
+mod brc20_snapshot;
 pub(crate) mod entry;
+pub(crate) mod event;
 mod fetcher;
+pub(crate) mod mempool;
 mod reorg;
 pub(crate) mod rtx;
 pub(crate) mod updater;
@@ -60,7 +67,7 @@ mod extend;
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 16;
+const SCHEMA_VERSION: u64 = 32;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -101,12 +108,26 @@ define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 define_table! { ORD_TX_TO_OPERATIONS, &TxidValue, &[u8] }
 define_table! { COLLECTIONS_KEY_TO_INSCRIPTION_ID, &str, InscriptionIdValue }
 define_multimap_table! { COLLECTIONS_INSCRIPTION_ID_TO_KINDS, InscriptionIdValue, &[u8] }
+define_table! { BITMAP_DISTRICT_TO_INSCRIPTION_ID, u32, InscriptionIdValue }
+define_table! { BITMAP_INSCRIPTION_ID_TO_DISTRICT, InscriptionIdValue, u32 }
+define_table! { BITMAP_DISTRICT_TO_OWNER, u32, &[u8] }
+define_multimap_table! { BITMAP_OWNER_TO_DISTRICTS, &str, u32 }
+define_table! { HEIGHT_TO_BLOCK_RECORD, u32, &[u8] }
+define_table! { HEIGHT_TO_SPENT_OUTPOINTS, u32, &[u8] }
+define_table! { REORG_ID_TO_REORG, u32, &[u8] }
+define_table! { SAVEPOINT_TO_BLOCK_COUNT, u64, u32 }
 
 define_table! { BRC20_BALANCES, &str, &[u8] }
 define_table! { BRC20_TOKEN, &str, &[u8] }
 define_table! { BRC20_EVENTS, &TxidValue, &[u8] }
 define_table! { BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &SatPointValue, &[u8] }
 define_multimap_table! { BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, &str, &SatPointValue }
+define_table! { BRC20_ADDRESS_TO_RECEIPTS, &str, &[u8] }
+define_table! { BRC20_TICKER_TO_HOLDERS, &str, &[u8] }
+define_table! { BRC20_TICKER_TO_RECEIPTS, &str, &[u8] }
+define_table! { BRC20_TICKER_TO_BURNS, &str, () }
+define_table! { BRC20_BALANCE_JOURNAL, &str, &[u8] }
+define_table! { BRC20_HEIGHT_TO_CHECKSUM, u32, &[u8; 32] }
 
 #[derive(Debug, PartialEq)]
 pub enum List {
@@ -129,6 +150,8 @@ pub(crate) enum Statistic {
   SatRanges = 10,
   UnboundInscriptions = 11,
   IndexTransactions = 12,
+  FirstBrc20Height = 13,
+  CompactedOutputsHeight = 14,
 }
 
 impl Statistic {
@@ -175,6 +198,66 @@ pub(crate) struct TableInfo {
   tree_height: u32,
 }
 
+pub(crate) fn insert_table_info<K: RedbKey + 'static, V: RedbValue + 'static>(
+  tables: &mut BTreeMap<String, TableInfo>,
+  wtx: &WriteTransaction,
+  database_total_bytes: u64,
+  definition: TableDefinition<K, V>,
+) {
+  let stats = wtx.open_table(definition).unwrap().stats().unwrap();
+
+  let fragmented_bytes = stats.fragmented_bytes();
+  let metadata_bytes = stats.metadata_bytes();
+  let stored_bytes = stats.stored_bytes();
+  let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
+
+  tables.insert(
+    definition.name().into(),
+    TableInfo {
+      branch_pages: stats.branch_pages(),
+      fragmented_bytes,
+      leaf_pages: stats.leaf_pages(),
+      metadata_bytes,
+      proportion: total_bytes as f64 / database_total_bytes as f64,
+      stored_bytes,
+      total_bytes,
+      tree_height: stats.tree_height(),
+    },
+  );
+}
+
+pub(crate) fn insert_multimap_table_info<K: RedbKey + 'static, V: RedbValue + RedbKey + 'static>(
+  tables: &mut BTreeMap<String, TableInfo>,
+  wtx: &WriteTransaction,
+  database_total_bytes: u64,
+  definition: MultimapTableDefinition<K, V>,
+) {
+  let stats = wtx
+    .open_multimap_table(definition)
+    .unwrap()
+    .stats()
+    .unwrap();
+
+  let fragmented_bytes = stats.fragmented_bytes();
+  let metadata_bytes = stats.metadata_bytes();
+  let stored_bytes = stats.stored_bytes();
+  let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
+
+  tables.insert(
+    definition.name().into(),
+    TableInfo {
+      branch_pages: stats.branch_pages(),
+      fragmented_bytes,
+      leaf_pages: stats.leaf_pages(),
+      metadata_bytes,
+      proportion: total_bytes as f64 / database_total_bytes as f64,
+      stored_bytes,
+      total_bytes,
+      tree_height: stats.tree_height(),
+    },
+  );
+}
+
 #[derive(Serialize)]
 pub(crate) struct TransactionInfo {
   pub(crate) starting_block_count: u32,
@@ -218,9 +301,13 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
+  /// The blocks located in Bitcoin Core's blk files, kept between updates so that only the blocks
+  /// written since are scanned.
+  blk_files: Arc<Mutex<Option<blk_files::BlkFiles>>>,
   client: Client,
   database: Database,
   durability: redb::Durability,
+  event_sender: tokio::sync::broadcast::Sender<event::IndexEvent>,
   first_inscription_height: u32,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
@@ -228,6 +315,7 @@ pub struct Index {
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
+  mempool: Mutex<Option<Arc<mempool::Mempool>>>,
   options: Options,
   path: PathBuf,
   started: DateTime<Utc>,
@@ -365,13 +453,16 @@ impl Index {
         tx.open_table(ORD_TX_TO_OPERATIONS)?;
         tx.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?;
         tx.open_multimap_table(COLLECTIONS_INSCRIPTION_ID_TO_KINDS)?;
+        tx.open_table(BITMAP_DISTRICT_TO_INSCRIPTION_ID)?;
+        tx.open_table(BITMAP_INSCRIPTION_ID_TO_DISTRICT)?;
+        tx.open_table(BITMAP_DISTRICT_TO_OWNER)?;
+        tx.open_multimap_table(BITMAP_OWNER_TO_DISTRICTS)?;
+        tx.open_table(HEIGHT_TO_BLOCK_RECORD)?;
+        tx.open_table(HEIGHT_TO_SPENT_OUTPOINTS)?;
+        tx.open_table(REORG_ID_TO_REORG)?;
+        tx.open_table(SAVEPOINT_TO_BLOCK_COUNT)?;
 
-        // brc20 tables
-        tx.open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?;
-        tx.open_table(BRC20_BALANCES)?;
-        tx.open_table(BRC20_TOKEN)?;
-        tx.open_table(BRC20_EVENTS)?;
-        tx.open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
+        ProtocolRegistry::builtin(options).create_tables(&tx)?;
 
         {
           let mut outpoint_to_sat_ranges = tx.open_table(OUTPOINT_TO_SAT_RANGES)?;
@@ -413,15 +504,18 @@ impl Index {
 
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
+      blk_files: Arc::default(),
       client,
       database,
       durability,
+      event_sender: event::event_channel(),
       first_inscription_height: options.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
       index_runes,
       index_sats,
       index_transactions,
+      mempool: Mutex::new(None),
       options: options.clone(),
       path,
       started: Utc::now(),
@@ -433,6 +527,10 @@ impl Index {
     self.options.chain()
   }
 
+  pub(crate) fn name_namespaces(&self) -> &[ord::names::Namespace] {
+    &self.options.index_names
+  }
+
   pub(crate) fn get_extend_change_address(&self) -> Option<Address> {
     let address = Address::from_str(self.options.extend_change_address().as_str())
       .and_then(|address| address.require_network(self.get_chain().network()));
@@ -522,66 +620,6 @@ impl Index {
   }
 
   pub(crate) fn info(&self) -> Result<Info> {
-    fn insert_table_info<K: RedbKey + 'static, V: RedbValue + 'static>(
-      tables: &mut BTreeMap<String, TableInfo>,
-      wtx: &WriteTransaction,
-      database_total_bytes: u64,
-      definition: TableDefinition<K, V>,
-    ) {
-      let stats = wtx.open_table(definition).unwrap().stats().unwrap();
-
-      let fragmented_bytes = stats.fragmented_bytes();
-      let metadata_bytes = stats.metadata_bytes();
-      let stored_bytes = stats.stored_bytes();
-      let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
-
-      tables.insert(
-        definition.name().into(),
-        TableInfo {
-          branch_pages: stats.branch_pages(),
-          fragmented_bytes,
-          leaf_pages: stats.leaf_pages(),
-          metadata_bytes,
-          proportion: total_bytes as f64 / database_total_bytes as f64,
-          stored_bytes,
-          total_bytes,
-          tree_height: stats.tree_height(),
-        },
-      );
-    }
-
-    fn insert_multimap_table_info<K: RedbKey + 'static, V: RedbValue + RedbKey + 'static>(
-      tables: &mut BTreeMap<String, TableInfo>,
-      wtx: &WriteTransaction,
-      database_total_bytes: u64,
-      definition: MultimapTableDefinition<K, V>,
-    ) {
-      let stats = wtx
-        .open_multimap_table(definition)
-        .unwrap()
-        .stats()
-        .unwrap();
-
-      let fragmented_bytes = stats.fragmented_bytes();
-      let metadata_bytes = stats.metadata_bytes();
-      let stored_bytes = stats.stored_bytes();
-      let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
-
-      tables.insert(
-        definition.name().into(),
-        TableInfo {
-          branch_pages: stats.branch_pages(),
-          fragmented_bytes,
-          leaf_pages: stats.leaf_pages(),
-          metadata_bytes,
-          proportion: total_bytes as f64 / database_total_bytes as f64,
-          stored_bytes,
-          total_bytes,
-          tree_height: stats.tree_height(),
-        },
-      );
-    }
-
     let wtx = self.begin_write()?;
 
     let stats = wtx.stats()?;
@@ -658,21 +696,25 @@ impl Index {
       total_bytes,
       COLLECTIONS_INSCRIPTION_ID_TO_KINDS,
     );
-    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_BALANCES);
-    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_TOKEN);
-    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_EVENTS);
     insert_table_info(
       &mut tables,
       &wtx,
       total_bytes,
-      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+      BITMAP_DISTRICT_TO_INSCRIPTION_ID,
     );
-    insert_multimap_table_info(
+    insert_table_info(
       &mut tables,
       &wtx,
       total_bytes,
-      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      BITMAP_INSCRIPTION_ID_TO_DISTRICT,
     );
+    insert_table_info(&mut tables, &wtx, total_bytes, BITMAP_DISTRICT_TO_OWNER);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, BITMAP_OWNER_TO_DISTRICTS);
+    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_BLOCK_RECORD);
+    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_SPENT_OUTPOINTS);
+    insert_table_info(&mut tables, &wtx, total_bytes, REORG_ID_TO_REORG);
+    insert_table_info(&mut tables, &wtx, total_bytes, SAVEPOINT_TO_BLOCK_COUNT);
+    ProtocolRegistry::builtin(&self.options).insert_table_info(&mut tables, &wtx, total_bytes);
 
     for table in wtx.list_tables()? {
       assert!(tables.contains_key(table.name()));
@@ -745,6 +787,12 @@ impl Index {
             Some(&ReorgError::Recoverable { height, depth }) => {
               Reorg::handle_reorg(self, height, depth)?;
 
+              self.publish_events(vec![event::IndexEvent::Rollback {
+                height,
+                depth,
+                block_count: self.block_count()?,
+              }]);
+
               updater = Updater::new(self)?;
             }
             Some(&ReorgError::Unrecoverable) => {
@@ -760,6 +808,106 @@ impl Index {
     }
   }
 
+  /// Roll back to the most recent savepoint at or below `height`, returning the new block count.
+  pub(crate) fn rollback(&self, height: u32) -> Result<u32> {
+    Reorg::rollback(self, height)
+  }
+
+  /// Delete the outputs spent by the indexed blocks from `OUTPOINT_TO_ENTRY`, returning how many
+  /// blocks were read and how many outputs were pruned.
+  ///
+  /// The blocks below `Statistic::CompactedOutputsHeight` have their spent outputs pruned already,
+  /// the updater moves it along while the index is compact. The other ones are read from bitcoind,
+  /// the outputs they spend are deleted, or queued in `HEIGHT_TO_SPENT_OUTPOINTS` if the block can
+  /// still be rolled back.
+  pub(crate) fn compact_outputs(&self) -> Result<(u64, u64)> {
+    const CHUNK_SIZE: usize = 100;
+
+    let (hashes, oldest) = {
+      let rtx = self.database.begin_read()?;
+
+      let compacted = rtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&Statistic::CompactedOutputsHeight.key())?
+        .map(|x| u32::try_from(x.value()).unwrap())
+        .unwrap_or_default()
+        .max(self.first_inscription_height);
+
+      let hashes = rtx
+        .open_table(HEIGHT_TO_BLOCK_HEADER)?
+        .range(compacted..)?
+        .map(|result| {
+          result
+            .map(|(height, header)| (height.value(), Header::load(*header.value()).block_hash()))
+        })
+        .collect::<Result<Vec<(u32, BlockHash)>, StorageError>>()?;
+
+      let height = rtx::Rtx(rtx).block_height()?.map(|height| height.n());
+
+      (
+        hashes,
+        height.and_then(|height| height.checked_sub(Reorg::rollback_depth(self))),
+      )
+    };
+
+    let mut blocks = 0;
+    let mut pruned = 0;
+
+    for chunk in hashes.chunks(CHUNK_SIZE) {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+
+      let mut spent = Vec::new();
+      for &(height, hash) in chunk {
+        let block = self
+          .get_block_by_hash(hash)?
+          .ok_or_else(|| anyhow!("block {hash} at height {height} not found"))?;
+
+        let mut outpoints = Vec::new();
+        for input in block.txdata.iter().flat_map(|tx| &tx.input) {
+          if !input.previous_output.is_null() {
+            outpoints.extend_from_slice(&input.previous_output.store());
+          }
+        }
+
+        spent.push((height, outpoints));
+      }
+
+      let wtx = self.begin_write()?;
+      {
+        let mut outpoint_to_entry = wtx.open_table(OUTPOINT_TO_ENTRY)?;
+        let mut height_to_spent_outpoints = wtx.open_table(HEIGHT_TO_SPENT_OUTPOINTS)?;
+        for (height, outpoints) in &spent {
+          if oldest.map_or(false, |oldest| *height <= oldest) {
+            for outpoint in outpoints.chunks_exact(mem::size_of::<OutPointValue>()) {
+              let outpoint: &OutPointValue = outpoint.try_into().unwrap();
+              if outpoint_to_entry.remove(outpoint)?.is_some() {
+                pruned += 1;
+              }
+            }
+          } else {
+            height_to_spent_outpoints.insert(height, outpoints.as_slice())?;
+          }
+        }
+      }
+      // the next run goes on from the block after this chunk.
+      Index::set_statistic(
+        &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+        Statistic::CompactedOutputsHeight,
+        u64::from(chunk.last().unwrap().0 + 1),
+      )?;
+      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      wtx.commit()?;
+
+      blocks += u64::try_from(chunk.len()).unwrap();
+
+      log::info!("read {blocks} blocks, pruned {pruned} spent outputs");
+    }
+
+    Ok((blocks, pruned))
+  }
+
   pub(crate) fn export(&self, filename: &String, include_addresses: bool) -> Result {
     let mut writer = BufWriter::new(File::create(filename)?);
     let rtx = self.database.begin_read()?;
@@ -2276,6 +2424,534 @@ mod tests {
     }
   }
 
+  #[test]
+  fn brc20_transfers_to_op_return_are_indexed_as_burns() {
+    let context = Context::builder().arg("--enable-index-brc20").build();
+    context.mine_blocks(1);
+
+    for (input, op) in [
+      (
+        1,
+        r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+      ),
+      (2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#),
+      (
+        3,
+        r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"4"}"#,
+      ),
+    ] {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          input,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", op).to_witness(),
+        )],
+        ..Default::default()
+      });
+      context.mine_blocks(1);
+    }
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Default::default())],
+      op_return: Some(
+        script::Builder::new()
+          .push_opcode(opcodes::all::OP_RETURN)
+          .into_script(),
+      ),
+      op_return_index: Some(0),
+      op_return_value: Some(1000),
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let tick = brc20::Tick::from_str("ordi").unwrap();
+    let rtx = context.index.begin_read().unwrap();
+
+    let token = rtx.brc20_get_tick_info(&tick).unwrap().unwrap();
+    assert_eq!(token.burned_supply, 4_000_000_000_000_000_000);
+
+    let (burns, next_cursor) = rtx
+      .brc20_get_tick_burns(&tick, 0, u32::MAX, None, 10)
+      .unwrap();
+    assert_eq!(next_cursor, None);
+    assert_eq!(burns.len(), 1);
+    assert_eq!(burns[0].0, 5);
+    assert_eq!(burns[0].1, txid);
+    assert!(burns[0].2.is_burn());
+
+    let (receipts, _) = rtx
+      .brc20_get_tick_receipts(&tick, 0, u32::MAX, None, 10)
+      .unwrap();
+    assert_eq!(receipts.len(), 4);
+  }
+
+  #[test]
+  fn brc20_snapshot_round_trip() {
+    let context = Context::builder().arg("--enable-index-brc20").build();
+    context.mine_blocks(1);
+
+    for (input, op) in [
+      (
+        1,
+        r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+      ),
+      (2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#),
+    ] {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          input,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", op).to_witness(),
+        )],
+        ..Default::default()
+      });
+      context.mine_blocks(1);
+    }
+
+    let tick = brc20::Tick::from_str("ordi").unwrap();
+    let token = context
+      .index
+      .begin_read()
+      .unwrap()
+      .brc20_get_tick_info(&tick)
+      .unwrap()
+      .unwrap();
+    assert_eq!(token.minted, 10_000_000_000_000_000_000);
+    assert_eq!(token.holders, 1);
+
+    let rtx = context.index.begin_read().unwrap();
+    let receipts = rtx
+      .brc20_get_tick_receipts(&tick, 0, u32::MAX, None, 10)
+      .unwrap();
+    assert_eq!(receipts.0.len(), 2);
+    let checksum = rtx.brc20_get_block_checksum(3).unwrap();
+    assert!(checksum.is_some());
+
+    let exported = context.tempdir.path().join("brc20.snapshot");
+    context.index.brc20_export(&exported).unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .brc20_import(&exported)
+        .unwrap_err()
+        .to_string(),
+      "BRC20 state of the index is not empty"
+    );
+
+    // a second index built up to the same block without BRC20 state.
+    let mut options = context.options.clone();
+    options.index = Some(context.tempdir.path().join("imported.redb"));
+    options.first_brc20_height = Some(u32::MAX);
+    let index = Index::open(&options).unwrap();
+    index.update().unwrap();
+    assert_eq!(index.brc20_import(&exported).unwrap(), checksum);
+
+    let imported = index.begin_read().unwrap();
+    assert_eq!(imported.brc20_get_tick_info(&tick).unwrap(), Some(token));
+    assert_eq!(
+      imported.brc20_get_tick_holders(&tick, 0..10).unwrap().len(),
+      1
+    );
+    assert_eq!(
+      imported
+        .brc20_get_tick_receipts(&tick, 0, u32::MAX, None, 10)
+        .unwrap(),
+      receipts
+    );
+    assert_eq!(imported.brc20_get_block_checksum(3).unwrap(), checksum);
+
+    let reexported = context.tempdir.path().join("brc20.reexported");
+    index.brc20_export(&reexported).unwrap();
+    assert_eq!(fs::read(&exported).unwrap(), fs::read(&reexported).unwrap());
+
+    let mut corrupted = fs::read(&exported).unwrap();
+    *corrupted.last_mut().unwrap() ^= 1;
+    fs::write(&exported, corrupted).unwrap();
+    assert_eq!(
+      index.brc20_import(&exported).unwrap_err().to_string(),
+      "BRC20 snapshot checksum mismatch, the file is corrupted"
+    );
+
+    // BRC20 is indexed from the block following the snapshot, whatever the first BRC20 height.
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        3,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+    index.update().unwrap();
+    assert_eq!(
+      index
+        .begin_read()
+        .unwrap()
+        .brc20_get_tick_info(&tick)
+        .unwrap()
+        .unwrap()
+        .minted,
+      20_000_000_000_000_000_000
+    );
+  }
+
+  #[test]
+  fn index_events_are_published_on_commit_and_reorg() {
+    let mut context = Context::builder().arg("--enable-index-brc20").build();
+    context.index.set_durability(redb::Durability::Immediate);
+    context.mine_blocks(1);
+
+    let mut receiver = context.index.subscribe_events();
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    match receiver.try_recv().unwrap() {
+      event::IndexEvent::InscriptionOperation {
+        height, operation, ..
+      } => {
+        assert_eq!(height, 2);
+        assert_eq!(operation.inscription_id, InscriptionId { txid, index: 0 });
+      }
+      event => panic!("unexpected event {event:?}"),
+    }
+    match receiver.try_recv().unwrap() {
+      event::IndexEvent::BRC20Receipt {
+        height,
+        txid: receipt_txid,
+        receipt,
+        ..
+      } => {
+        assert_eq!(height, 2);
+        assert_eq!(receipt_txid, txid);
+        assert_eq!(receipt.op, brc20::OperationType::Deploy);
+      }
+      event => panic!("unexpected event {event:?}"),
+    }
+    assert!(receiver.try_recv().is_err());
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    match receiver.try_recv().unwrap() {
+      event::IndexEvent::Rollback {
+        height,
+        depth,
+        block_count,
+      } => {
+        assert_eq!(height, 3);
+        assert_eq!(depth, 2);
+        assert_eq!(block_count, 2);
+      }
+      event => panic!("unexpected event {event:?}"),
+    }
+  }
+
+  #[test]
+  fn bitmap_districts_are_indexed_with_their_owner() {
+    let context = Context::builder().arg("--enable-index-bitmap").build();
+    context.mine_blocks(1);
+
+    let mut txids = Vec::new();
+    for (input, content) in [(1, "1.bitmap"), (2, "1.bitmap"), (3, "9.bitmap")] {
+      txids.push(context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          input,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", content).to_witness(),
+        )],
+        ..Default::default()
+      }));
+      context.mine_blocks(1);
+    }
+
+    let inscription_id = InscriptionId {
+      txid: txids[0],
+      index: 0,
+    };
+    let owner = ScriptKey::from_script(&ScriptBuf::new(), context.index.get_chain());
+
+    let rtx = context.index.begin_read().unwrap();
+    assert_eq!(
+      rtx.bitmap_get_districts(0..10).unwrap(),
+      vec![(1, inscription_id)]
+    );
+    assert_eq!(rtx.bitmap_count_districts().unwrap(), 1);
+    assert_eq!(
+      rtx.bitmap_get_district(1).unwrap(),
+      Some((inscription_id, Some(owner.clone())))
+    );
+    assert_eq!(rtx.bitmap_get_district(9).unwrap(), None);
+    assert_eq!(
+      rtx.bitmap_get_inscription_district(inscription_id).unwrap(),
+      Some(1)
+    );
+    assert_eq!(
+      rtx
+        .bitmap_get_inscription_district(InscriptionId {
+          txid: txids[1],
+          index: 0,
+        })
+        .unwrap(),
+      None
+    );
+    assert_eq!(
+      rtx.bitmap_get_districts_by_owner(&owner, 0..10).unwrap(),
+      vec![1]
+    );
+    assert_eq!(rtx.bitmap_count_districts_by_owner(&owner).unwrap(), 1);
+    drop(rtx);
+
+    // send the district inscription to an output with another script.
+    let script = script::Builder::new()
+      .push_opcode(opcodes::all::OP_RETURN)
+      .into_script();
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      op_return: Some(script.clone()),
+      op_return_index: Some(0),
+      op_return_value: Some(COIN_VALUE),
+      outputs: 1,
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let new_owner = ScriptKey::from_script(&script, context.index.get_chain());
+    assert_ne!(new_owner, owner);
+
+    let rtx = context.index.begin_read().unwrap();
+    assert_eq!(
+      rtx.bitmap_get_district(1).unwrap(),
+      Some((inscription_id, Some(new_owner.clone())))
+    );
+    assert_eq!(
+      rtx.bitmap_get_districts_by_owner(&owner, 0..10).unwrap(),
+      Vec::<u32>::new()
+    );
+    assert_eq!(rtx.bitmap_count_districts_by_owner(&owner).unwrap(), 0);
+    assert_eq!(
+      rtx
+        .bitmap_get_districts_by_owner(&new_owner, 0..10)
+        .unwrap(),
+      vec![1]
+    );
+  }
+
+  #[test]
+  fn first_is_first_names_are_indexed() {
+    let context = Context::builder()
+      .args(["--index-name", "sats", "--index-name", "unisat@6"])
+      .build();
+    context.mine_blocks(1);
+
+    let mut txids = Vec::new();
+    for (input, content) in [
+      (1, "Alice.sats"),
+      (2, "alice.sats\n"),
+      (3, "bob.unisat"),
+      (4, "a.b.sats"),
+    ] {
+      txids.push(context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          input,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", content).to_witness(),
+        )],
+        ..Default::default()
+      }));
+      context.mine_blocks(1);
+    }
+
+    let rtx = context.index.begin_read().unwrap();
+    let alice = InscriptionId {
+      txid: txids[0],
+      index: 0,
+    };
+    assert_eq!(
+      rtx.ord_name_to_inscription_id("alice.sats").unwrap(),
+      Some(alice)
+    );
+    assert_eq!(
+      rtx.ord_inscription_id_to_collections(alice).unwrap(),
+      Some(vec![CollectionKind::Name("sats".into())])
+    );
+    assert_eq!(
+      rtx
+        .ord_inscription_id_to_collections(InscriptionId {
+          txid: txids[1],
+          index: 0,
+        })
+        .unwrap(),
+      Some(Vec::new())
+    );
+    assert_eq!(
+      rtx
+        .inscription_owner(alice, context.index.get_chain())
+        .unwrap(),
+      Some(ScriptKey::from_script(
+        &ScriptBuf::new(),
+        context.index.get_chain()
+      ))
+    );
+    // `bob.unisat` was inscribed at height 4, before the namespace activated at 6.
+    assert_eq!(rtx.ord_name_to_inscription_id("bob.unisat").unwrap(), None);
+    assert_eq!(rtx.ord_name_to_inscription_id("a.b.sats").unwrap(), None);
+    drop(rtx);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        5,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "bob.unisat").to_witness(),
+      )],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    assert!(context
+      .index
+      .begin_read()
+      .unwrap()
+      .ord_name_to_inscription_id("bob.unisat")
+      .unwrap()
+      .is_some());
+  }
+
+  #[test]
+  fn reorgs_are_recorded_with_orphaned_blocks() {
+    let mut context = Context::builder().arg("--enable-index-brc20").build();
+    context.index.set_durability(redb::Durability::Immediate);
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    let block_hash = context.mine_blocks(1)[0].block_hash();
+
+    assert_eq!(
+      context.index.begin_read().unwrap().reorg_count().unwrap(),
+      0
+    );
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let rtx = context.index.begin_read().unwrap();
+    assert_eq!(rtx.reorg_count().unwrap(), 1);
+    let reorgs = rtx.reorgs(0..10).unwrap();
+    let (id, reorg) = &reorgs[0];
+    assert_eq!(*id, 0);
+    assert_eq!(reorg.height, 3);
+    assert_eq!(reorg.depth, 2);
+
+    let orphaned_blocks = &reorg.orphaned_blocks;
+    assert_eq!(orphaned_blocks.len(), 1);
+    assert_eq!(orphaned_blocks[0].height, 2);
+    assert_eq!(orphaned_blocks[0].block_hash, block_hash);
+    assert_eq!(orphaned_blocks[0].txids, vec![txid]);
+    assert_eq!(orphaned_blocks[0].receipts.len(), 1);
+    assert_eq!(orphaned_blocks[0].receipts[0].0, txid);
+    assert_eq!(
+      orphaned_blocks[0].receipts[0].1[0].op,
+      brc20::OperationType::Deploy
+    );
+  }
+
+  #[test]
+  fn consecutive_reorgs_are_all_recorded() {
+    let mut context = Context::builder().build();
+    context.index.set_durability(redb::Durability::Immediate);
+    for _ in 0..3 {
+      context.mine_blocks(1);
+    }
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+    context.mine_blocks(1);
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let rtx = context.index.begin_read().unwrap();
+    assert_eq!(rtx.reorg_count().unwrap(), 2);
+    let reorgs = rtx.reorgs(0..10).unwrap();
+    assert_eq!(
+      reorgs
+        .iter()
+        .map(|(id, reorg)| (*id, reorg.height, reorg.depth))
+        .collect::<Vec<_>>(),
+      vec![(1, 6, 2), (0, 4, 2)]
+    );
+  }
+
+  #[test]
+  fn rollback_to_height_with_block_savepoints() {
+    let mut context = Context::builder().arg("--block-savepoints=5").build();
+    context.index.set_durability(redb::Durability::Immediate);
+    for _ in 0..8 {
+      context.mine_blocks(1);
+    }
+    assert_eq!(context.index.block_count().unwrap(), 9);
+
+    assert_eq!(
+      context.index.rollback(2).unwrap_err().to_string(),
+      "cannot roll back to height 2, the oldest savepoint is at height 4"
+    );
+    assert_eq!(context.index.block_count().unwrap(), 9);
+
+    assert_eq!(context.index.rollback(6).unwrap(), 7);
+    assert_eq!(context.index.block_count().unwrap(), 7);
+
+    context.index.update().unwrap();
+    assert_eq!(context.index.block_count().unwrap(), 9);
+
+    // the restored savepoint can be restored again.
+    assert_eq!(context.index.rollback(6).unwrap(), 7);
+  }
+
+  #[test]
+  fn rollback_without_savepoints_fails() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+    assert_eq!(
+      context.index.rollback(0).unwrap_err().to_string(),
+      "savepoints are not kept when index durability is `Durability::None`"
+    );
+  }
+
   #[test]
   fn inscriptions_below_first_inscription_height_are_skipped() {
     let inscription = inscription("text/plain;charset=utf-8", "hello");
@@ -5953,4 +6629,170 @@ mod tests {
       assert_eq!(sat, entry.sat);
     }
   }
+
+  #[test]
+  fn spent_outputs_are_pruned_once_they_can_no_longer_be_rolled_back() {
+    let context = Context::builder().arg("--block-savepoints=2").build();
+
+    context.mine_blocks(1);
+
+    let spent = OutPoint {
+      txid: context.rpc_server.tx(1, 0).txid(),
+      vout: 0,
+    };
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+    let unspent = OutPoint { txid, vout: 0 };
+
+    context.mine_blocks(1);
+
+    assert!(context
+      .index
+      .begin_read()
+      .unwrap()
+      .outpoint_to_entry(spent)
+      .unwrap()
+      .is_some());
+
+    context.mine_blocks(2);
+
+    let rtx = context.index.begin_read().unwrap();
+    assert_eq!(rtx.outpoint_to_entry(spent).unwrap(), None);
+    assert!(rtx.outpoint_to_entry(unspent).unwrap().is_some());
+
+    assert!(context
+      .index
+      .check_sync(&[(unspent, Amount::from_sat(50 * COIN_VALUE))].into())
+      .unwrap());
+  }
+
+  #[test]
+  fn compact_outputs_prunes_outputs_spent_before_they_were_recorded() {
+    let context = Context::builder().arg("--block-savepoints=2").build();
+
+    context.mine_blocks(1);
+
+    // every indexed block already has its spent outputs recorded.
+    assert_eq!(context.index.compact_outputs().unwrap(), (0, 0));
+
+    let spent = OutPoint {
+      txid: context.rpc_server.tx(1, 0).txid(),
+      vout: 0,
+    };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(3);
+
+    // an index built before spent outputs were recorded.
+    {
+      let wtx = context.index.begin_write().unwrap();
+      for result in wtx
+        .open_table(HEIGHT_TO_SPENT_OUTPOINTS)
+        .unwrap()
+        .drain::<u32>(..)
+        .unwrap()
+      {
+        result.unwrap();
+      }
+      wtx
+        .open_table(OUTPOINT_TO_ENTRY)
+        .unwrap()
+        .insert(&spent.store(), [].as_slice())
+        .unwrap();
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .remove(&Statistic::CompactedOutputsHeight.key())
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(context.index.compact_outputs().unwrap(), (5, 1));
+
+    assert_eq!(
+      context
+        .index
+        .begin_read()
+        .unwrap()
+        .outpoint_to_entry(spent)
+        .unwrap(),
+      None
+    );
+
+    // the blocks that can still be rolled back are queued for the updater.
+    assert_eq!(
+      context
+        .index
+        .begin_read()
+        .unwrap()
+        .0
+        .open_table(HEIGHT_TO_SPENT_OUTPOINTS)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| result.unwrap().0.value())
+        .collect::<Vec<u32>>(),
+      [3, 4]
+    );
+
+    // the index stays compact.
+    assert_eq!(context.index.compact_outputs().unwrap(), (0, 0));
+    context.mine_blocks(1);
+    assert_eq!(context.index.compact_outputs().unwrap(), (0, 0));
+  }
+
+  #[test]
+  fn compact_outputs_skips_blocks_drained_by_the_updater() {
+    let context = Context::builder().arg("--block-savepoints=2").build();
+
+    context.mine_blocks(1);
+
+    let spent = OutPoint {
+      txid: context.rpc_server.tx(1, 0).txid(),
+      vout: 0,
+    };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(10);
+
+    // the updater pruned the outputs spent more than two blocks ago and dropped their records.
+    assert_eq!(
+      context
+        .index
+        .begin_read()
+        .unwrap()
+        .0
+        .open_table(HEIGHT_TO_SPENT_OUTPOINTS)
+        .unwrap()
+        .len()
+        .unwrap(),
+      2
+    );
+    assert_eq!(
+      context
+        .index
+        .begin_read()
+        .unwrap()
+        .outpoint_to_entry(spent)
+        .unwrap(),
+      None
+    );
+
+    assert_eq!(context.index.compact_outputs().unwrap(), (0, 0));
+    assert_eq!(
+      context.index.statistic(Statistic::CompactedOutputsHeight),
+      12
+    );
+  }
 }
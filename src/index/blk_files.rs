@@ -0,0 +1,509 @@
+use {
+  super::*,
+  std::io::{BufReader, Read, Seek, SeekFrom},
+};
+
+/// Blocks that Bitcoin Core stores in `blocks/blk*.dat`.
+///
+/// Blocks are written to the files in the order they were downloaded, not in chain order, so the
+/// files are scanned for block headers to locate blocks by hash, and blocks are put in chain order
+/// by following the previous block hashes of their headers. Bitcoin Core's LevelDB block index in
+/// `blocks/index` is not read, instead the files are scanned once and then only from the end of
+/// the previous scan, so an index keeps its `BlkFiles` between updates.
+pub(crate) struct BlkFiles {
+  dir: PathBuf,
+  files: Vec<PathBuf>,
+  locations: HashMap<BlockHash, Location>,
+  magic: [u8; 4],
+  scanned: Vec<u64>,
+  xor: [u8; 8],
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+  file: usize,
+  offset: u64,
+  size: u32,
+  prev_blockhash: BlockHash,
+}
+
+impl BlkFiles {
+  pub(crate) fn open(dir: &Path, network: Network) -> Result<Self> {
+    ensure!(
+      dir.is_dir(),
+      "Bitcoin Core blocks directory {} not found",
+      dir.display()
+    );
+
+    // Bitcoin Core 28.0 and later obfuscate block files with the key in `xor.dat`.
+    let xor = match fs::read(dir.join("xor.dat")) {
+      Ok(key) => key
+        .try_into()
+        .map_err(|_| anyhow!("{} is not 8 bytes long", dir.join("xor.dat").display()))?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => [0; 8],
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut blk_files = Self {
+      dir: dir.into(),
+      files: Vec::new(),
+      locations: HashMap::new(),
+      magic: network.magic().to_bytes(),
+      scanned: Vec::new(),
+      xor,
+    };
+
+    blk_files.list_files()?;
+
+    Ok(blk_files)
+  }
+
+  /// Read the block with `hash`, `None` if it is not in the files.
+  pub(crate) fn block(&mut self, hash: BlockHash) -> Result<Option<Block>> {
+    if !self.locations.contains_key(&hash) {
+      self.scan()?;
+    }
+
+    let Some(location) = self.locations.get(&hash).copied() else {
+      return Ok(None);
+    };
+
+    let mut file = File::open(&self.files[location.file])?;
+    file.seek(SeekFrom::Start(location.offset))?;
+
+    let mut bytes = vec![0; usize::try_from(location.size).unwrap()];
+    self.read(&mut file, location.offset, &mut bytes)?;
+
+    let block = Block::consensus_decode(&mut bytes.as_slice())?;
+
+    ensure!(
+      block.block_hash() == hash,
+      "block {hash} in {} is corrupt",
+      self.files[location.file].display()
+    );
+
+    Ok(Some(block))
+  }
+
+  /// The hashes of `hash` and of up to `count - 1` of its ancestors, in chain order. Fewer hashes
+  /// are returned if an ancestor is not in the files.
+  pub(crate) fn ancestors(&mut self, hash: BlockHash, count: u32) -> Result<Vec<BlockHash>> {
+    if !self.locations.contains_key(&hash) {
+      self.scan()?;
+    }
+
+    let mut hashes = Vec::new();
+    let mut next = hash;
+    while hashes.len() < usize::try_from(count).unwrap() {
+      let Some(location) = self.locations.get(&next) else {
+        break;
+      };
+      hashes.push(next);
+      next = location.prev_blockhash;
+    }
+
+    hashes.reverse();
+
+    Ok(hashes)
+  }
+
+  fn list_files(&mut self) -> Result {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&self.dir)? {
+      let path = entry?.path();
+      let number = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("blk"))
+        .and_then(|name| name.strip_suffix(".dat"))
+        .and_then(|number| number.parse::<u32>().ok());
+      if let Some(number) = number {
+        files.push((number, path));
+      }
+    }
+
+    files.sort();
+
+    self.files = files.into_iter().map(|(_, path)| path).collect();
+    self.scanned.resize(self.files.len(), 0);
+
+    Ok(())
+  }
+
+  /// Locate the blocks written since the last scan.
+  fn scan(&mut self) -> Result {
+    self.list_files()?;
+
+    for file in 0..self.files.len() {
+      let path = self.files[file].clone();
+      let len = fs::metadata(&path)?.len();
+      let mut reader = BufReader::new(File::open(&path)?);
+      let mut offset = self.scanned[file];
+      reader.seek(SeekFrom::Start(offset))?;
+
+      // every block is preceded by the network magic and its size
+      while offset + 8 + 80 <= len {
+        let mut prefix = [0; 8];
+        reader.read_exact(&mut prefix)?;
+
+        // the unused space at the end of a file is preallocated with zeros, which are not
+        // obfuscated
+        if prefix[..4] == [0; 4] {
+          break;
+        }
+
+        self.deobfuscate(offset, &mut prefix);
+
+        ensure!(
+          prefix[..4] == self.magic,
+          "unexpected network magic at offset {offset} of {}",
+          path.display()
+        );
+
+        let size = u32::from_le_bytes(prefix[4..].try_into().unwrap());
+
+        // the block is still being written
+        if offset + 8 + u64::from(size) > len {
+          break;
+        }
+
+        let mut header = [0; 80];
+        self.read(&mut reader, offset + 8, &mut header)?;
+
+        let header = Header::consensus_decode(&mut header.as_slice())?;
+
+        self.locations.insert(
+          header.block_hash(),
+          Location {
+            file,
+            offset: offset + 8,
+            size,
+            prev_blockhash: header.prev_blockhash,
+          },
+        );
+
+        reader.seek_relative(i64::from(size) - 80)?;
+        offset += 8 + u64::from(size);
+      }
+
+      self.scanned[file] = offset;
+    }
+
+    Ok(())
+  }
+
+  /// Read `buf.len()` bytes found at `offset` of the file `reader` is positioned at.
+  fn read(&self, reader: &mut impl Read, offset: u64, buf: &mut [u8]) -> Result {
+    reader.read_exact(buf)?;
+    self.deobfuscate(offset, buf);
+    Ok(())
+  }
+
+  /// Undo the obfuscation of the bytes in `buf` found at `offset` of a file.
+  fn deobfuscate(&self, offset: u64, buf: &mut [u8]) {
+    if self.xor != [0; 8] {
+      for (i, byte) in buf.iter_mut().enumerate() {
+        *byte ^= self.xor[usize::try_from((offset + i as u64) % 8).unwrap()];
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  fn block(nonce: u32) -> Block {
+    Block {
+      header: Header {
+        version: bitcoin::block::Version::ONE,
+        prev_blockhash: BlockHash::all_zeros(),
+        merkle_root: bitcoin::hash_types::TxMerkleNode::all_zeros(),
+        time: 0,
+        bits: bitcoin::CompactTarget::from_consensus(0),
+        nonce,
+      },
+      txdata: vec![Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: Vec::new(),
+        output: vec![TxOut {
+          value: nonce.into(),
+          script_pubkey: ScriptBuf::new(),
+        }],
+      }],
+    }
+  }
+
+  fn write_blk_file(path: &Path, blocks: &[Block], xor: [u8; 8], padding: usize) {
+    let mut bytes = Vec::new();
+    for block in blocks {
+      let block = bitcoin::consensus::serialize(block);
+      bytes.extend_from_slice(&Network::Regtest.magic().to_bytes());
+      bytes.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+      bytes.extend_from_slice(&block);
+    }
+
+    for (i, byte) in bytes.iter_mut().enumerate() {
+      *byte ^= xor[i % 8];
+    }
+
+    // like Bitcoin Core, the preallocated space is not obfuscated
+    bytes.resize(bytes.len() + padding, 0);
+
+    fs::write(path, bytes).unwrap();
+  }
+
+  #[test]
+  fn blocks_are_located_by_hash_across_files() {
+    let tempdir = TempDir::new().unwrap();
+
+    write_blk_file(
+      &tempdir.path().join("blk00000.dat"),
+      &[block(0), block(1)],
+      [0; 8],
+      64,
+    );
+    write_blk_file(&tempdir.path().join("blk00001.dat"), &[block(2)], [0; 8], 0);
+
+    let mut blk_files = BlkFiles::open(tempdir.path(), Network::Regtest).unwrap();
+
+    for nonce in [2, 0, 1] {
+      assert_eq!(
+        blk_files.block(block(nonce).block_hash()).unwrap(),
+        Some(block(nonce))
+      );
+    }
+
+    assert_eq!(blk_files.block(block(3).block_hash()).unwrap(), None);
+
+    write_blk_file(&tempdir.path().join("blk00002.dat"), &[block(3)], [0; 8], 0);
+
+    assert_eq!(
+      blk_files.block(block(3).block_hash()).unwrap(),
+      Some(block(3))
+    );
+  }
+
+  #[test]
+  fn ancestors_follow_previous_block_hashes() {
+    let tempdir = TempDir::new().unwrap();
+
+    let mut chain = vec![block(0)];
+    for nonce in 1..4 {
+      let mut block = block(nonce);
+      block.header.prev_blockhash = chain.last().unwrap().block_hash();
+      chain.push(block);
+    }
+
+    let mut stale = block(4);
+    stale.header.prev_blockhash = chain[1].block_hash();
+
+    write_blk_file(
+      &tempdir.path().join("blk00000.dat"),
+      &[chain[2].clone(), stale, chain[0].clone()],
+      [0; 8],
+      0,
+    );
+    write_blk_file(
+      &tempdir.path().join("blk00001.dat"),
+      &[chain[1].clone(), chain[3].clone()],
+      [0; 8],
+      0,
+    );
+
+    let mut blk_files = BlkFiles::open(tempdir.path(), Network::Regtest).unwrap();
+
+    let hashes = chain.iter().map(Block::block_hash).collect::<Vec<_>>();
+
+    assert_eq!(blk_files.ancestors(hashes[3], 3).unwrap(), hashes[1..]);
+    assert_eq!(blk_files.ancestors(hashes[3], 10).unwrap(), hashes);
+    assert_eq!(
+      blk_files.ancestors(block(5).block_hash(), 10).unwrap(),
+      Vec::new()
+    );
+  }
+
+  #[test]
+  fn obfuscated_blocks_are_read_with_xor_key() {
+    let tempdir = TempDir::new().unwrap();
+
+    let xor = [1, 2, 3, 4, 5, 6, 7, 8];
+    fs::write(tempdir.path().join("xor.dat"), xor).unwrap();
+    write_blk_file(
+      &tempdir.path().join("blk00000.dat"),
+      &[block(0), block(1)],
+      xor,
+      0,
+    );
+
+    let mut blk_files = BlkFiles::open(tempdir.path(), Network::Regtest).unwrap();
+
+    assert_eq!(
+      blk_files.block(block(1).block_hash()).unwrap(),
+      Some(block(1))
+    );
+  }
+
+  #[test]
+  fn obfuscated_files_end_with_unobfuscated_zeros() {
+    let tempdir = TempDir::new().unwrap();
+
+    let xor = [1, 2, 3, 4, 5, 6, 7, 8];
+    fs::write(tempdir.path().join("xor.dat"), xor).unwrap();
+    write_blk_file(
+      &tempdir.path().join("blk00000.dat"),
+      &[block(0), block(1)],
+      xor,
+      1024,
+    );
+
+    let mut blk_files = BlkFiles::open(tempdir.path(), Network::Regtest).unwrap();
+
+    assert_eq!(blk_files.block(block(2).block_hash()).unwrap(), None);
+    assert_eq!(
+      blk_files.block(block(1).block_hash()).unwrap(),
+      Some(block(1))
+    );
+
+    // blocks written into the preallocated space are found by the next scan
+    write_blk_file(
+      &tempdir.path().join("blk00000.dat"),
+      &[block(0), block(1), block(2)],
+      xor,
+      512,
+    );
+
+    assert_eq!(
+      blk_files.block(block(2).block_hash()).unwrap(),
+      Some(block(2))
+    );
+  }
+
+  #[test]
+  fn blocks_of_other_networks_are_rejected() {
+    let tempdir = TempDir::new().unwrap();
+
+    write_blk_file(&tempdir.path().join("blk00000.dat"), &[block(0)], [0; 8], 0);
+
+    assert_eq!(
+      BlkFiles::open(tempdir.path(), Network::Bitcoin)
+        .unwrap()
+        .block(block(0).block_hash())
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "unexpected network magic at offset 0 of {}",
+        tempdir.path().join("blk00000.dat").display()
+      )
+    );
+  }
+
+  #[test]
+  fn missing_blocks_directory_is_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    assert_eq!(
+      BlkFiles::open(&tempdir.path().join("blocks"), Network::Regtest)
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "Bitcoin Core blocks directory {} not found",
+        tempdir.path().join("blocks").display()
+      )
+    );
+  }
+
+  fn blk_files_context() -> (Context, PathBuf) {
+    let tempdir = TempDir::new().unwrap();
+    let bitcoin_data_dir = tempdir.path().join("bitcoin");
+    let blocks_dir = bitcoin_data_dir.join("regtest/blocks");
+    fs::create_dir_all(&blocks_dir).unwrap();
+
+    let context = Context::builder()
+      .arg("--index-from-blk-files")
+      .arg(format!("--bitcoin-data-dir={}", bitcoin_data_dir.display()))
+      .tempdir(tempdir)
+      .build();
+
+    (context, blocks_dir)
+  }
+
+  #[test]
+  fn index_reads_blocks_from_blk_files() {
+    let (context, blocks_dir) = blk_files_context();
+
+    let mut blocks = context.rpc_server.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    blocks.extend(context.rpc_server.mine_blocks(10));
+
+    // files are not in chain order
+    blocks.reverse();
+    write_blk_file(&blocks_dir.join("blk00000.dat"), &blocks, [0; 8], 0);
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 12);
+    assert!(context
+      .index
+      .get_inscription_entry(InscriptionId { txid, index: 0 })
+      .unwrap()
+      .is_some());
+  }
+
+  #[test]
+  fn index_falls_back_to_rpc_when_blk_files_are_unreadable() {
+    let (context, blocks_dir) = blk_files_context();
+
+    fs::write(blocks_dir.join("blk00000.dat"), [1; 1024]).unwrap();
+
+    context.mine_blocks(10);
+
+    assert_eq!(context.index.block_count().unwrap(), 11);
+  }
+
+  #[test]
+  fn index_keeps_located_blocks_between_updates() {
+    let (context, blocks_dir) = blk_files_context();
+
+    let mut blocks = context.rpc_server.mine_blocks(10);
+    write_blk_file(&blocks_dir.join("blk00000.dat"), &blocks, [0; 8], 0);
+
+    context.index.update().unwrap();
+
+    // the first file is not scanned again
+    let len = fs::metadata(blocks_dir.join("blk00000.dat")).unwrap().len();
+    fs::write(
+      blocks_dir.join("blk00000.dat"),
+      vec![1; usize::try_from(len).unwrap()],
+    )
+    .unwrap();
+
+    let new = context.rpc_server.mine_blocks(10);
+    write_blk_file(&blocks_dir.join("blk00001.dat"), &new, [0; 8], 0);
+    blocks.extend(new);
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 21);
+
+    let mut blk_files = context.index.blk_files.lock().unwrap();
+    assert_eq!(
+      blk_files
+        .as_mut()
+        .unwrap()
+        .ancestors(blocks[13].block_hash(), 14)
+        .unwrap(),
+      blocks[..14]
+        .iter()
+        .map(Block::block_hash)
+        .collect::<Vec<BlockHash>>()
+    );
+  }
+}
@@ -0,0 +1,231 @@
+use {
+  super::*,
+  crate::okx::datastore::brc20::redb::table::get_block_checksum,
+  bitcoin::hashes::{sha256, Hash},
+  redb::{MultimapTableDefinition, RedbKey, RedbValue},
+  std::io::{BufReader, Read},
+};
+
+const BRC20_SNAPSHOT_VERSION: u32 = 2;
+
+/// Header of a BRC20 snapshot file, followed by the msgpack encoded `Brc20State`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Brc20SnapshotHeader {
+  version: u32,
+  height: u32,
+  block_hash: BlockHash,
+  block_checksum: Option<sha256::Hash>,
+  /// sha256 of the encoded state that follows the header.
+  checksum: sha256::Hash,
+}
+
+/// The rows of a table, keys and values in their stored encoding.
+type Rows = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Every BRC20 table by name, so that an imported index serves the same results as one that
+/// indexed the blocks before the snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+struct Brc20State {
+  tables: BTreeMap<String, Rows>,
+}
+
+fn export_table<K: RedbKey + 'static, V: RedbValue + 'static>(
+  rtx: &rtx::Rtx,
+  state: &mut Brc20State,
+  definition: TableDefinition<K, V>,
+) -> Result {
+  let rows = rtx
+    .0
+    .open_table(definition)?
+    .iter()?
+    .map(|result| {
+      result.map(|(key, value)| {
+        (
+          K::as_bytes(&key.value()).as_ref().to_vec(),
+          V::as_bytes(&value.value()).as_ref().to_vec(),
+        )
+      })
+    })
+    .collect::<Result<Rows, StorageError>>()?;
+  state.tables.insert(definition.name().into(), rows);
+  Ok(())
+}
+
+fn export_multimap_table<K: RedbKey + 'static, V: RedbKey + 'static>(
+  rtx: &rtx::Rtx,
+  state: &mut Brc20State,
+  definition: MultimapTableDefinition<K, V>,
+) -> Result {
+  let mut rows = Rows::new();
+  for result in rtx.0.open_multimap_table(definition)?.iter()? {
+    let (key, values) = result?;
+    let key = K::as_bytes(&key.value()).as_ref().to_vec();
+    for value in values {
+      rows.push((key.clone(), V::as_bytes(&value?.value()).as_ref().to_vec()));
+    }
+  }
+  state.tables.insert(definition.name().into(), rows);
+  Ok(())
+}
+
+fn import_table<K: RedbKey + 'static, V: RedbValue + 'static>(
+  wtx: &WriteTransaction,
+  state: &mut Brc20State,
+  definition: TableDefinition<K, V>,
+) -> Result {
+  let rows = state
+    .tables
+    .remove(definition.name())
+    .ok_or_else(|| anyhow!("BRC20 snapshot is missing table {}", definition.name()))?;
+  let mut table = wtx.open_table(definition)?;
+  ensure!(table.is_empty()?, "BRC20 state of the index is not empty");
+  for (key, value) in &rows {
+    table.insert(K::from_bytes(key), V::from_bytes(value))?;
+  }
+  Ok(())
+}
+
+fn import_multimap_table<K: RedbKey + 'static, V: RedbKey + 'static>(
+  wtx: &WriteTransaction,
+  state: &mut Brc20State,
+  definition: MultimapTableDefinition<K, V>,
+) -> Result {
+  let rows = state
+    .tables
+    .remove(definition.name())
+    .ok_or_else(|| anyhow!("BRC20 snapshot is missing table {}", definition.name()))?;
+  let mut table = wtx.open_multimap_table(definition)?;
+  ensure!(table.is_empty()?, "BRC20 state of the index is not empty");
+  for (key, value) in &rows {
+    table.insert(K::from_bytes(key), V::from_bytes(value))?;
+  }
+  Ok(())
+}
+
+impl Index {
+  pub(crate) fn brc20_export(&self, filename: &Path) -> Result {
+    let rtx = self.begin_read()?;
+
+    let height = rtx
+      .block_height()?
+      .ok_or_else(|| anyhow!("no blocks have been indexed"))?
+      .n();
+    let block_hash = rtx.block_hash(Some(height))?.unwrap();
+
+    log::info!("exporting BRC20 state at block {height} {block_hash} to {filename:?}");
+
+    let mut state = Brc20State {
+      tables: BTreeMap::new(),
+    };
+    export_table(&rtx, &mut state, BRC20_TOKEN)?;
+    export_table(&rtx, &mut state, BRC20_BALANCES)?;
+    export_table(&rtx, &mut state, BRC20_EVENTS)?;
+    export_table(&rtx, &mut state, BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
+    export_multimap_table(
+      &rtx,
+      &mut state,
+      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+    )?;
+    export_table(&rtx, &mut state, BRC20_ADDRESS_TO_RECEIPTS)?;
+    export_table(&rtx, &mut state, BRC20_TICKER_TO_HOLDERS)?;
+    export_table(&rtx, &mut state, BRC20_TICKER_TO_RECEIPTS)?;
+    export_table(&rtx, &mut state, BRC20_TICKER_TO_BURNS)?;
+    export_table(&rtx, &mut state, BRC20_BALANCE_JOURNAL)?;
+    export_table(&rtx, &mut state, BRC20_HEIGHT_TO_CHECKSUM)?;
+    let state = rmp_serde::to_vec(&state)?;
+
+    let header = Brc20SnapshotHeader {
+      version: BRC20_SNAPSHOT_VERSION,
+      height,
+      block_hash,
+      block_checksum: get_block_checksum(&rtx.0.open_table(BRC20_HEIGHT_TO_CHECKSUM)?, height)?,
+      checksum: sha256::Hash::hash(&state),
+    };
+
+    let mut writer = BufWriter::new(File::create(filename)?);
+    rmp_serde::encode::write(&mut writer, &header)?;
+    writer.write_all(&state)?;
+    writer.flush()?;
+
+    Ok(())
+  }
+
+  /// Restore the BRC20 state of a snapshot taken at the index tip, returning the checksum of the
+  /// snapshot block. BRC20 messages are indexed from the block following the snapshot on.
+  pub(crate) fn brc20_import(&self, filename: &Path) -> Result<Option<sha256::Hash>> {
+    let mut reader = BufReader::new(File::open(filename)?);
+
+    let header = rmp_serde::from_read::<_, Brc20SnapshotHeader>(&mut reader)?;
+    if header.version != BRC20_SNAPSHOT_VERSION {
+      bail!(
+        "unsupported BRC20 snapshot version {}, expected {BRC20_SNAPSHOT_VERSION}",
+        header.version
+      );
+    }
+
+    let mut state = Vec::new();
+    reader.read_to_end(&mut state)?;
+    if sha256::Hash::hash(&state) != header.checksum {
+      bail!("BRC20 snapshot checksum mismatch, the file is corrupted");
+    }
+    let mut state = rmp_serde::from_slice::<Brc20State>(&state)?;
+
+    let rtx = self.begin_read()?;
+    let height = rtx.block_height()?.map(|height| height.n());
+    if height != Some(header.height) || rtx.block_hash(height)? != Some(header.block_hash) {
+      bail!(
+        "BRC20 snapshot is taken at block {} {}, but the index is at block {}",
+        header.height,
+        header.block_hash,
+        height.map_or("none".to_string(), |height| height.to_string()),
+      );
+    }
+
+    log::info!(
+      "importing BRC20 state at block {} {} from {filename:?}",
+      header.height,
+      header.block_hash
+    );
+
+    let wtx = self.begin_write()?;
+
+    import_table(&wtx, &mut state, BRC20_TOKEN)?;
+    import_table(&wtx, &mut state, BRC20_BALANCES)?;
+    import_table(&wtx, &mut state, BRC20_EVENTS)?;
+    import_table(&wtx, &mut state, BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
+    import_multimap_table(
+      &wtx,
+      &mut state,
+      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+    )?;
+    import_table(&wtx, &mut state, BRC20_ADDRESS_TO_RECEIPTS)?;
+    import_table(&wtx, &mut state, BRC20_TICKER_TO_HOLDERS)?;
+    import_table(&wtx, &mut state, BRC20_TICKER_TO_RECEIPTS)?;
+    import_table(&wtx, &mut state, BRC20_TICKER_TO_BURNS)?;
+    import_table(&wtx, &mut state, BRC20_BALANCE_JOURNAL)?;
+    import_table(&wtx, &mut state, BRC20_HEIGHT_TO_CHECKSUM)?;
+
+    if let Some(name) = state.tables.keys().next() {
+      bail!("BRC20 snapshot has unknown table {name}");
+    }
+
+    let block_checksum =
+      get_block_checksum(&wtx.open_table(BRC20_HEIGHT_TO_CHECKSUM)?, header.height)?;
+    if block_checksum != header.block_checksum {
+      bail!(
+        "BRC20 checksum of block {} does not match the imported state",
+        header.height
+      );
+    }
+
+    Index::set_statistic(
+      &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+      Statistic::FirstBrc20Height,
+      u64::from(header.height + 1),
+    )?;
+
+    wtx.commit()?;
+
+    Ok(block_checksum)
+  }
+}
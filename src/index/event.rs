@@ -0,0 +1,54 @@
+use {
+  super::*,
+  crate::okx::datastore::{brc20::Receipt, ord::InscriptionOp},
+  tokio::sync::broadcast,
+};
+
+/// Pending notifications that have not been received by slow subscribers are dropped.
+const EVENT_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Notification published once the data it describes has been committed to the index.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub(crate) enum IndexEvent {
+  InscriptionOperation {
+    height: u32,
+    block_hash: BlockHash,
+    operation: InscriptionOp,
+  },
+  BRC20Receipt {
+    height: u32,
+    block_hash: BlockHash,
+    txid: Txid,
+    receipt: Receipt,
+  },
+  /// A `depth` block deep reorg was detected at `height`, the index has been rolled back to
+  /// `block_count` blocks.
+  Rollback {
+    height: u32,
+    depth: u32,
+    block_count: u32,
+  },
+}
+
+pub(crate) fn event_channel() -> broadcast::Sender<IndexEvent> {
+  broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
+
+impl Index {
+  pub(crate) fn subscribe_events(&self) -> broadcast::Receiver<IndexEvent> {
+    self.event_sender.subscribe()
+  }
+
+  pub(crate) fn has_event_subscribers(&self) -> bool {
+    self.event_sender.receiver_count() > 0
+  }
+
+  pub(crate) fn publish_events(&self, events: Vec<IndexEvent>) {
+    let sender = &self.event_sender;
+    for event in events {
+      // an error only means that nobody is listening.
+      sender.send(event).ok();
+    }
+  }
+}
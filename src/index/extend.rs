@@ -152,6 +152,27 @@ impl Index {
     })
   }
 
+  pub(crate) fn get_brc20_balance_at_by_tick_and_address(
+    tick: brc20::Tick,
+    script_key: ScriptKey,
+    height: u32,
+    rtx: &Rtx,
+  ) -> Result<Option<brc20::Balance>> {
+    Ok(
+      match rtx.brc20_get_balance_at_by_address(&tick, script_key, height)? {
+        Some(balance) => Some(balance),
+        None
+          if rtx
+            .brc20_get_tick_info(&tick)?
+            .map_or(false, |info| info.deployed_number <= height) =>
+        {
+          Some(brc20::Balance::new(&tick))
+        }
+        _ => None,
+      },
+    )
+  }
+
   pub(crate) fn get_brc20_transferable_utxo_by_tick_and_address(
     tick: brc20::Tick,
     script_key: ScriptKey,
@@ -0,0 +1,920 @@
+use {
+  super::*,
+  crate::okx::{
+    datastore::{
+      brc20::{Brc20Reader, Receipt},
+      ord::{redb::table::get_txout_by_outpoint, Action, InscriptionOp},
+    },
+    protocol::{
+      brc20::{execute, overlay::Brc20Overlay, ExecutionMessage, Message},
+      ChainContext,
+    },
+  },
+  redb::{ReadOnlyMultimapTable, ReadOnlyTable},
+  rtx::Rtx,
+};
+
+/// Inscription operations and BRC20 receipts of the unconfirmed transactions, as they would be if
+/// the transactions were mined in the block following the index tip.
+#[derive(Debug, Default)]
+pub(crate) struct Mempool {
+  /// The height of the block the transactions were indexed at.
+  pub(crate) height: u32,
+  pub(crate) operations: Vec<InscriptionOp>,
+  pub(crate) receipts: Vec<(Txid, Receipt)>,
+  /// The index tip the transactions were simulated on.
+  tip: Option<BlockHash>,
+  /// The transactions in the order they were simulated, each one after the ones it spends.
+  order: Vec<Txid>,
+  transactions: HashMap<Txid, Arc<Transaction>>,
+  simulations: HashMap<Txid, Arc<Simulation>>,
+  /// The confirmed outputs spent by the transactions, so that they are fetched only once.
+  prevouts: HashMap<OutPoint, TxOut>,
+  /// The number of transactions simulated by the last update, the others were reused.
+  simulated: usize,
+}
+
+/// What an unconfirmed transaction does to inscriptions. It stays valid as long as the index tip
+/// and the unconfirmed transactions it spends do not change.
+#[derive(Debug, Default)]
+struct Simulation {
+  /// The operations of the transaction, new inscriptions are numbered when the mempool is
+  /// published since their numbers depend on the transactions before them.
+  operations: Vec<InscriptionOp>,
+  /// The inscriptions left on the outputs of the transaction.
+  inscriptions: Vec<(SatPoint, InscriptionId)>,
+}
+
+struct Flotsam {
+  inscription_id: InscriptionId,
+  offset: u64,
+  old_satpoint: SatPoint,
+  from: ScriptKey,
+  origin: Origin,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Origin {
+  New {
+    cursed: bool,
+    inscription: Inscription,
+    pointer: Option<u64>,
+    unbound: bool,
+    vindicated: bool,
+  },
+  Old,
+}
+
+/// Simulates unconfirmed transactions on top of a read transaction, keeping the inscriptions they
+/// move in memory.
+struct Simulator<'a> {
+  index: &'a Index,
+  height: u32,
+  transactions: &'a HashMap<Txid, Arc<Transaction>>,
+  prevouts: HashMap<OutPoint, TxOut>,
+  /// The inscriptions on the outputs of the transactions simulated so far.
+  inscriptions: HashMap<OutPoint, Vec<(SatPoint, InscriptionId)>>,
+  /// Whether the inscriptions made by the transactions simulated so far are cursed or vindicated.
+  new_inscriptions: HashMap<InscriptionId, bool>,
+  id_to_sequence_number: ReadOnlyTable<'a, InscriptionIdValue, u32>,
+  outpoint_to_entry: ReadOnlyTable<'a, &'static OutPointValue, &'static [u8]>,
+  satpoint_to_sequence_number: ReadOnlyMultimapTable<'a, &'static SatPointValue, u32>,
+  sequence_number_to_entry: ReadOnlyTable<'a, u32, InscriptionEntryValue>,
+}
+
+impl<'a> Simulator<'a> {
+  fn new(
+    index: &'a Index,
+    rtx: &'a Rtx,
+    height: u32,
+    transactions: &'a HashMap<Txid, Arc<Transaction>>,
+    prevouts: HashMap<OutPoint, TxOut>,
+  ) -> Result<Self> {
+    Ok(Self {
+      index,
+      height,
+      transactions,
+      prevouts,
+      inscriptions: HashMap::new(),
+      new_inscriptions: HashMap::new(),
+      id_to_sequence_number: rtx.0.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?,
+      outpoint_to_entry: rtx.0.open_table(OUTPOINT_TO_ENTRY)?,
+      satpoint_to_sequence_number: rtx.0.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?,
+      sequence_number_to_entry: rtx.0.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?,
+    })
+  }
+
+  /// The output spent by an input, from the mempool, the index, or Bitcoin Core.
+  fn prevout(&mut self, outpoint: OutPoint) -> Result<TxOut> {
+    if let Some(transaction) = self.transactions.get(&outpoint.txid) {
+      return transaction
+        .output
+        .get(usize::try_from(outpoint.vout).unwrap())
+        .cloned()
+        .ok_or_else(|| {
+          anyhow!(
+            "mempool transaction {} has no output {outpoint}",
+            outpoint.txid
+          )
+        });
+    }
+
+    if let Some(tx_out) = self.prevouts.get(&outpoint) {
+      return Ok(tx_out.clone());
+    }
+
+    let tx_out = match get_txout_by_outpoint(&self.outpoint_to_entry, &outpoint)? {
+      Some(tx_out) => tx_out,
+      None => self
+        .index
+        .client
+        .get_raw_transaction(&outpoint.txid, None)?
+        .output
+        .into_iter()
+        .nth(usize::try_from(outpoint.vout).unwrap())
+        .ok_or_else(|| anyhow!("transaction {} has no output {outpoint}", outpoint.txid))?,
+    };
+
+    self.prevouts.insert(outpoint, tx_out.clone());
+
+    Ok(tx_out)
+  }
+
+  fn inscriptions_on_output(&self, outpoint: OutPoint) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    if self.transactions.contains_key(&outpoint.txid) {
+      return Ok(
+        self
+          .inscriptions
+          .get(&outpoint)
+          .cloned()
+          .unwrap_or_default(),
+      );
+    }
+
+    Index::inscriptions_on_output(
+      &self.satpoint_to_sequence_number,
+      &self.sequence_number_to_entry,
+      outpoint,
+    )
+  }
+
+  /// The sequence number and inscription number of a confirmed inscription.
+  fn confirmed_entry(&self, inscription_id: InscriptionId) -> Result<Option<InscriptionEntry>> {
+    let Some(sequence_number) = self.id_to_sequence_number.get(&inscription_id.store())? else {
+      return Ok(None);
+    };
+
+    Ok(
+      self
+        .sequence_number_to_entry
+        .get(sequence_number.value())?
+        .map(|entry| InscriptionEntry::load(entry.value())),
+    )
+  }
+
+  fn cursed_or_vindicated(&self, inscription_id: InscriptionId) -> Result<bool> {
+    if let Some(cursed_or_vindicated) = self.new_inscriptions.get(&inscription_id) {
+      return Ok(*cursed_or_vindicated);
+    }
+
+    Ok(
+      self
+        .confirmed_entry(inscription_id)?
+        .map(|entry| entry.inscription_number < 0 || Charm::Vindicated.is_set(entry.charms))
+        .unwrap_or_default(),
+    )
+  }
+
+  /// Move the inscriptions of `tx` the way the inscription updater would, new inscriptions are
+  /// left unnumbered and inscriptions paid as fees are lost.
+  fn simulate(&mut self, tx: &Transaction, txid: Txid) -> Result<Simulation> {
+    let envelopes = ParsedEnvelope::from_transaction(tx);
+
+    let input_inscriptions = tx
+      .input
+      .iter()
+      .map(|tx_in| self.inscriptions_on_output(tx_in.previous_output))
+      .collect::<Result<Vec<_>>>()?;
+
+    // the outputs spent by other transactions are not needed.
+    if envelopes.is_empty() && input_inscriptions.iter().all(Vec::is_empty) {
+      return Ok(Simulation::default());
+    }
+
+    let chain = self.index.options.chain();
+    let jubilant = self.height >= chain.jubilee_height();
+    let total_output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
+
+    let mut envelopes = envelopes.into_iter().peekable();
+    let mut floating_inscriptions = Vec::new();
+    let mut id_counter = 0;
+    let mut inscribed_offsets = BTreeMap::new();
+    let mut total_input_value = 0;
+
+    for ((input_index, tx_in), inscriptions) in tx.input.iter().enumerate().zip(input_inscriptions)
+    {
+      let prevout = self.prevout(tx_in.previous_output)?;
+      let from = ScriptKey::from_script(&prevout.script_pubkey, chain);
+
+      for (old_satpoint, inscription_id) in inscriptions {
+        let offset = total_input_value + old_satpoint.offset;
+        floating_inscriptions.push(Flotsam {
+          inscription_id,
+          offset,
+          old_satpoint,
+          from: from.clone(),
+          origin: Origin::Old,
+        });
+
+        inscribed_offsets
+          .entry(offset)
+          .or_insert((inscription_id, 0))
+          .1 += 1;
+      }
+
+      let offset = total_input_value;
+
+      total_input_value += prevout.value;
+
+      while let Some(inscription) = envelopes.peek() {
+        if inscription.input != u32::try_from(input_index).unwrap() {
+          break;
+        }
+
+        let inscription_id = InscriptionId {
+          txid,
+          index: id_counter,
+        };
+
+        let curse = inscription.payload.unrecognized_even_field
+          || inscription.payload.duplicate_field
+          || inscription.payload.incomplete_field
+          || inscription.input != 0
+          || inscription.offset != 0
+          || inscription.payload.pointer.is_some()
+          || inscription.pushnum
+          || inscription.stutter
+          || match inscribed_offsets.get(&offset) {
+            Some((_, count)) if *count > 1 => true,
+            Some((id, _)) => !self.cursed_or_vindicated(*id)?,
+            None => false,
+          };
+
+        let offset = inscription
+          .payload
+          .pointer()
+          .filter(|&pointer| pointer < total_output_value)
+          .unwrap_or(offset);
+
+        floating_inscriptions.push(Flotsam {
+          inscription_id,
+          offset,
+          old_satpoint: SatPoint {
+            outpoint: tx_in.previous_output,
+            offset: 0,
+          },
+          from: from.clone(),
+          origin: Origin::New {
+            cursed: curse && !jubilant,
+            inscription: inscription.payload.clone(),
+            pointer: inscription.payload.pointer(),
+            unbound: prevout.value == 0 || inscription.payload.unrecognized_even_field,
+            vindicated: curse && jubilant,
+          },
+        });
+
+        inscribed_offsets
+          .entry(offset)
+          .or_insert((inscription_id, 0))
+          .1 += 1;
+
+        envelopes.next();
+        id_counter += 1;
+      }
+    }
+
+    floating_inscriptions.sort_by_key(|flotsam| flotsam.offset);
+    let mut floating_inscriptions = floating_inscriptions.into_iter().peekable();
+
+    let mut range_to_vout = BTreeMap::new();
+    let mut new_locations = Vec::new();
+    let mut output_value = 0;
+    for (vout, tx_out) in tx.output.iter().enumerate() {
+      let end = output_value + tx_out.value;
+
+      while let Some(flotsam) = floating_inscriptions.peek() {
+        if flotsam.offset >= end {
+          break;
+        }
+
+        let new_satpoint = SatPoint {
+          outpoint: OutPoint {
+            txid,
+            vout: vout.try_into().unwrap(),
+          },
+          offset: flotsam.offset - output_value,
+        };
+
+        new_locations.push((new_satpoint, floating_inscriptions.next().unwrap()));
+      }
+
+      range_to_vout.insert((output_value, end), u32::try_from(vout).unwrap());
+
+      output_value = end;
+    }
+
+    let mut simulation = Simulation::default();
+
+    for (new_satpoint, flotsam) in new_locations {
+      let new_satpoint = match flotsam.origin {
+        Origin::New {
+          pointer: Some(pointer),
+          ..
+        } if pointer < output_value => range_to_vout
+          .iter()
+          .find_map(|((start, end), vout)| {
+            (pointer >= *start && pointer < *end).then(|| SatPoint {
+              outpoint: OutPoint { txid, vout: *vout },
+              offset: pointer - start,
+            })
+          })
+          .unwrap_or(new_satpoint),
+        _ => new_satpoint,
+      };
+
+      self.place(tx, txid, &mut simulation, flotsam, new_satpoint)?;
+    }
+
+    // the block is not known yet, so inscriptions paid as fees are lost at their offset in the fee.
+    for flotsam in floating_inscriptions {
+      let new_satpoint = SatPoint {
+        outpoint: OutPoint::null(),
+        offset: flotsam.offset - output_value,
+      };
+      self.place(tx, txid, &mut simulation, flotsam, new_satpoint)?;
+    }
+
+    Ok(simulation)
+  }
+
+  fn place(
+    &self,
+    tx: &Transaction,
+    txid: Txid,
+    simulation: &mut Simulation,
+    flotsam: Flotsam,
+    new_satpoint: SatPoint,
+  ) -> Result {
+    let unbound = matches!(flotsam.origin, Origin::New { unbound: true, .. });
+
+    let (sequence_number, inscription_number) = match flotsam.origin {
+      Origin::Old if !self.new_inscriptions.contains_key(&flotsam.inscription_id) => self
+        .confirmed_entry(flotsam.inscription_id)?
+        .map(|entry| (entry.sequence_number, Some(entry.inscription_number)))
+        .ok_or_else(|| anyhow!("inscription {} not found", flotsam.inscription_id))?,
+      _ => (0, None),
+    };
+
+    let (new_satpoint, to) = if unbound {
+      (
+        SatPoint {
+          outpoint: unbound_outpoint(),
+          offset: 0,
+        },
+        None,
+      )
+    } else if new_satpoint.outpoint == OutPoint::null() {
+      (new_satpoint, None)
+    } else {
+      simulation
+        .inscriptions
+        .push((new_satpoint, flotsam.inscription_id));
+      (
+        new_satpoint,
+        Some(ScriptKey::from_script(
+          &tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey,
+          self.index.options.chain(),
+        )),
+      )
+    };
+
+    simulation.operations.push(InscriptionOp {
+      txid,
+      action: match flotsam.origin {
+        Origin::Old => Action::Transfer,
+        Origin::New {
+          cursed,
+          inscription,
+          pointer: _,
+          unbound,
+          vindicated,
+        } => Action::New {
+          cursed,
+          unbound,
+          inscription,
+          vindicated,
+        },
+      },
+      sequence_number,
+      inscription_number,
+      inscription_id: flotsam.inscription_id,
+      old_satpoint: flotsam.old_satpoint,
+      new_satpoint: Some(new_satpoint),
+      from: flotsam.from,
+      to,
+    });
+
+    Ok(())
+  }
+
+  /// Record where a simulated transaction left its inscriptions for the transactions spending it.
+  fn apply(&mut self, tx: &Transaction, simulation: &Simulation) {
+    for tx_in in &tx.input {
+      self.inscriptions.remove(&tx_in.previous_output);
+    }
+
+    for (satpoint, inscription_id) in &simulation.inscriptions {
+      self
+        .inscriptions
+        .entry(satpoint.outpoint)
+        .or_default()
+        .push((*satpoint, *inscription_id));
+    }
+
+    for operation in &simulation.operations {
+      if let Action::New {
+        cursed, vindicated, ..
+      } = operation.action
+      {
+        self
+          .new_inscriptions
+          .insert(operation.inscription_id, cursed || vindicated);
+      }
+    }
+  }
+}
+
+impl Index {
+  /// The unconfirmed operations found by the last `update_mempool`, `None` if the mempool is not
+  /// tracked.
+  pub(crate) fn mempool(&self) -> Option<Arc<Mempool>> {
+    self.mempool.lock().unwrap().clone()
+  }
+
+  /// Start tracking the mempool, it is empty until the first `update_mempool`.
+  pub(crate) fn track_mempool(&self) -> Result {
+    self.mempool.lock().unwrap().replace(Arc::new(Mempool {
+      height: self.block_count()?,
+      ..Default::default()
+    }));
+
+    Ok(())
+  }
+
+  /// Simulate the transactions of the Bitcoin Core mempool on top of the index tip. Nothing is
+  /// written to the index, and only the transactions which are new or spend transactions that
+  /// changed are simulated again.
+  pub(crate) fn update_mempool(&self) -> Result {
+    let previous = self.mempool().unwrap_or_default();
+
+    let mut transactions = HashMap::new();
+    let mut new_txids = Vec::new();
+    for txid in self.client.get_raw_mempool()? {
+      let transaction = match previous.transactions.get(&txid) {
+        Some(transaction) => transaction.clone(),
+        // the transaction may have been mined or evicted in the meantime.
+        None => match self.client.get_raw_transaction(&txid, None) {
+          Ok(transaction) => {
+            new_txids.push(txid);
+            Arc::new(transaction)
+          }
+          Err(err) => {
+            log::debug!("failed to fetch mempool transaction {txid}: {err}");
+            continue;
+          }
+        },
+      };
+      transactions.insert(txid, transaction);
+    }
+
+    new_txids.sort();
+
+    let order = Self::spending_order(
+      previous
+        .order
+        .iter()
+        .filter(|txid| transactions.contains_key(*txid))
+        .chain(&new_txids)
+        .copied()
+        .collect(),
+      &transactions,
+    );
+
+    let rtx = self.begin_read()?;
+    let height = rtx.block_count()?;
+    let tip = rtx.block_hash(None)?;
+
+    let reusable = previous.tip == tip;
+
+    let mut simulator = Simulator::new(
+      self,
+      &rtx,
+      height,
+      &transactions,
+      if reusable {
+        previous.prevouts.clone()
+      } else {
+        HashMap::new()
+      },
+    )?;
+
+    let mut simulations = HashMap::new();
+    let mut simulated = HashSet::new();
+    for txid in &order {
+      let transaction = &transactions[txid];
+
+      let ancestors_changed = transaction.input.iter().any(|tx_in| {
+        let parent = tx_in.previous_output.txid;
+        simulated.contains(&parent)
+          || (previous.transactions.contains_key(&parent) && !transactions.contains_key(&parent))
+      });
+
+      let simulation = match previous.simulations.get(txid) {
+        Some(simulation) if reusable && !ancestors_changed => simulation.clone(),
+        _ => {
+          simulated.insert(*txid);
+          Arc::new(simulator.simulate(transaction, *txid)?)
+        }
+      };
+
+      simulator.apply(transaction, &simulation);
+      simulations.insert(*txid, simulation);
+    }
+
+    let operations = self.number_operations(&rtx, &order, &simulations)?;
+
+    let receipts = if self.options.enable_index_brc20 {
+      self.execute_brc20(&rtx, height, &order, &transactions, &operations)?
+    } else {
+      Vec::new()
+    };
+
+    // keep the fetched outputs which are still spent by the mempool.
+    let mut prevouts = mem::take(&mut simulator.prevouts);
+    drop(simulator);
+    let spent = transactions
+      .values()
+      .flat_map(|transaction| transaction.input.iter())
+      .map(|tx_in| tx_in.previous_output)
+      .collect::<HashSet<OutPoint>>();
+    prevouts.retain(|outpoint, _| spent.contains(outpoint));
+
+    let mempool = Mempool {
+      height,
+      operations: order
+        .iter()
+        .flat_map(|txid| operations.get(txid).cloned().unwrap_or_default())
+        .collect(),
+      receipts,
+      tip,
+      order,
+      transactions,
+      simulations,
+      prevouts,
+      simulated: simulated.len(),
+    };
+
+    log::info!(
+      "Simulated {} of {} mempool transactions",
+      mempool.simulated,
+      mempool.order.len()
+    );
+
+    self.mempool.lock().unwrap().replace(Arc::new(mempool));
+
+    Ok(())
+  }
+
+  /// Number the new inscriptions in the order of their transactions, as the block following the
+  /// index tip would.
+  fn number_operations(
+    &self,
+    rtx: &Rtx,
+    order: &[Txid],
+    simulations: &HashMap<Txid, Arc<Simulation>>,
+  ) -> Result<HashMap<Txid, Vec<InscriptionOp>>> {
+    let statistic_to_count = rtx.0.open_table(STATISTIC_TO_COUNT)?;
+    let statistic = |statistic: Statistic| -> Result<u64> {
+      Ok(
+        statistic_to_count
+          .get(&statistic.key())?
+          .map(|count| count.value())
+          .unwrap_or_default(),
+      )
+    };
+
+    let mut blessed_inscription_count = statistic(Statistic::BlessedInscriptions)?;
+    let mut cursed_inscription_count = statistic(Statistic::CursedInscriptions)?;
+    let mut unbound_inscriptions = statistic(Statistic::UnboundInscriptions)?;
+
+    let mut next_sequence_number = rtx
+      .0
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .iter()?
+      .next_back()
+      .transpose()?
+      .map(|(number, _entry)| number.value() + 1)
+      .unwrap_or(0);
+
+    let mut numbers = HashMap::new();
+    let mut operations = HashMap::new();
+    for txid in order {
+      let simulation = &simulations[txid];
+      if simulation.operations.is_empty() {
+        continue;
+      }
+
+      let mut tx_operations = simulation.operations.clone();
+      for operation in &mut tx_operations {
+        match operation.action {
+          Action::New {
+            cursed, unbound, ..
+          } => {
+            let inscription_number = if cursed {
+              let number = i32::try_from(cursed_inscription_count).unwrap();
+              cursed_inscription_count += 1;
+
+              // because cursed numbers start at -1
+              -(number + 1)
+            } else {
+              let number = i32::try_from(blessed_inscription_count).unwrap();
+              blessed_inscription_count += 1;
+
+              number
+            };
+
+            operation.sequence_number = next_sequence_number;
+            operation.inscription_number = Some(inscription_number);
+            next_sequence_number += 1;
+
+            if unbound {
+              operation.new_satpoint = Some(SatPoint {
+                outpoint: unbound_outpoint(),
+                offset: unbound_inscriptions,
+              });
+              unbound_inscriptions += 1;
+            }
+
+            numbers.insert(
+              operation.inscription_id,
+              (operation.sequence_number, inscription_number),
+            );
+          }
+          Action::Transfer => {
+            if let Some((sequence_number, inscription_number)) =
+              numbers.get(&operation.inscription_id)
+            {
+              operation.sequence_number = *sequence_number;
+              operation.inscription_number = Some(*inscription_number);
+            }
+          }
+        }
+      }
+
+      operations.insert(*txid, tx_operations);
+    }
+
+    Ok(operations)
+  }
+
+  /// Execute the BRC20 messages of the operations against the index state, keeping the changes in
+  /// memory.
+  fn execute_brc20(
+    &self,
+    rtx: &Rtx,
+    height: u32,
+    order: &[Txid],
+    transactions: &HashMap<Txid, Arc<Transaction>>,
+    operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<Vec<(Txid, Receipt)>> {
+    // an index restored from a BRC20 snapshot indexes BRC20 from the block following it.
+    let first_brc20_height = rtx
+      .0
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::FirstBrc20Height.key())?
+      .map(|height| u32::try_from(height.value()).unwrap())
+      .unwrap_or_else(|| self.options.first_brc20_height());
+
+    if height < first_brc20_height {
+      return Ok(Vec::new());
+    }
+
+    let chain_conf = ChainContext {
+      chain: self.options.chain(),
+      blockheight: height,
+      blocktime: u32::try_from(Utc::now().timestamp()).unwrap_or(u32::MAX),
+    };
+
+    let mut ledger = Brc20Overlay::open(rtx)?;
+
+    let mut receipts = Vec::new();
+    for txid in order {
+      let Some(tx_operations) = operations.get(txid) else {
+        continue;
+      };
+
+      let new_inscriptions = ParsedEnvelope::from_transaction(&transactions[txid])
+        .into_iter()
+        .map(|envelope| envelope.payload)
+        .collect::<Vec<Inscription>>();
+
+      // operations are resolved in input order, as the protocol manager does for blocks.
+      let mut messages = Vec::new();
+      let mut tx_operations = tx_operations.iter().peekable();
+      for tx_in in &transactions[txid].input {
+        while let Some(operation) = tx_operations
+          .next_if(|operation| operation.old_satpoint.outpoint == tx_in.previous_output)
+        {
+          let transfer_assets = ledger
+            .get_transferable_assets_by_outpoint(operation.old_satpoint.outpoint)?
+            .into_iter()
+            .map(|(satpoint, asset)| (satpoint.store(), asset))
+            .collect();
+
+          if let Some(message) = Message::resolve(operation, &new_inscriptions, transfer_assets)? {
+            messages.push((message, operation));
+          }
+        }
+      }
+
+      for (message, operation) in messages {
+        let execution_message = ExecutionMessage::new(
+          &message,
+          operation
+            .inscription_number
+            .ok_or_else(|| anyhow!("inscription {} is not numbered", operation.inscription_id))?,
+          operation.from.clone(),
+          if message.sat_in_outputs {
+            operation.to.clone()
+          } else {
+            None
+          },
+        )?;
+        receipts.push((
+          *txid,
+          execute(&chain_conf, &mut ledger, &execution_message)?,
+        ));
+      }
+    }
+
+    Ok(receipts)
+  }
+
+  /// Order `txids` so that each transaction follows the unconfirmed transactions it spends,
+  /// keeping the given order otherwise.
+  fn spending_order(txids: Vec<Txid>, transactions: &HashMap<Txid, Arc<Transaction>>) -> Vec<Txid> {
+    fn visit(
+      txid: Txid,
+      transactions: &HashMap<Txid, Arc<Transaction>>,
+      visited: &mut HashSet<Txid>,
+      ordered: &mut Vec<Txid>,
+    ) {
+      if !visited.insert(txid) {
+        return;
+      }
+
+      for input in &transactions[&txid].input {
+        if transactions.contains_key(&input.previous_output.txid) {
+          visit(input.previous_output.txid, transactions, visited, ordered);
+        }
+      }
+
+      ordered.push(txid);
+    }
+
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::new();
+    for txid in txids {
+      visit(txid, transactions, &mut visited, &mut ordered);
+    }
+
+    ordered
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn unchanged_transactions_are_not_simulated_again() {
+    let context = Context::builder().build();
+    context.mine_blocks(2);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    context.index.update_mempool().unwrap();
+    let mempool = context.index.mempool().unwrap();
+    assert_eq!(mempool.simulated, 1);
+    assert_eq!(mempool.operations.len(), 1);
+    assert_eq!(mempool.operations[0].inscription_number, Some(0));
+
+    context.index.update_mempool().unwrap();
+    let mempool = context.index.mempool().unwrap();
+    assert_eq!(mempool.simulated, 0);
+    assert_eq!(mempool.operations.len(), 1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..Default::default()
+    });
+
+    context.index.update_mempool().unwrap();
+    let mempool = context.index.mempool().unwrap();
+    assert_eq!(mempool.simulated, 1);
+    assert_eq!(mempool.operations.len(), 2);
+    assert_eq!(mempool.operations[1].txid, txid);
+    assert_eq!(mempool.operations[1].inscription_number, Some(1));
+    assert_eq!(mempool.operations[1].sequence_number, 1);
+
+    context.mine_blocks(1);
+
+    context.index.update_mempool().unwrap();
+    let mempool = context.index.mempool().unwrap();
+    assert_eq!(mempool.height, 4);
+    assert!(mempool.operations.is_empty());
+  }
+
+  #[test]
+  fn transfers_of_confirmed_inscriptions_are_simulated() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let inscribe = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let transfer = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.index.update_mempool().unwrap();
+    let mempool = context.index.mempool().unwrap();
+    assert_eq!(mempool.operations.len(), 1);
+
+    let operation = &mempool.operations[0];
+    assert_eq!(operation.txid, transfer);
+    assert_eq!(operation.action, Action::Transfer);
+    assert_eq!(
+      operation.inscription_id,
+      InscriptionId {
+        txid: inscribe,
+        index: 0
+      }
+    );
+    assert_eq!(operation.inscription_number, Some(0));
+    assert_eq!(
+      operation.old_satpoint,
+      SatPoint {
+        outpoint: OutPoint {
+          txid: inscribe,
+          vout: 0
+        },
+        offset: 0,
+      }
+    );
+    assert_eq!(
+      operation.new_satpoint,
+      Some(SatPoint {
+        outpoint: OutPoint {
+          txid: transfer,
+          vout: 0
+        },
+        offset: 0,
+      })
+    );
+  }
+
+  #[test]
+  fn mempool_is_simulated_while_the_index_is_written() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let wtx = context.index.begin_write().unwrap();
+    context.index.update_mempool().unwrap();
+    wtx.abort().unwrap();
+
+    assert_eq!(context.index.mempool().unwrap().operations.len(), 1);
+  }
+}
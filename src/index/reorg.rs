@@ -1,4 +1,4 @@
-use {super::*, updater::BlockData};
+use {super::*, crate::okx::datastore::brc20::Receipt, updater::BlockData};
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ReorgError {
@@ -19,9 +19,31 @@ impl fmt::Display for ReorgError {
 
 impl std::error::Error for ReorgError {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
+/// What a block added to the index, kept for the most recent blocks so that a rollback can tell
+/// which receipts and inscription operations were undone. It does not hold the values the block
+/// replaced, blocks are rolled back by restoring a savepoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BlockRecord {
+  pub(crate) height: u32,
+  pub(crate) block_hash: BlockHash,
+  /// Transactions with inscription operations, in block order.
+  pub(crate) txids: Vec<Txid>,
+  /// BRC20 receipts of those transactions.
+  pub(crate) receipts: Vec<(Txid, Vec<Receipt>)>,
+}
+
+/// A reorg that has been handled by rolling back the index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReorgRecord {
+  pub(crate) height: u32,
+  pub(crate) depth: u32,
+  /// Unix timestamp in seconds.
+  pub(crate) timestamp: i64,
+  /// Number of blocks left in the index after the rollback.
+  pub(crate) block_count: u32,
+  /// Indexed blocks that are no longer part of the best chain.
+  pub(crate) orphaned_blocks: Vec<BlockRecord>,
+}
 
 pub(crate) struct Reorg {}
 
@@ -32,8 +54,9 @@ impl Reorg {
     match index.block_hash(height.checked_sub(1))? {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
+        let savepoint_interval = index.options.savepoint_interval();
         let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+          (index.options.max_savepoints() - 1) * savepoint_interval + height % savepoint_interval;
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -60,30 +83,235 @@ impl Reorg {
       panic!("set index durability to `Durability::Immediate` to test reorg handling");
     }
 
+    // the common ancestor is `depth` blocks below `height`, every indexed block above it is orphaned.
+    let orphaned_blocks = index
+      .begin_read()?
+      .0
+      .open_table(HEIGHT_TO_BLOCK_RECORD)?
+      .range(height - depth + 1..)?
+      .map(|result| {
+        result.map(|(_, record)| rmp_serde::from_slice::<BlockRecord>(record.value()).unwrap())
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let wtx = index.begin_write()?;
+
+    let oldest_savepoint = wtx.list_persistent_savepoints()?.min().unwrap();
+    let block_count = wtx
+      .open_table(SAVEPOINT_TO_BLOCK_COUNT)?
+      .get(oldest_savepoint)?
+      .map(|block_count| block_count.value());
+    let reorgs = Self::reorg_records(&wtx)?;
+
+    // restoring a savepoint needs a transaction that has not opened any table yet.
+    wtx.abort()?;
+
     let mut wtx = index.begin_write()?;
+    Self::restore_savepoint(&mut wtx, oldest_savepoint, block_count, reorgs)?;
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
 
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let block_count = index.block_count()?;
 
-    wtx.restore_savepoint(&oldest_savepoint)?;
+    log::info!("successfully rolled back database to height {block_count}");
 
+    // the savepoint does not contain the reorg, so it is recorded once the rollback is committed.
+    let wtx = index.begin_write()?;
+    {
+      let mut reorgs = wtx.open_table(REORG_ID_TO_REORG)?;
+      let id = match reorgs.last()? {
+        Some((id, _)) => id.value() + 1,
+        None => 0,
+      };
+      reorgs.insert(
+        id,
+        rmp_serde::to_vec(&ReorgRecord {
+          height,
+          depth,
+          timestamp: Utc::now().timestamp(),
+          block_count,
+          orphaned_blocks,
+        })?
+        .as_slice(),
+      )?;
+    }
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.block_count()?
-    );
+    Ok(())
+  }
+
+  /// Restore the most recent savepoint at or below `height`, returning the new block count.
+  /// Blocks are not undone one at a time, so the index can go back no further than the oldest
+  /// savepoint that is still kept.
+  pub(crate) fn rollback(index: &Index, height: u32) -> Result<u32> {
+    if let redb::Durability::None = index.durability {
+      bail!("savepoints are not kept when index durability is `Durability::None`");
+    }
+
+    let wtx = index.begin_write()?;
+
+    let savepoints = {
+      let savepoint_to_block_count = wtx.open_table(SAVEPOINT_TO_BLOCK_COUNT)?;
+      let mut savepoints = Vec::new();
+      for savepoint in wtx.list_persistent_savepoints()? {
+        if let Some(block_count) = savepoint_to_block_count.get(savepoint)? {
+          savepoints.push((savepoint, block_count.value()));
+        }
+      }
+      savepoints.sort_unstable();
+      savepoints
+    };
+
+    let Some(&(savepoint, block_count)) = savepoints
+      .iter()
+      .rev()
+      .find(|(_, block_count)| *block_count <= height + 1)
+    else {
+      match savepoints.first() {
+        Some((_, block_count)) => bail!(
+          "cannot roll back to height {height}, the oldest savepoint is at height {}",
+          block_count - 1
+        ),
+        None => bail!("cannot roll back to height {height}, no savepoints have been created"),
+      }
+    };
+
+    let reorgs = Self::reorg_records(&wtx)?;
+
+    // restoring a savepoint needs a transaction that has not opened any table yet.
+    wtx.abort()?;
+
+    let mut wtx = index.begin_write()?;
+    Self::restore_savepoint(&mut wtx, savepoint, Some(block_count), reorgs)?;
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    log::info!("rolled back database to height {block_count}");
+
+    Ok(block_count)
+  }
+
+  /// The savepoint itself does not contain its block count nor the reorgs handled after it was
+  /// taken, they are recorded again once restored.
+  fn restore_savepoint(
+    wtx: &mut WriteTransaction,
+    savepoint: u64,
+    block_count: Option<u32>,
+    reorgs: Vec<(u32, Vec<u8>)>,
+  ) -> Result {
+    wtx.restore_savepoint(&wtx.get_persistent_savepoint(savepoint)?)?;
+    if let Some(block_count) = block_count {
+      wtx
+        .open_table(SAVEPOINT_TO_BLOCK_COUNT)?
+        .insert(savepoint, block_count)?;
+    }
+    let mut table = wtx.open_table(REORG_ID_TO_REORG)?;
+    for (id, reorg) in reorgs {
+      table.insert(id, reorg.as_slice())?;
+    }
+    Ok(())
+  }
+
+  fn reorg_records(wtx: &WriteTransaction) -> Result<Vec<(u32, Vec<u8>)>> {
+    Ok(
+      wtx
+        .open_table(REORG_ID_TO_REORG)?
+        .iter()?
+        .map(|result| result.map(|(id, reorg)| (id.value(), reorg.value().to_vec())))
+        .collect::<Result<Vec<_>, _>>()?,
+    )
+  }
+
+  pub(crate) fn save_block_record(
+    index: &Index,
+    wtx: &WriteTransaction,
+    record: &BlockRecord,
+  ) -> Result {
+    let mut table = wtx.open_table(HEIGHT_TO_BLOCK_RECORD)?;
+    table.insert(record.height, rmp_serde::to_vec(record)?.as_slice())?;
+
+    // blocks deeper than the oldest savepoint can not be rolled back, their records are dropped.
+    if let Some(oldest) = record.height.checked_sub(Self::rollback_depth(index)) {
+      for result in table.drain(..=oldest)? {
+        result?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Queue the outputs spent at `height` for deletion from `OUTPOINT_TO_ENTRY`, and delete the
+  /// ones spent by blocks that have become deeper than the oldest savepoint.
+  pub(crate) fn prune_spent_outputs(
+    index: &Index,
+    wtx: &WriteTransaction,
+    outpoint_to_entry: &mut Table<&OutPointValue, &[u8]>,
+    statistic_to_count: &mut Table<u64, u64>,
+    height: u32,
+    spent: impl Iterator<Item = OutPoint>,
+  ) -> Result {
+    // an index built before spent outputs were pruned stays behind until it is compacted.
+    let compacted = statistic_to_count
+      .get(&Statistic::CompactedOutputsHeight.key())?
+      .map(|x| x.value())
+      .unwrap_or_default()
+      .max(index.first_inscription_height.into());
+    if compacted == u64::from(height) {
+      Index::set_statistic(
+        statistic_to_count,
+        Statistic::CompactedOutputsHeight,
+        u64::from(height + 1),
+      )?;
+    }
+
+    let mut table = wtx.open_table(HEIGHT_TO_SPENT_OUTPOINTS)?;
+
+    let mut outpoints = Vec::new();
+    for outpoint in spent {
+      outpoints.extend_from_slice(&outpoint.store());
+    }
+    table.insert(height, outpoints.as_slice())?;
+
+    let Some(oldest) = height.checked_sub(Self::rollback_depth(index)) else {
+      return Ok(());
+    };
+
+    let mut pruned = 0;
+    for result in table.drain(..=oldest)? {
+      let (_, outpoints) = result?;
+      for outpoint in outpoints
+        .value()
+        .chunks_exact(mem::size_of::<OutPointValue>())
+      {
+        let outpoint: &OutPointValue = outpoint.try_into().unwrap();
+        if outpoint_to_entry.remove(outpoint)?.is_some() {
+          pruned += 1;
+        }
+      }
+    }
+
+    log::debug!("pruned {pruned} spent outputs at height {height}");
 
     Ok(())
   }
 
+  /// How many blocks below the tip can still be rolled back to a savepoint.
+  pub(crate) fn rollback_depth(index: &Index) -> u32 {
+    index.options.max_savepoints() * index.options.savepoint_interval()
+  }
+
   pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
     if let redb::Durability::None = index.durability {
       return Ok(());
     }
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
+    let max_savepoints = index.options.max_savepoints();
+    let savepoint_interval = index.options.savepoint_interval();
+    let chain_tip_distance = max_savepoints * savepoint_interval + 1;
+
+    if (height < savepoint_interval || height % savepoint_interval == 0)
       && u32::try_from(
         index
           .options
@@ -93,14 +321,17 @@ impl Reorg {
       )
       .unwrap()
       .saturating_sub(height)
-        <= CHAIN_TIP_DISTANCE
+        <= chain_tip_distance
     {
       let wtx = index.begin_write()?;
 
-      let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+      let mut savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+      savepoints.sort_unstable();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
-        wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
+      // make room for the new savepoint, also dropping the excess if the maximum was lowered.
+      let excess = (savepoints.len() + 1).saturating_sub(usize::try_from(max_savepoints).unwrap());
+      for savepoint in savepoints.into_iter().take(excess) {
+        wtx.delete_persistent_savepoint(savepoint)?;
       }
 
       Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
@@ -109,7 +340,10 @@ impl Reorg {
       let wtx = index.begin_write()?;
 
       log::debug!("creating savepoint at height {}", height);
-      wtx.persistent_savepoint()?;
+      let savepoint = wtx.persistent_savepoint()?;
+      wtx
+        .open_table(SAVEPOINT_TO_BLOCK_COUNT)?
+        .insert(savepoint, height)?;
 
       Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
       wtx.commit()?;
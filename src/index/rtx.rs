@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, std::ops::Range};
 
 pub(crate) struct Rtx<'a>(pub(crate) redb::ReadTransaction<'a>);
 
@@ -61,6 +61,33 @@ impl Rtx<'_> {
     )
   }
 
+  /// The number of handled reorgs.
+  pub(crate) fn reorg_count(&self) -> Result<usize> {
+    Ok(usize::try_from(self.0.open_table(REORG_ID_TO_REORG)?.len()?).unwrap())
+  }
+
+  /// The reorgs at the given positions, counted from the most recent one.
+  pub(crate) fn reorgs(&self, positions: Range<usize>) -> Result<Vec<(u32, ReorgRecord)>> {
+    Ok(
+      self
+        .0
+        .open_table(REORG_ID_TO_REORG)?
+        .iter()?
+        .rev()
+        .skip(positions.start)
+        .take(positions.len())
+        .map(|result| {
+          result.map(|(id, reorg)| {
+            (
+              id.value(),
+              rmp_serde::from_slice::<ReorgRecord>(reorg.value()).unwrap(),
+            )
+          })
+        })
+        .collect::<Result<Vec<_>, _>>()?,
+    )
+  }
+
   pub(crate) fn inscription_id_to_sequence_number(
     &self,
     inscription_id: InscriptionId,
@@ -174,6 +201,88 @@ impl Rtx<'_> {
     get_collection_inscription_id(&table, &district.to_collection_key())
   }
 
+  pub(crate) fn ord_name_to_inscription_id(&self, name: &str) -> Result<Option<InscriptionId>> {
+    let table = self.0.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?;
+    get_collection_inscription_id(&table, &ord::names::Namespace::to_collection_key(name))
+  }
+
+  /// The script key of the output holding the inscription, `None` if it has been lost.
+  pub(crate) fn inscription_owner(
+    &self,
+    inscription_id: InscriptionId,
+    chain: Chain,
+  ) -> Result<Option<ScriptKey>> {
+    let Some(satpoint) = Index::get_inscription_satpoint_by_id_with_rtx(inscription_id, self)?
+    else {
+      return Ok(None);
+    };
+
+    if satpoint.outpoint == OutPoint::null() || satpoint.outpoint == unbound_outpoint() {
+      return Ok(None);
+    }
+
+    Ok(
+      self
+        .outpoint_to_entry(satpoint.outpoint)?
+        .map(|tx_out| ScriptKey::from_script(&tx_out.script_pubkey, chain)),
+    )
+  }
+
+  /// The number of indexed districts.
+  pub(crate) fn bitmap_count_districts(&self) -> Result<u64> {
+    Ok(
+      self
+        .0
+        .open_table(BITMAP_DISTRICT_TO_INSCRIPTION_ID)?
+        .len()?,
+    )
+  }
+
+  /// The districts at the given positions in ascending order.
+  pub(crate) fn bitmap_get_districts(
+    &self,
+    positions: Range<usize>,
+  ) -> Result<Vec<(u32, InscriptionId)>> {
+    let table = self.0.open_table(BITMAP_DISTRICT_TO_INSCRIPTION_ID)?;
+    get_districts(&table, positions.start, positions.len())
+  }
+
+  pub(crate) fn bitmap_get_district(
+    &self,
+    number: u32,
+  ) -> Result<Option<(InscriptionId, Option<ScriptKey>)>> {
+    let table = self.0.open_table(BITMAP_DISTRICT_TO_INSCRIPTION_ID)?;
+    let Some(inscription_id) = get_district_inscription_id(&table, number)? else {
+      return Ok(None);
+    };
+
+    let table = self.0.open_table(BITMAP_DISTRICT_TO_OWNER)?;
+    Ok(Some((inscription_id, get_district_owner(&table, number)?)))
+  }
+
+  pub(crate) fn bitmap_get_inscription_district(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<u32>> {
+    let table = self.0.open_table(BITMAP_INSCRIPTION_ID_TO_DISTRICT)?;
+    get_inscription_district(&table, &inscription_id)
+  }
+
+  pub(crate) fn bitmap_count_districts_by_owner(&self, owner: &ScriptKey) -> Result<u64> {
+    let table = self.0.open_multimap_table(BITMAP_OWNER_TO_DISTRICTS)?;
+    Ok(get_districts_by_owner(&table, owner, 0, 0)?.1)
+  }
+
+  /// The districts currently owned by the script key at the given positions in ascending order.
+  pub(crate) fn bitmap_get_districts_by_owner(
+    &self,
+    owner: &ScriptKey,
+    positions: Range<usize>,
+  ) -> Result<Vec<u32>> {
+    let table = self.0.open_multimap_table(BITMAP_OWNER_TO_DISTRICTS)?;
+    Ok(get_districts_by_owner(&table, owner, positions.start, positions.len())?.0)
+  }
+
   pub(crate) fn ord_transaction_id_to_inscription_operations(
     &self,
     txid: Txid,
@@ -192,6 +301,49 @@ impl Rtx<'_> {
     get_tokens_info(&table)
   }
 
+  /// The holders of the ticker at the given positions, by descending overall balance.
+  pub(crate) fn brc20_get_tick_holders(
+    &self,
+    tick: &brc20::Tick,
+    positions: Range<usize>,
+  ) -> Result<Vec<(ScriptKey, brc20::Balance)>> {
+    let table = self.0.open_table(BRC20_TICKER_TO_HOLDERS)?;
+    get_tick_holders(&table, tick, positions.start, positions.len())
+  }
+
+  pub(crate) fn brc20_get_tick_receipts(
+    &self,
+    tick: &brc20::Tick,
+    from_height: u32,
+    to_height: u32,
+    cursor: Option<&str>,
+    limit: usize,
+  ) -> Result<(Vec<(u32, Txid, brc20::Receipt)>, Option<String>)> {
+    let table = self.0.open_table(BRC20_TICKER_TO_RECEIPTS)?;
+    get_tick_receipts(&table, tick, from_height, to_height, cursor, limit)
+  }
+
+  pub(crate) fn brc20_get_tick_burns(
+    &self,
+    tick: &brc20::Tick,
+    from_height: u32,
+    to_height: u32,
+    cursor: Option<&str>,
+    limit: usize,
+  ) -> Result<(Vec<(u32, Txid, brc20::Receipt)>, Option<String>)> {
+    let burn_table = self.0.open_table(BRC20_TICKER_TO_BURNS)?;
+    let receipt_table = self.0.open_table(BRC20_TICKER_TO_RECEIPTS)?;
+    get_tick_burns(
+      &burn_table,
+      &receipt_table,
+      tick,
+      from_height,
+      to_height,
+      cursor,
+      limit,
+    )
+  }
+
   pub(crate) fn brc20_get_balance_by_address(
     &self,
     tick: &brc20::Tick,
@@ -201,6 +353,24 @@ impl Rtx<'_> {
     get_balance(&table, &script_key, tick)
   }
 
+  pub(crate) fn brc20_get_balance_at_by_address(
+    &self,
+    tick: &brc20::Tick,
+    script_key: ScriptKey,
+    height: u32,
+  ) -> Result<Option<brc20::Balance>> {
+    let table = self.0.open_table(BRC20_BALANCE_JOURNAL)?;
+    get_balance_at(&table, &script_key, tick, height)
+  }
+
+  pub(crate) fn brc20_get_block_checksum(
+    &self,
+    height: u32,
+  ) -> Result<Option<bitcoin::hashes::sha256::Hash>> {
+    let table = self.0.open_table(BRC20_HEIGHT_TO_CHECKSUM)?;
+    get_block_checksum(&table, height)
+  }
+
   pub(crate) fn brc20_get_all_balance_by_address(
     &self,
     script_key: ScriptKey,
@@ -217,6 +387,20 @@ impl Rtx<'_> {
     get_transaction_receipts(&table, &txid)
   }
 
+  pub(crate) fn brc20_get_address_receipts<F>(
+    &self,
+    script_key: ScriptKey,
+    cursor: Option<&str>,
+    limit: usize,
+    filter: F,
+  ) -> Result<(Vec<(u32, Txid, brc20::Receipt)>, Option<String>)>
+  where
+    F: Fn(&brc20::Receipt) -> bool,
+  {
+    let table = self.0.open_table(BRC20_ADDRESS_TO_RECEIPTS)?;
+    get_address_receipts(&table, &script_key, cursor, limit, filter)
+  }
+
   pub(crate) fn brc20_get_tick_transferable_by_address(
     &self,
     tick: &brc20::Tick,
@@ -1,18 +1,23 @@
-use crate::okx::protocol::{context::Context, ChainContext, ProtocolConfig, ProtocolManager};
+use crate::okx::protocol::{
+  brc20::Brc20Protocol, context::Context, ChainContext, ProtocolConfig, ProtocolManager,
+};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
-  super::{fetcher::Fetcher, *},
+  super::{blk_files::BlkFiles, event::IndexEvent, fetcher::Fetcher, *},
   futures::future::try_join_all,
   std::sync::mpsc,
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
 };
 
 pub(crate) mod inscription_updater;
-use crate::okx::lru::SimpleLru;
+use crate::{metrics::BlockMetrics, okx::lru::SimpleLru};
 
 mod rune_updater;
 
+/// Blocks within this distance of the chain tip are not read from the blk files.
+const BLK_FILES_TIP_DISTANCE: u32 = 6;
+
 pub(crate) struct BlockData {
   pub(crate) header: Header,
   pub(crate) txdata: Vec<(Transaction, Txid)>,
@@ -42,6 +47,7 @@ pub(crate) struct Updater<'index> {
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
   outputs_traversed: u64,
+  pending_events: Vec<IndexEvent>,
 }
 
 impl<'index> Updater<'_> {
@@ -54,12 +60,14 @@ impl<'index> Updater<'_> {
       outputs_cached: 0,
       outputs_inserted_since_flush: 0,
       outputs_traversed: 0,
+      pending_events: Vec::new(),
     })
   }
 
   pub(crate) fn update_index(&mut self) -> Result {
     let mut wtx = self.index.begin_write()?;
     let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
+    metrics::CHAIN_TIP_LAG.set(starting_height.saturating_sub(self.height).into());
 
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
@@ -112,6 +120,8 @@ impl<'index> Updater<'_> {
         &mut tx_out_cache,
       )?;
 
+      metrics::CHAIN_TIP_LAG.set(starting_height.saturating_sub(self.height).into());
+
       if let Some(progress_bar) = &mut progress_bar {
         progress_bar.inc(1);
 
@@ -191,6 +201,39 @@ impl<'index> Updater<'_> {
 
     let first_inscription_height = index.first_inscription_height;
 
+    // blocks close to the tip may still be reorganized, they are always fetched over RPC. The
+    // older ones are put in chain order by following the headers in the blk files back from the
+    // deepest of them.
+    let mut blk_files = if index.options.index_from_blk_files {
+      let mut files = index.blk_files.lock().unwrap();
+      let files = match &mut *files {
+        Some(files) => files,
+        None => files.insert(BlkFiles::open(
+          &index.options.bitcoin_data_dir()?.join("blocks"),
+          index.options.chain().network(),
+        )?),
+      };
+      let tip = u32::try_from(client.get_block_count()?).unwrap();
+      match tip.checked_sub(BLK_FILES_TIP_DISTANCE) {
+        Some(last) if last >= height => {
+          let hash = client.get_block_hash(last.into())?;
+          match files.ancestors(hash, last - height + 1) {
+            Ok(hashes) => {
+              let first = last + 1 - u32::try_from(hashes.len()).unwrap();
+              Some((index.blk_files.clone(), first, hashes))
+            }
+            Err(err) => {
+              log::warn!("failed to scan blk files, fetching blocks over RPC: {err}");
+              None
+            }
+          }
+        }
+        _ => None,
+      }
+    } else {
+      None
+    };
+
     thread::spawn(move || loop {
       if let Some(height_limit) = height_limit {
         if height >= height_limit {
@@ -198,7 +241,41 @@ impl<'index> Updater<'_> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      let mut block = None;
+      if let Some((files, first, hashes)) = &mut blk_files {
+        let hash = height
+          .checked_sub(*first)
+          .and_then(|i| hashes.get(usize::try_from(i).unwrap()))
+          .copied();
+        if let Some(hash) = hash {
+          let mut files = files.lock().unwrap();
+          match files.as_mut().map(|files| files.block(hash)) {
+            Some(Ok(found)) => block = found,
+            Some(Err(err)) => {
+              log::warn!(
+                "failed to read block {height} from blk files, fetching blocks over RPC: {err}"
+              );
+              // the files are scanned again from the start by the next update.
+              *files = None;
+              drop(files);
+              blk_files = None;
+            }
+            None => {}
+          }
+        }
+      }
+
+      let result = match block {
+        Some(mut block) => {
+          if !index_sats && height < first_inscription_height {
+            block.txdata.clear();
+          }
+          Ok(Some(block))
+        }
+        None => Self::get_block_with_retries(&client, height, index_sats, first_inscription_height),
+      };
+
+      match result {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -346,6 +423,7 @@ impl<'index> Updater<'_> {
     let start = Instant::now();
     let mut sat_ranges_written = 0;
     let mut outputs_in_block = 0;
+    let mut metrics = BlockMetrics::default();
 
     // If value_receiver still has values something went wrong with the last block
     // Could be an assert, shouldn't recover from this and commit the last block
@@ -422,6 +500,17 @@ impl<'index> Updater<'_> {
       start.elapsed().as_millis(),
     );
 
+    metrics.phase("previous_outputs", start.elapsed());
+    metrics.lru_lookups(
+      cache_outputs_count
+        .load(Ordering::Relaxed)
+        .try_into()
+        .unwrap(),
+      (miss_outputs_count.load(Ordering::Relaxed) + fetching_outputs_count.load(Ordering::Relaxed))
+        .try_into()
+        .unwrap(),
+    );
+
     let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
@@ -458,6 +547,12 @@ impl<'index> Updater<'_> {
       .map(|unbound_inscriptions| unbound_inscriptions.value())
       .unwrap_or(0);
 
+    // an index restored from a BRC20 snapshot indexes BRC20 from the block following it.
+    let first_brc20_height = statistic_to_count
+      .get(&Statistic::FirstBrc20Height.key())?
+      .map(|height| u32::try_from(height.value()).unwrap())
+      .unwrap_or_else(|| self.index.options.first_brc20_height());
+
     let next_sequence_number = sequence_number_to_inscription_entry
       .iter()?
       .next_back()
@@ -588,6 +683,7 @@ impl<'index> Updater<'_> {
       }
     }
     let ord_cost = start_time.elapsed().as_millis();
+    metrics.phase("inscriptions", start_time.elapsed());
 
     if index_inscriptions {
       height_to_last_sequence_number
@@ -629,24 +725,111 @@ impl<'index> Updater<'_> {
       tx_out_cache,
       hit: 0,
       miss: 0,
+      wtx,
       ORD_TX_TO_OPERATIONS: &mut wtx.open_table(ORD_TX_TO_OPERATIONS)?,
       COLLECTIONS_KEY_TO_INSCRIPTION_ID: &mut wtx.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?,
       COLLECTIONS_INSCRIPTION_ID_TO_KINDS: &mut wtx
         .open_multimap_table(COLLECTIONS_INSCRIPTION_ID_TO_KINDS)?,
+      BITMAP_DISTRICT_TO_INSCRIPTION_ID: &mut wtx.open_table(BITMAP_DISTRICT_TO_INSCRIPTION_ID)?,
+      BITMAP_INSCRIPTION_ID_TO_DISTRICT: &mut wtx.open_table(BITMAP_INSCRIPTION_ID_TO_DISTRICT)?,
+      BITMAP_DISTRICT_TO_OWNER: &mut wtx.open_table(BITMAP_DISTRICT_TO_OWNER)?,
+      BITMAP_OWNER_TO_DISTRICTS: &mut wtx.open_multimap_table(BITMAP_OWNER_TO_DISTRICTS)?,
       SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY: &mut sequence_number_to_inscription_entry,
       OUTPOINT_TO_ENTRY: &mut outpoint_to_entry,
-      BRC20_BALANCES: &mut wtx.open_table(BRC20_BALANCES)?,
-      BRC20_TOKEN: &mut wtx.open_table(BRC20_TOKEN)?,
-      BRC20_EVENTS: &mut wtx.open_table(BRC20_EVENTS)?,
-      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: &mut wtx
-        .open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
-      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS: &mut wtx
-        .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
     };
 
     // Create a protocol manager to index the block of bitmap data.
     let config = ProtocolConfig::new_with_options(&index.options);
-    ProtocolManager::new(config).index_block(&mut context, &block, operations)?;
+    let block_hash = block.header.block_hash();
+    let txids = block
+      .txdata
+      .iter()
+      .map(|(_, txid)| *txid)
+      .filter(|txid| operations.contains_key(txid))
+      .collect::<Vec<_>>();
+    let mut block_operations = if index.has_event_subscribers() {
+      Some(operations.clone())
+    } else {
+      None
+    };
+    let mut protocol_manager = ProtocolManager::new(config);
+    if index.options.enable_index_brc20 {
+      protocol_manager.register(Brc20Protocol::new(first_brc20_height));
+    }
+    let start_time = Instant::now();
+    protocol_manager.index_block(&mut context, &block, operations, &mut metrics)?;
+    metrics.phase("protocols", start_time.elapsed());
+
+    let mut receipts = Vec::new();
+    {
+      let brc20_events = wtx.open_table(BRC20_EVENTS)?;
+      for txid in &txids {
+        if let Some(tx_receipts) = get_transaction_receipts(&brc20_events, txid)? {
+          receipts.push((*txid, tx_receipts));
+        }
+      }
+    }
+
+    let (hit, miss) = (context.hit, context.miss);
+    metrics.lru_lookups(hit, miss);
+
+    // outputs are only written while inscriptions are indexed, so are the ones they spend.
+    if index_inscriptions {
+      Reorg::prune_spent_outputs(
+        self.index,
+        wtx,
+        &mut outpoint_to_entry,
+        &mut statistic_to_count,
+        self.height,
+        block
+          .txdata
+          .iter()
+          .flat_map(|(tx, _)| tx.input.iter())
+          .map(|input| input.previous_output)
+          .filter(|outpoint| !outpoint.is_null()),
+      )?;
+    }
+
+    // queue notifications for subscribers, they are published once the block is committed.
+    if let Some(block_operations) = &mut block_operations {
+      for txid in &txids {
+        self.pending_events.extend(
+          block_operations
+            .remove(txid)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|operation| IndexEvent::InscriptionOperation {
+              height: self.height,
+              block_hash,
+              operation,
+            }),
+        );
+        for (_, tx_receipts) in receipts
+          .iter()
+          .filter(|(receipt_txid, _)| receipt_txid == txid)
+        {
+          self
+            .pending_events
+            .extend(tx_receipts.iter().map(|receipt| IndexEvent::BRC20Receipt {
+              height: self.height,
+              block_hash,
+              txid: *txid,
+              receipt: receipt.clone(),
+            }));
+        }
+      }
+    }
+
+    Reorg::save_block_record(
+      self.index,
+      wtx,
+      &BlockRecord {
+        height: self.height,
+        block_hash,
+        txids,
+        receipts,
+      },
+    )?;
 
     if index.index_runes && self.height >= self.index.options.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
@@ -697,6 +880,9 @@ impl<'index> Updater<'_> {
 
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
+    metrics.phase("block", start.elapsed());
+    metrics.observe(self.height);
+
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
@@ -704,8 +890,8 @@ impl<'index> Updater<'_> {
       "Wrote {sat_ranges_written} sat ranges from {outputs_in_block} outputs in {}/{} ms, hit miss: {}/{}",
       ord_cost,
       (Instant::now() - start).as_millis(),
-      context.hit,
-      context.miss,
+      hit,
+      miss,
     );
 
     Ok(())
@@ -808,7 +994,13 @@ impl<'index> Updater<'_> {
     Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
     self.sat_ranges_since_flush = 0;
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    let start = Instant::now();
     wtx.commit()?;
+    metrics::COMMIT_DURATION.observe(start.elapsed().as_secs_f64());
+
+    self
+      .index
+      .publish_events(mem::take(&mut self.pending_events));
 
     Reorg::update_savepoints(self.index, self.height)?;
 
@@ -1,5 +1,8 @@
 use super::*;
-use crate::okx::datastore::ord::operation::{Action, InscriptionOp};
+use crate::okx::datastore::{
+  ord::operation::{Action, InscriptionOp},
+  ScriptKey,
+};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Curse {
@@ -20,6 +23,8 @@ pub(super) struct Flotsam {
   inscription_id: InscriptionId,
   offset: u64,
   old_satpoint: SatPoint,
+  /// The owner of the spent output the inscription was on.
+  from: ScriptKey,
   origin: Origin,
 }
 
@@ -151,6 +156,26 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         continue;
       }
 
+      // multi-level cache for UTXO set to get to the input amount
+      let current_input_value = if let Some(tx_out) = self.tx_out_cache.get(&tx_in.previous_output)
+      {
+        tx_out.value
+      } else {
+        let tx_out = self.tx_out_receiver.blocking_recv().ok_or_else(|| {
+          anyhow!(
+            "failed to get transaction for {}",
+            tx_in.previous_output.txid
+          )
+        })?;
+        // received new tx out from chain node, add it to new_outpoints first and persist it in db later.
+        #[cfg(not(feature = "cache"))]
+        self.new_outpoints.push(tx_in.previous_output);
+        self
+          .tx_out_cache
+          .insert(tx_in.previous_output, tx_out.clone());
+        tx_out.value
+      };
+
       // find existing inscriptions on input (transfers of inscriptions)
       for (old_satpoint, inscription_id) in Index::inscriptions_on_output(
         self.satpoint_to_sequence_number,
@@ -163,6 +188,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           offset,
           inscription_id,
           old_satpoint,
+          from: self.script_key(tx_in.previous_output),
           origin: Origin::Old,
         });
 
@@ -174,26 +200,6 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
 
       let offset = total_input_value;
 
-      // multi-level cache for UTXO set to get to the input amount
-      let current_input_value = if let Some(tx_out) = self.tx_out_cache.get(&tx_in.previous_output)
-      {
-        tx_out.value
-      } else {
-        let tx_out = self.tx_out_receiver.blocking_recv().ok_or_else(|| {
-          anyhow!(
-            "failed to get transaction for {}",
-            tx_in.previous_output.txid
-          )
-        })?;
-        // received new tx out from chain node, add it to new_outpoints first and persist it in db later.
-        #[cfg(not(feature = "cache"))]
-        self.new_outpoints.push(tx_in.previous_output);
-        self
-          .tx_out_cache
-          .insert(tx_in.previous_output, tx_out.clone());
-        tx_out.value
-      };
-
       total_input_value += current_input_value;
 
       // go through all inscriptions in this input
@@ -269,6 +275,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             outpoint: tx_in.previous_output,
             offset: 0,
           },
+          from: self.script_key(tx_in.previous_output),
           origin: Origin::New {
             cursed: curse.is_some() && !jubilant,
             fee: 0,
@@ -431,6 +438,14 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     }
   }
 
+  /// The owner of a cached output, which operations record because spent outputs are pruned.
+  fn script_key(&self, outpoint: OutPoint) -> ScriptKey {
+    ScriptKey::from_script(
+      &self.tx_out_cache.get(&outpoint).unwrap().script_pubkey,
+      self.chain,
+    )
+  }
+
   // write tx_out to outpoint_to_entry table
   pub(super) fn flush_cache(self) -> Result {
     let start = Instant::now();
@@ -638,6 +653,13 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       new_satpoint.store()
     };
 
+    // outputs of the transaction are cached before inscriptions are moved to them.
+    let to = if unbound || new_satpoint.outpoint == OutPoint::null() {
+      None
+    } else {
+      Some(self.script_key(new_satpoint.outpoint))
+    };
+
     self
       .operations
       .entry(flotsam.txid)
@@ -671,6 +693,8 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         },
         old_satpoint: flotsam.old_satpoint,
         new_satpoint: Some(Entry::load(satpoint)),
+        from: flotsam.from,
+        to,
       });
 
     self
@@ -127,6 +127,7 @@ mod height;
 mod index;
 mod inscriptions;
 mod logger;
+mod metrics;
 mod object;
 mod okx;
 mod options;
@@ -0,0 +1,149 @@
+use {
+  super::*,
+  prometheus::{
+    core::Collector, Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, Opts, Registry, TextEncoder,
+  },
+};
+
+lazy_static! {
+  static ref REGISTRY: Registry = Registry::new();
+  pub(crate) static ref BLOCKS_INDEXED: IntCounter =
+    register(IntCounter::new("ord_blocks_indexed_total", "Number of blocks indexed.").unwrap());
+  pub(crate) static ref INDEX_HEIGHT: IntGauge =
+    register(IntGauge::new("ord_index_height", "Height of the last indexed block.").unwrap());
+  pub(crate) static ref CHAIN_TIP_LAG: IntGauge = register(
+    IntGauge::new(
+      "ord_chain_tip_lag_blocks",
+      "Number of blocks Bitcoin Core is ahead of the index, as of the last index update."
+    )
+    .unwrap()
+  );
+  static ref BLOCK_PHASE_DURATION: HistogramVec = register(
+    HistogramVec::new(
+      HistogramOpts::new(
+        "ord_block_phase_duration_seconds",
+        "Time spent indexing a block, by phase."
+      ),
+      &["phase"]
+    )
+    .unwrap()
+  );
+  static ref LRU_LOOKUPS: IntCounterVec = register(
+    IntCounterVec::new(
+      Opts::new(
+        "ord_lru_lookups_total",
+        "Lookups of previous outputs in the LRU cache of the indexer, by result."
+      ),
+      &["result"]
+    )
+    .unwrap()
+  );
+  pub(crate) static ref COMMIT_DURATION: Histogram = register(
+    Histogram::with_opts(HistogramOpts::new(
+      "ord_redb_commit_duration_seconds",
+      "Time spent committing write transactions to the index database."
+    ))
+    .unwrap()
+  );
+  pub(crate) static ref API_REQUEST_DURATION: HistogramVec = register(
+    HistogramVec::new(
+      HistogramOpts::new(
+        "ord_api_request_duration_seconds",
+        "Latency of API requests, by route."
+      ),
+      &["route"]
+    )
+    .unwrap()
+  );
+  pub(crate) static ref API_ERRORS: IntCounterVec = register(
+    IntCounterVec::new(
+      Opts::new(
+        "ord_api_errors_total",
+        "API requests answered with an error status, by route and status."
+      ),
+      &["route", "status"]
+    )
+    .unwrap()
+  );
+}
+
+fn register<T: Collector + Clone + 'static>(metric: T) -> T {
+  REGISTRY.register(Box::new(metric.clone())).unwrap();
+  metric
+}
+
+/// All metrics in the Prometheus text exposition format.
+pub(crate) fn encode() -> Result<String> {
+  // metrics are created on first use, make sure they are all exported from the start.
+  lazy_static::initialize(&BLOCKS_INDEXED);
+  lazy_static::initialize(&INDEX_HEIGHT);
+  lazy_static::initialize(&CHAIN_TIP_LAG);
+  lazy_static::initialize(&BLOCK_PHASE_DURATION);
+  lazy_static::initialize(&LRU_LOOKUPS);
+  lazy_static::initialize(&COMMIT_DURATION);
+  lazy_static::initialize(&API_REQUEST_DURATION);
+  lazy_static::initialize(&API_ERRORS);
+
+  let mut buffer = Vec::new();
+  TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer)?;
+  Ok(String::from_utf8(buffer)?)
+}
+
+/// The measurements of indexing a block, which are only exported once the block is indexed so that
+/// blocks which fail to index are left out.
+#[derive(Debug, Default)]
+pub(crate) struct BlockMetrics {
+  phases: Vec<(&'static str, Duration)>,
+  lru_hits: u64,
+  lru_misses: u64,
+}
+
+impl BlockMetrics {
+  pub(crate) fn phase(&mut self, phase: &'static str, duration: Duration) {
+    self.phases.push((phase, duration));
+  }
+
+  pub(crate) fn lru_lookups(&mut self, hits: u64, misses: u64) {
+    self.lru_hits += hits;
+    self.lru_misses += misses;
+  }
+
+  pub(crate) fn observe(self, height: u32) {
+    BLOCKS_INDEXED.inc();
+    INDEX_HEIGHT.set(height.into());
+
+    for (phase, duration) in self.phases {
+      BLOCK_PHASE_DURATION
+        .with_label_values(&[phase])
+        .observe(duration.as_secs_f64());
+    }
+
+    LRU_LOOKUPS
+      .with_label_values(&["hit"])
+      .inc_by(self.lru_hits);
+    LRU_LOOKUPS
+      .with_label_values(&["miss"])
+      .inc_by(self.lru_misses);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn block_metrics_are_exported() {
+    let mut metrics = BlockMetrics::default();
+    metrics.phase("test", Duration::from_millis(1));
+    metrics.lru_lookups(3, 1);
+    metrics.observe(7);
+
+    let encoded = encode().unwrap();
+
+    assert!(encoded.contains("ord_block_phase_duration_seconds_count{phase=\"test\"} 1\n"));
+    assert!(encoded.contains("# TYPE ord_blocks_indexed_total counter\n"));
+    assert!(encoded.contains("# TYPE ord_redb_commit_duration_seconds histogram\n"));
+    assert!(encoded.contains("ord_lru_lookups_total{result=\"hit\"}"));
+  }
+}
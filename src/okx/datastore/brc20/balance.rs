@@ -7,6 +7,15 @@ pub struct Balance {
   pub transferable_balance: u128,
 }
 
+/// A balance change of an account recorded at a block height.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+  pub old_overall_balance: u128,
+  pub old_transferable_balance: u128,
+  pub new_overall_balance: u128,
+  pub new_transferable_balance: u128,
+}
+
 impl Balance {
   pub fn new(tick: &Tick) -> Self {
     Self {
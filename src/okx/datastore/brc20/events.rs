@@ -1,6 +1,7 @@
 use super::*;
 use crate::{InscriptionId, SatPoint};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, strum_macros::Display)]
 #[strum(serialize_all = "camelCase")]
@@ -10,6 +11,20 @@ pub enum OperationType {
   InscribeTransfer,
   Transfer,
 }
+
+impl FromStr for OperationType {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "deploy" => Ok(Self::Deploy),
+      "mint" => Ok(Self::Mint),
+      "inscribeTransfer" => Ok(Self::InscribeTransfer),
+      "transfer" => Ok(Self::Transfer),
+      _ => Err(anyhow::anyhow!("invalid operation type {s}")),
+    }
+  }
+}
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Receipt {
   pub inscription_id: InscriptionId,
@@ -22,6 +37,31 @@ pub struct Receipt {
   pub result: Result<Event, BRC20Error>,
 }
 
+impl Receipt {
+  /// The ticker touched by this receipt, failed receipts carry no ticker.
+  pub fn tick(&self) -> Option<&Tick> {
+    match &self.result {
+      Ok(Event::Deploy(event)) => Some(&event.tick),
+      Ok(Event::Mint(event)) => Some(&event.tick),
+      Ok(Event::InscribeTransfer(event)) => Some(&event.tick),
+      Ok(Event::Transfer(event)) => Some(&event.tick),
+      Err(_) => None,
+    }
+  }
+
+  /// Whether the receipt is a successful transfer to an OP_RETURN output, which burns its amount.
+  pub fn is_burn(&self) -> bool {
+    matches!(self.result, Ok(Event::Transfer(_)))
+      && matches!(
+        self.to,
+        ScriptKey::ScriptHash {
+          is_op_return: true,
+          ..
+        }
+      )
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum Event {
   Deploy(DeployEvent),
@@ -63,7 +103,6 @@ pub struct TransferEvent {
 mod tests {
   use super::*;
   use bitcoin::Address;
-  use std::str::FromStr;
 
   #[test]
   fn action_receipt_serialize() {
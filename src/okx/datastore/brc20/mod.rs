@@ -7,12 +7,17 @@ pub(super) mod token_info;
 pub(super) mod transferable_log;
 
 pub use self::{
-  balance::Balance, errors::BRC20Error, events::Receipt, events::*, tick::*, token_info::TokenInfo,
+  balance::{Balance, BalanceChange},
+  errors::BRC20Error,
+  events::Receipt,
+  events::*,
+  tick::*,
+  token_info::TokenInfo,
   transferable_log::TransferableLog,
 };
 use super::ScriptKey;
 use crate::{Result, SatPoint};
-use bitcoin::{OutPoint, Txid};
+use bitcoin::{hashes::sha256, OutPoint, Txid};
 use std::fmt::{Debug, Display};
 
 pub trait Brc20Reader {
@@ -26,6 +31,8 @@ pub trait Brc20Reader {
   ) -> Result<Option<Balance>, Self::Error>;
 
   fn get_token_info(&self, tick: &Tick) -> Result<Option<TokenInfo>, Self::Error>;
+
+  fn get_block_checksum(&self, height: u32) -> Result<Option<sha256::Hash>, Self::Error>;
   fn get_tokens_info(&self) -> Result<Vec<TokenInfo>, Self::Error>;
 
   fn get_transaction_receipts(&self, txid: &Txid) -> Result<Option<Vec<Receipt>>, Self::Error>;
@@ -58,6 +65,12 @@ pub trait Brc20ReaderWriter: Brc20Reader {
 
   fn insert_token_info(&mut self, tick: &Tick, new_info: &TokenInfo) -> Result<(), Self::Error>;
 
+  fn save_block_checksum(
+    &mut self,
+    height: u32,
+    checksum: &sha256::Hash,
+  ) -> Result<(), Self::Error>;
+
   fn update_mint_token_info(
     &mut self,
     tick: &Tick,
@@ -73,6 +86,34 @@ pub trait Brc20ReaderWriter: Brc20Reader {
     receipt: &[Receipt],
   ) -> Result<(), Self::Error>;
 
+  fn save_address_receipt(
+    &mut self,
+    script_key: &ScriptKey,
+    height: u32,
+    tx_index: u32,
+    txid: &Txid,
+    index: u32,
+    receipt: &Receipt,
+  ) -> Result<(), Self::Error>;
+
+  fn save_tick_receipt(
+    &mut self,
+    tick: &Tick,
+    height: u32,
+    tx_index: u32,
+    txid: &Txid,
+    index: u32,
+    receipt: &Receipt,
+  ) -> Result<(), Self::Error>;
+
+  fn save_tick_burn(
+    &mut self,
+    tick: &Tick,
+    height: u32,
+    tx_index: u32,
+    index: u32,
+  ) -> Result<(), Self::Error>;
+
   fn insert_transferable_asset(
     &mut self,
     satpoint: SatPoint,
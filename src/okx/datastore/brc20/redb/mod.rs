@@ -22,3 +22,77 @@ fn min_script_tick_key(script: &ScriptKey) -> String {
 fn max_script_tick_key(script: &ScriptKey) -> String {
   format!("{}_{}", script, LowerTick::max_hex())
 }
+
+fn script_tick_height_key(script: &ScriptKey, tick: &Tick, height: u32) -> String {
+  format!("{}_{:08x}", script_tick_key(script, tick), height)
+}
+
+fn min_script_tick_height_key(script: &ScriptKey, tick: &Tick) -> String {
+  format!("{}_", script_tick_key(script, tick))
+}
+
+fn tick_holder_key(tick: &Tick, overall_balance: u128, script: &ScriptKey) -> String {
+  // holders are sorted by the inverted balance, so the largest holder comes first.
+  format!(
+    "{}_{:032x}_{}",
+    tick.to_lowercase().hex(),
+    u128::MAX - overall_balance,
+    script
+  )
+}
+
+fn min_tick_holder_key(tick: &Tick) -> String {
+  format!("{}_", tick.to_lowercase().hex())
+}
+
+fn max_tick_holder_key(tick: &Tick) -> String {
+  format!("{}_g", tick.to_lowercase().hex())
+}
+
+fn script_receipt_key(script: &ScriptKey, cursor: &str) -> String {
+  format!("{}_{}", script, cursor)
+}
+
+fn min_script_receipt_key(script: &ScriptKey) -> String {
+  format!("{}_", script)
+}
+
+fn max_script_receipt_key(script: &ScriptKey) -> String {
+  // the cursor part of the key only contains hex characters and `_`, so `g` is greater than any of them.
+  format!("{}_g", script)
+}
+
+fn tick_receipt_key(tick: &Tick, cursor: &str) -> String {
+  format!("{}_{}", tick.to_lowercase().hex(), cursor)
+}
+
+fn min_tick_receipt_key(tick: &Tick, from_height: u32) -> String {
+  format!("{}_{:08x}", tick.to_lowercase().hex(), from_height)
+}
+
+fn max_tick_receipt_key(tick: &Tick, to_height: u32) -> String {
+  format!("{}_{:08x}_g", tick.to_lowercase().hex(), to_height)
+}
+
+/// Encode the chain position of a receipt as a cursor that sorts in (height, tx index, receipt index) order.
+pub fn receipt_cursor(height: u32, tx_index: u32, index: u32) -> String {
+  format!("{:08x}_{:08x}_{:08x}", height, tx_index, index)
+}
+
+/// Decode a cursor produced by `receipt_cursor`.
+pub fn parse_receipt_cursor(cursor: &str) -> Option<(u32, u32, u32)> {
+  let mut parts = cursor
+    .split('_')
+    .map(|part| Some(part).filter(|s| s.len() == 8));
+  let height = parts.next()??;
+  let tx_index = parts.next()??;
+  let index = parts.next()??;
+  if parts.next().is_some() {
+    return None;
+  }
+  Some((
+    u32::from_str_radix(height, 16).ok()?,
+    u32::from_str_radix(tx_index, 16).ok()?,
+    u32::from_str_radix(index, 16).ok()?,
+  ))
+}
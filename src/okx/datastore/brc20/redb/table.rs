@@ -6,17 +6,25 @@ use crate::{
   okx::datastore::{
     brc20::{
       redb::{
-        max_script_tick_id_key, max_script_tick_key, min_script_tick_id_key, min_script_tick_key,
-        script_tick_key,
+        max_script_receipt_key, max_script_tick_id_key, max_script_tick_key, max_tick_holder_key,
+        max_tick_receipt_key, min_script_receipt_key, min_script_tick_height_key,
+        min_script_tick_id_key, min_script_tick_key, min_tick_holder_key, min_tick_receipt_key,
+        parse_receipt_cursor, receipt_cursor, script_receipt_key, script_tick_height_key,
+        script_tick_key, tick_holder_key, tick_receipt_key,
       },
-      Balance, Receipt, Tick, TokenInfo, TransferableLog,
+      Balance, BalanceChange, Receipt, Tick, TokenInfo, TransferableLog,
     },
     ScriptKey,
   },
   Result, SatPoint,
 };
-use bitcoin::{OutPoint, Txid};
+use anyhow::anyhow;
+use bitcoin::{
+  hashes::{sha256, Hash},
+  OutPoint, Txid,
+};
 use redb::{MultimapTable, ReadableMultimapTable, ReadableTable, Table};
+use std::ops::Bound;
 
 // BRC20_BALANCES
 pub fn get_balances<T>(table: &T, script_key: &ScriptKey) -> Result<Vec<Balance>>
@@ -45,6 +53,70 @@ where
   )
 }
 
+// BRC20_BALANCE_JOURNAL
+pub fn get_balance_at<T>(
+  table: &T,
+  script_key: &ScriptKey,
+  tick: &Tick,
+  height: u32,
+) -> Result<Option<Balance>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  // the latest change at or below the height holds the balance at that height.
+  Ok(
+    table
+      .range(
+        min_script_tick_height_key(script_key, tick).as_str()
+          ..=script_tick_height_key(script_key, tick, height).as_str(),
+      )?
+      .next_back()
+      .transpose()?
+      .map(|(_, data)| {
+        let change = rmp_serde::from_slice::<BalanceChange>(data.value()).unwrap();
+        Balance {
+          tick: tick.clone(),
+          overall_balance: change.new_overall_balance,
+          transferable_balance: change.new_transferable_balance,
+        }
+      }),
+  )
+}
+
+// BRC20_HEIGHT_TO_CHECKSUM
+pub fn get_block_checksum<T>(table: &T, height: u32) -> Result<Option<sha256::Hash>>
+where
+  T: ReadableTable<u32, &'static [u8; 32]>,
+{
+  Ok(
+    table
+      .get(height)?
+      .map(|v| sha256::Hash::from_byte_array(*v.value())),
+  )
+}
+
+// BRC20_TICKER_TO_HOLDERS
+pub fn get_tick_holders<T>(
+  table: &T,
+  tick: &Tick,
+  start: usize,
+  limit: usize,
+) -> Result<Vec<(ScriptKey, Balance)>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  Ok(
+    table
+      .range(min_tick_holder_key(tick).as_str()..max_tick_holder_key(tick).as_str())?
+      .skip(start)
+      .take(limit)
+      .flat_map(|result| {
+        result.map(|(_, data)| rmp_serde::from_slice::<(ScriptKey, Balance)>(data.value()).unwrap())
+      })
+      .collect(),
+  )
+}
+
 // BRC20_TOKEN
 pub fn get_token_info<T>(table: &T, tick: &Tick) -> Result<Option<TokenInfo>>
 where
@@ -84,6 +156,167 @@ where
   )
 }
 
+// BRC20_ADDRESS_TO_RECEIPTS
+pub fn get_address_receipts<T, F>(
+  table: &T,
+  script_key: &ScriptKey,
+  cursor: Option<&str>,
+  limit: usize,
+  filter: F,
+) -> Result<(Vec<(u32, Txid, Receipt)>, Option<String>)>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+  F: Fn(&Receipt) -> bool,
+{
+  let prefix = min_script_receipt_key(script_key);
+  let start = match cursor {
+    Some(cursor) => script_receipt_key(script_key, cursor),
+    None => prefix.clone(),
+  };
+  let end = max_script_receipt_key(script_key);
+
+  let mut receipts: Vec<(u32, Txid, Receipt, String)> = Vec::new();
+  let mut next_cursor = None;
+  for range in table.range::<&str>((
+    Bound::Excluded(start.as_str()),
+    Bound::Excluded(end.as_str()),
+  ))? {
+    let (key, value) = range?;
+    let (txid, receipt) = rmp_serde::from_slice::<(Txid, Receipt)>(value.value()).unwrap();
+    if !filter(&receipt) {
+      continue;
+    }
+    if receipts.len() >= limit {
+      next_cursor = receipts.last().map(|(_, _, _, cursor)| cursor.clone());
+      break;
+    }
+    let cursor = key.value()[prefix.len()..].to_string();
+    let (height, _, _) = parse_receipt_cursor(&cursor).unwrap();
+    receipts.push((height, txid, receipt, cursor));
+  }
+  Ok((
+    receipts
+      .into_iter()
+      .map(|(height, txid, receipt, _)| (height, txid, receipt))
+      .collect(),
+    next_cursor,
+  ))
+}
+
+/// The bounds of the tick receipt keys within the height range, after the cursor if any, or none
+/// if nothing is left to return.
+fn tick_receipt_range(
+  tick: &Tick,
+  from_height: u32,
+  to_height: u32,
+  cursor: Option<&str>,
+) -> Option<(Bound<String>, String)> {
+  let from = min_tick_receipt_key(tick, from_height);
+  let cursor = cursor.map(|cursor| tick_receipt_key(tick, cursor));
+  // resume after the cursor unless it points before the requested height range.
+  let start = match cursor {
+    Some(cursor) if cursor >= from => Bound::Excluded(cursor),
+    _ => Bound::Included(from),
+  };
+  let end = max_tick_receipt_key(tick, to_height);
+
+  // a cursor past the requested height range, or an empty range, leaves nothing to return.
+  match &start {
+    Bound::Included(start) | Bound::Excluded(start) if *start >= end => None,
+    _ => Some((start, end)),
+  }
+}
+
+fn as_str_bound(bound: &Bound<String>) -> Bound<&str> {
+  match bound {
+    Bound::Included(key) => Bound::Included(key.as_str()),
+    Bound::Excluded(key) => Bound::Excluded(key.as_str()),
+    Bound::Unbounded => Bound::Unbounded,
+  }
+}
+
+// BRC20_TICKER_TO_RECEIPTS
+pub fn get_tick_receipts<T>(
+  table: &T,
+  tick: &Tick,
+  from_height: u32,
+  to_height: u32,
+  cursor: Option<&str>,
+  limit: usize,
+) -> Result<(Vec<(u32, Txid, Receipt)>, Option<String>)>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  let Some((start, end)) = tick_receipt_range(tick, from_height, to_height, cursor) else {
+    return Ok((Vec::new(), None));
+  };
+
+  let prefix_len = tick_receipt_key(tick, "").len();
+  let mut receipts: Vec<(u32, Txid, Receipt, String)> = Vec::new();
+  let mut next_cursor = None;
+  for range in table.range::<&str>((as_str_bound(&start), Bound::Excluded(end.as_str())))? {
+    let (key, value) = range?;
+    if receipts.len() >= limit {
+      next_cursor = receipts.last().map(|(_, _, _, cursor)| cursor.clone());
+      break;
+    }
+    let cursor = key.value()[prefix_len..].to_string();
+    let (height, _, _) = parse_receipt_cursor(&cursor).unwrap();
+    let (txid, receipt) = rmp_serde::from_slice::<(Txid, Receipt)>(value.value()).unwrap();
+    receipts.push((height, txid, receipt, cursor));
+  }
+  Ok((
+    receipts
+      .into_iter()
+      .map(|(height, txid, receipt, _)| (height, txid, receipt))
+      .collect(),
+    next_cursor,
+  ))
+}
+
+// BRC20_TICKER_TO_BURNS
+// BRC20_TICKER_TO_RECEIPTS
+pub fn get_tick_burns<B, R>(
+  burn_table: &B,
+  receipt_table: &R,
+  tick: &Tick,
+  from_height: u32,
+  to_height: u32,
+  cursor: Option<&str>,
+  limit: usize,
+) -> Result<(Vec<(u32, Txid, Receipt)>, Option<String>)>
+where
+  B: ReadableTable<&'static str, ()>,
+  R: ReadableTable<&'static str, &'static [u8]>,
+{
+  let Some((start, end)) = tick_receipt_range(tick, from_height, to_height, cursor) else {
+    return Ok((Vec::new(), None));
+  };
+
+  let prefix_len = tick_receipt_key(tick, "").len();
+  let mut burns: Vec<(u32, Txid, Receipt, String)> = Vec::new();
+  let mut next_cursor = None;
+  for range in burn_table.range::<&str>((as_str_bound(&start), Bound::Excluded(end.as_str())))? {
+    let (key, _) = range?;
+    if burns.len() >= limit {
+      next_cursor = burns.last().map(|(_, _, _, cursor)| cursor.clone());
+      break;
+    }
+    let cursor = key.value()[prefix_len..].to_string();
+    let (height, _, _) = parse_receipt_cursor(&cursor).unwrap();
+    let value = receipt_table.get(key.value())?.unwrap();
+    let (txid, receipt) = rmp_serde::from_slice::<(Txid, Receipt)>(value.value()).unwrap();
+    burns.push((height, txid, receipt, cursor));
+  }
+  Ok((
+    burns
+      .into_iter()
+      .map(|(height, txid, receipt, _)| (height, txid, receipt))
+      .collect(),
+    next_cursor,
+  ))
+}
+
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
 pub fn get_transferable_assets_by_account<T, S>(
@@ -191,13 +424,76 @@ where
 }
 
 // BRC20_BALANCES
+// BRC20_TICKER_TO_HOLDERS
+// BRC20_TOKEN
+// BRC20_BALANCE_JOURNAL
 pub fn update_token_balance(
-  table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  balance_table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  holder_table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  token_table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  journal_table: &mut Table<'_, '_, &'static str, &'static [u8]>,
   script_key: &ScriptKey,
   new_balance: Balance,
+  height: u32,
 ) -> Result<()> {
-  table.insert(
-    script_tick_key(script_key, &new_balance.tick).as_str(),
+  let tick = new_balance.tick.clone();
+  let old_balance =
+    get_balance(balance_table, script_key, &tick)?.unwrap_or_else(|| Balance::new(&tick));
+  let old_overall_balance = old_balance.overall_balance;
+
+  // several changes in one block are merged, keeping the balance from before the block.
+  let journal_key = script_tick_height_key(script_key, &tick, height);
+  let (old_overall_balance_in_block, old_transferable_balance_in_block) = journal_table
+    .get(journal_key.as_str())?
+    .map(|data| {
+      let change = rmp_serde::from_slice::<BalanceChange>(data.value()).unwrap();
+      (change.old_overall_balance, change.old_transferable_balance)
+    })
+    .unwrap_or((
+      old_balance.overall_balance,
+      old_balance.transferable_balance,
+    ));
+  journal_table.insert(
+    journal_key.as_str(),
+    rmp_serde::to_vec(&BalanceChange {
+      old_overall_balance: old_overall_balance_in_block,
+      old_transferable_balance: old_transferable_balance_in_block,
+      new_overall_balance: new_balance.overall_balance,
+      new_transferable_balance: new_balance.transferable_balance,
+    })
+    .unwrap()
+    .as_slice(),
+  )?;
+
+  if old_overall_balance > 0 {
+    holder_table.remove(tick_holder_key(&tick, old_overall_balance, script_key).as_str())?;
+  }
+  if new_balance.overall_balance > 0 {
+    holder_table.insert(
+      tick_holder_key(&tick, new_balance.overall_balance, script_key).as_str(),
+      rmp_serde::to_vec(&(script_key, &new_balance))
+        .unwrap()
+        .as_slice(),
+    )?;
+  }
+
+  // the holder count only changes when an account's balance moves from or to zero.
+  if (old_overall_balance == 0) != (new_balance.overall_balance == 0) {
+    let mut info = get_token_info(token_table, &tick)?
+      .ok_or_else(|| anyhow!("token {} not exist", tick.as_str()))?;
+    if new_balance.overall_balance > 0 {
+      info.holders += 1;
+    } else {
+      info.holders = info.holders.saturating_sub(1);
+    }
+    token_table.insert(
+      tick.to_lowercase().hex().as_str(),
+      rmp_serde::to_vec(&info).unwrap().as_slice(),
+    )?;
+  }
+
+  balance_table.insert(
+    script_tick_key(script_key, &tick).as_str(),
     rmp_serde::to_vec(&new_balance).unwrap().as_slice(),
   )?;
   Ok(())
@@ -264,6 +560,67 @@ pub fn save_transaction_receipts(
   Ok(())
 }
 
+// BRC20_ADDRESS_TO_RECEIPTS
+pub fn save_address_receipt(
+  table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  script_key: &ScriptKey,
+  height: u32,
+  tx_index: u32,
+  txid: &Txid,
+  index: u32,
+  receipt: &Receipt,
+) -> Result<()> {
+  table.insert(
+    script_receipt_key(script_key, &receipt_cursor(height, tx_index, index)).as_str(),
+    rmp_serde::to_vec(&(txid, receipt)).unwrap().as_slice(),
+  )?;
+  Ok(())
+}
+
+// BRC20_HEIGHT_TO_CHECKSUM
+pub fn save_block_checksum(
+  table: &mut Table<'_, '_, u32, &'static [u8; 32]>,
+  height: u32,
+  checksum: &sha256::Hash,
+) -> Result<()> {
+  table.insert(height, checksum.as_byte_array())?;
+  Ok(())
+}
+
+// BRC20_TICKER_TO_RECEIPTS
+pub fn save_tick_receipt(
+  table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  tick: &Tick,
+  height: u32,
+  tx_index: u32,
+  txid: &Txid,
+  index: u32,
+  receipt: &Receipt,
+) -> Result<()> {
+  table.insert(
+    tick_receipt_key(tick, &receipt_cursor(height, tx_index, index)).as_str(),
+    rmp_serde::to_vec(&(txid, receipt)).unwrap().as_slice(),
+  )?;
+  Ok(())
+}
+
+// BRC20_TICKER_TO_BURNS
+/// Mark the tick receipt at the given position as a burn, the receipt itself is only kept in the
+/// tick receipts.
+pub fn save_tick_burn(
+  table: &mut Table<'_, '_, &'static str, ()>,
+  tick: &Tick,
+  height: u32,
+  tx_index: u32,
+  index: u32,
+) -> Result<()> {
+  table.insert(
+    tick_receipt_key(tick, &receipt_cursor(height, tx_index, index)).as_str(),
+    (),
+  )?;
+  Ok(())
+}
+
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
 pub fn insert_transferable_asset(
@@ -299,3 +656,343 @@ pub fn remove_transferable_asset(
   }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    index::{
+      BRC20_ADDRESS_TO_RECEIPTS, BRC20_BALANCES, BRC20_BALANCE_JOURNAL, BRC20_TICKER_TO_HOLDERS,
+      BRC20_TICKER_TO_RECEIPTS, BRC20_TOKEN,
+    },
+    okx::datastore::brc20::{BRC20Error, Event, MintEvent, OperationType},
+    InscriptionId,
+  };
+  use bitcoin::Address;
+  use redb::Database;
+  use std::str::FromStr;
+  use tempfile::NamedTempFile;
+
+  fn mint_receipt(to: &ScriptKey, tick: &str, result: bool) -> Receipt {
+    Receipt {
+      inscription_id: InscriptionId::from_str(
+        "9991111111111111111111111111111111111111111111111111111111111111i1",
+      )
+      .unwrap(),
+      inscription_number: 1,
+      old_satpoint: SatPoint::from_str(
+        "1111111111111111111111111111111111111111111111111111111111111111:1:1",
+      )
+      .unwrap(),
+      new_satpoint: SatPoint::from_str(
+        "2111111111111111111111111111111111111111111111111111111111111111:1:1",
+      )
+      .unwrap(),
+      op: OperationType::Mint,
+      from: to.clone(),
+      to: to.clone(),
+      result: if result {
+        Ok(Event::Mint(MintEvent {
+          tick: Tick::from_str(tick).unwrap(),
+          amount: 100,
+          msg: None,
+        }))
+      } else {
+        Err(BRC20Error::TickNotFound(tick.to_string()))
+      },
+    }
+  }
+
+  #[test]
+  fn test_address_receipts_pagination() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut table = wtx.open_table(BRC20_ADDRESS_TO_RECEIPTS).unwrap();
+
+    let alice = ScriptKey::from_address(
+      Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+        .unwrap()
+        .assume_checked(),
+    );
+    let bob = ScriptKey::from_address(
+      Address::from_str("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM")
+        .unwrap()
+        .assume_checked(),
+    );
+    let txid =
+      Txid::from_str("b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735").unwrap();
+    let other_txid =
+      Txid::from_str("0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+
+    for (script_key, height, tx_index, txid, index, receipt) in [
+      (&alice, 20, 1, &txid, 0, mint_receipt(&alice, "ordi", true)),
+      (
+        &alice,
+        3,
+        2,
+        &other_txid,
+        0,
+        mint_receipt(&alice, "ordi", false),
+      ),
+      (&alice, 3, 1, &txid, 0, mint_receipt(&alice, "sats", true)),
+      (&bob, 4, 1, &txid, 0, mint_receipt(&bob, "ordi", true)),
+    ] {
+      save_address_receipt(
+        &mut table, script_key, height, tx_index, txid, index, &receipt,
+      )
+      .unwrap();
+    }
+
+    // receipts of a block are in transaction order, not txid order.
+    let (receipts, next_cursor) = get_address_receipts(&table, &alice, None, 2, |_| true).unwrap();
+    assert_eq!(
+      receipts
+        .iter()
+        .map(|(height, txid, receipt)| (
+          *height,
+          *txid,
+          receipt.tick().map(|tick| tick.to_string())
+        ))
+        .collect::<Vec<_>>(),
+      vec![(3, txid, Some("sats".to_string())), (3, other_txid, None)]
+    );
+    assert_eq!(next_cursor, Some(receipt_cursor(3, 2, 0)));
+
+    let (receipts, next_cursor) =
+      get_address_receipts(&table, &alice, next_cursor.as_deref(), 2, |_| true).unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].0, 20);
+    assert_eq!(next_cursor, None);
+
+    let (receipts, next_cursor) =
+      get_address_receipts(&table, &alice, None, 10, |receipt| receipt.result.is_ok()).unwrap();
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(next_cursor, None);
+
+    let (receipts, _) = get_address_receipts(&table, &bob, None, 10, |_| true).unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].0, 4);
+  }
+
+  #[test]
+  fn test_tick_holders() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut balance_table = wtx.open_table(BRC20_BALANCES).unwrap();
+    let mut holder_table = wtx.open_table(BRC20_TICKER_TO_HOLDERS).unwrap();
+    let mut token_table = wtx.open_table(BRC20_TOKEN).unwrap();
+    let mut journal_table = wtx.open_table(BRC20_BALANCE_JOURNAL).unwrap();
+
+    let alice = ScriptKey::from_address(
+      Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+        .unwrap()
+        .assume_checked(),
+    );
+    let bob = ScriptKey::from_address(
+      Address::from_str("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM")
+        .unwrap()
+        .assume_checked(),
+    );
+    let tick = Tick::from_str("ORDI").unwrap();
+    insert_token_info(
+      &mut token_table,
+      &tick,
+      &TokenInfo {
+        tick: tick.clone(),
+        inscription_id: InscriptionId::from_str(
+          "9991111111111111111111111111111111111111111111111111111111111111i1",
+        )
+        .unwrap(),
+        inscription_number: 1,
+        supply: 1000,
+        burned_supply: 0,
+        minted: 0,
+        limit_per_mint: 100,
+        decimal: 18,
+        deploy_by: alice.clone(),
+        is_self_mint: false,
+        deployed_number: 1,
+        deployed_timestamp: 1,
+        latest_mint_number: 1,
+        holders: 0,
+      },
+    )
+    .unwrap();
+
+    let balance = |overall_balance| Balance {
+      tick: tick.clone(),
+      overall_balance,
+      transferable_balance: 0,
+    };
+    let holders = |holder_table: &Table<'_, '_, &'static str, &'static [u8]>| {
+      get_tick_holders(holder_table, &tick, 0, 10)
+        .unwrap()
+        .into_iter()
+        .map(|(script_key, balance)| (script_key, balance.overall_balance))
+        .collect::<Vec<_>>()
+    };
+
+    for (script_key, overall_balance) in [(&alice, 100), (&bob, 300), (&alice, 500)] {
+      update_token_balance(
+        &mut balance_table,
+        &mut holder_table,
+        &mut token_table,
+        &mut journal_table,
+        script_key,
+        balance(overall_balance),
+        1,
+      )
+      .unwrap();
+    }
+    assert_eq!(
+      holders(&holder_table),
+      vec![(alice.clone(), 500), (bob.clone(), 300)]
+    );
+    assert_eq!(
+      get_token_info(&token_table, &tick)
+        .unwrap()
+        .unwrap()
+        .holders,
+      2
+    );
+    assert_eq!(
+      get_tick_holders(&holder_table, &tick, 1, 10).unwrap().len(),
+      1
+    );
+
+    update_token_balance(
+      &mut balance_table,
+      &mut holder_table,
+      &mut token_table,
+      &mut journal_table,
+      &alice,
+      balance(0),
+      2,
+    )
+    .unwrap();
+    assert_eq!(holders(&holder_table), vec![(bob.clone(), 300)]);
+
+    let balance_at = |height| {
+      get_balance_at(&journal_table, &alice, &tick, height)
+        .unwrap()
+        .map(|balance| balance.overall_balance)
+    };
+    assert_eq!(balance_at(0), None);
+    assert_eq!(balance_at(1), Some(500));
+    assert_eq!(balance_at(2), Some(0));
+    assert_eq!(
+      get_token_info(&token_table, &tick)
+        .unwrap()
+        .unwrap()
+        .holders,
+      1
+    );
+
+    assert!(update_token_balance(
+      &mut balance_table,
+      &mut holder_table,
+      &mut token_table,
+      &mut journal_table,
+      &alice,
+      Balance::new(&Tick::from_str("SATS").unwrap()),
+      2,
+    )
+    .is_ok());
+    assert!(update_token_balance(
+      &mut balance_table,
+      &mut holder_table,
+      &mut token_table,
+      &mut journal_table,
+      &alice,
+      Balance {
+        tick: Tick::from_str("SATS").unwrap(),
+        overall_balance: 100,
+        transferable_balance: 0,
+      },
+      2,
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn test_tick_receipts() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut table = wtx.open_table(BRC20_TICKER_TO_RECEIPTS).unwrap();
+
+    let alice = ScriptKey::from_address(
+      Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+        .unwrap()
+        .assume_checked(),
+    );
+    let txid =
+      Txid::from_str("b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735").unwrap();
+    let ordi = Tick::from_str("ordi").unwrap();
+    let sats = Tick::from_str("sats").unwrap();
+
+    for (tick, height, tx_index, index) in [
+      (&ordi, 10, 2, 0),
+      (&ordi, 10, 1, 1),
+      (&sats, 11, 1, 0),
+      (&ordi, 12, 5, 0),
+      (&ordi, 300, 1, 0),
+    ] {
+      save_tick_receipt(
+        &mut table,
+        tick,
+        height,
+        tx_index,
+        &txid,
+        index,
+        &mint_receipt(&alice, &tick.as_str(), true),
+      )
+      .unwrap();
+    }
+
+    let heights = |receipts: &[(u32, Txid, Receipt)]| {
+      receipts
+        .iter()
+        .map(|(height, _, _)| *height)
+        .collect::<Vec<_>>()
+    };
+
+    let upper = Tick::from_str("ORDI").unwrap();
+    let (receipts, next_cursor) = get_tick_receipts(&table, &upper, 0, u32::MAX, None, 2).unwrap();
+    assert_eq!(heights(&receipts), vec![10, 10]);
+    assert_eq!(next_cursor, Some(receipt_cursor(10, 2, 0)));
+
+    let (receipts, next_cursor) =
+      get_tick_receipts(&table, &ordi, 0, u32::MAX, next_cursor.as_deref(), 2).unwrap();
+    assert_eq!(heights(&receipts), vec![12, 300]);
+    assert_eq!(next_cursor, None);
+
+    let (receipts, _) = get_tick_receipts(&table, &ordi, 11, 299, None, 10).unwrap();
+    assert_eq!(heights(&receipts), vec![12]);
+
+    let (receipts, _) =
+      get_tick_receipts(&table, &ordi, 12, 300, Some(&receipt_cursor(1, 0, 0)), 10).unwrap();
+    assert_eq!(heights(&receipts), vec![12, 300]);
+
+    let (receipts, next_cursor) =
+      get_tick_receipts(&table, &ordi, 0, 11, Some(&receipt_cursor(12, 5, 0)), 10).unwrap();
+    assert!(receipts.is_empty());
+    assert_eq!(next_cursor, None);
+
+    let (receipts, next_cursor) = get_tick_receipts(&table, &ordi, 12, 11, None, 10).unwrap();
+    assert!(receipts.is_empty());
+    assert_eq!(next_cursor, None);
+
+    assert_eq!(
+      parse_receipt_cursor(&receipt_cursor(840000, 3, 2)),
+      Some((840000, 3, 2))
+    );
+    assert_eq!(parse_receipt_cursor("0000000a_00000001"), None);
+    assert_eq!(
+      parse_receipt_cursor(&format!("{}_1", receipt_cursor(1, 2, 3))),
+      None
+    );
+  }
+}
@@ -16,4 +16,6 @@ pub struct TokenInfo {
   pub deployed_number: u32,
   pub deployed_timestamp: u32,
   pub latest_mint_number: u32,
+  #[serde(default)]
+  pub holders: u64,
 }
@@ -1,4 +1,7 @@
-use {super::*, anyhow::anyhow, regex::Regex};
+use {
+  super::{names::NameRule, *},
+  anyhow::anyhow,
+};
 
 const BITMAP_KEY: &str = r"BITMAP";
 
@@ -8,18 +11,13 @@ pub struct District {
 
 impl District {
   pub fn parse(bytes: &[u8]) -> Result<Self> {
-    let pattern = r"^(0|[1-9][0-9]*)\.bitmap$";
-    // pattern must be validated
-    let content = std::str::from_utf8(bytes)?;
-    let re = Regex::new(pattern).unwrap();
-    if let Some(capture) = re.captures(content) {
-      if let Some(number) = capture.get(1) {
-        return Ok(Self {
-          number: number.as_str().parse()?,
-        });
-      }
-    }
-    Err(anyhow!("No match found."))
+    let name = NameRule::Number
+      .parse(bytes, "bitmap")
+      .ok_or_else(|| anyhow!("No match found."))?;
+
+    Ok(Self {
+      number: name.trim_end_matches(".bitmap").parse()?,
+    })
   }
 
   pub fn to_collection_key(&self) -> String {
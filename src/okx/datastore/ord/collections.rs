@@ -6,6 +6,8 @@ use std::fmt::Display;
 pub enum CollectionKind {
   BitMap,
   BRC20,
+  /// A name of the first-is-first namespace.
+  Name(String),
 }
 impl Display for CollectionKind {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -15,6 +17,7 @@ impl Display for CollectionKind {
       match self {
         CollectionKind::BitMap => String::from("bitmap"),
         CollectionKind::BRC20 => String::from("brc20"),
+        CollectionKind::Name(namespace) => namespace.clone(),
       }
     )
   }
@@ -9,6 +9,7 @@ use {
 
 pub mod bitmap;
 pub mod collections;
+pub mod names;
 pub mod operation;
 pub mod redb;
 
@@ -39,6 +40,11 @@ pub trait OrdReader {
     &self,
     collection_key: &str,
   ) -> Result<Option<InscriptionId>, Self::Error>;
+
+  fn get_inscription_district(
+    &self,
+    inscription_id: &InscriptionId,
+  ) -> Result<Option<u32>, Self::Error>;
 }
 
 pub trait OrdReaderWriter: OrdReader {
@@ -59,4 +65,17 @@ pub trait OrdReaderWriter: OrdReader {
     inscription_id: &InscriptionId,
     kind: CollectionKind,
   ) -> Result<(), Self::Error>;
+
+  fn insert_district(
+    &mut self,
+    number: u32,
+    inscription_id: &InscriptionId,
+  ) -> Result<(), Self::Error>;
+
+  /// Set the current owner of the district, `None` if its inscription has been lost.
+  fn update_district_owner(
+    &mut self,
+    number: u32,
+    owner: Option<&ScriptKey>,
+  ) -> Result<(), Self::Error>;
 }
@@ -0,0 +1,215 @@
+use {
+  super::*,
+  anyhow::{anyhow, bail},
+  std::str::FromStr,
+};
+
+const NAME_KEY: &str = r"NAME";
+
+/// How the label in front of the namespace suffix of a name is validated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NameRule {
+  /// A label without dots or whitespace, the name is trimmed and lowercased, e.g. `Alice.sats`.
+  Label,
+  /// A decimal number without leading zeros, which must not be above the current block height,
+  /// e.g. `840000.bitmap`.
+  Number,
+}
+
+impl NameRule {
+  /// Parse the canonical `<label>.<namespace>` name of the content, if it's valid.
+  pub fn parse(self, content: &[u8], namespace: &str) -> Option<String> {
+    let content = std::str::from_utf8(content).ok()?;
+
+    let name = match self {
+      Self::Label => content.trim().to_lowercase(),
+      Self::Number => content.to_string(),
+    };
+
+    let label = name.strip_suffix(namespace)?.strip_suffix('.')?;
+
+    let valid = match self {
+      Self::Label => !label.is_empty() && !label.contains(|c: char| c == '.' || c.is_whitespace()),
+      Self::Number => {
+        (label == "0" || !label.starts_with('0'))
+          && label.chars().all(|c| c.is_ascii_digit())
+          && label.parse::<u32>().is_ok()
+      }
+    };
+
+    valid.then_some(name)
+  }
+}
+
+impl Display for NameRule {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Label => "label",
+        Self::Number => "number",
+      }
+    )
+  }
+}
+
+impl FromStr for NameRule {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "label" => Ok(Self::Label),
+      "number" => Ok(Self::Number),
+      _ => Err(anyhow!(
+        "invalid name rule `{s}`, expected `label` or `number`"
+      )),
+    }
+  }
+}
+
+/// A first-is-first namespace: the first inscription of a valid name owns the name.
+///
+/// Parsed from `<NAMESPACE>[:<RULE>][@<ACTIVATION_HEIGHT>]`, e.g. `sats@779832` or
+/// `bitmap:number`, the rule defaults to `label` and the activation height to 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Namespace {
+  pub name: String,
+  pub rule: NameRule,
+  pub activation_height: u32,
+}
+
+impl Namespace {
+  /// Parse the name inscribed by the content at the height, if it belongs to the namespace.
+  pub fn parse_name(&self, content: &[u8], height: u32) -> Option<String> {
+    if height < self.activation_height {
+      return None;
+    }
+
+    let name = self.rule.parse(content, &self.name)?;
+
+    if self.rule == NameRule::Number {
+      let number = name[..name.len() - self.name.len() - 1]
+        .parse::<u32>()
+        .ok()?;
+      if number > height {
+        return None;
+      }
+    }
+
+    Some(name)
+  }
+
+  pub fn to_collection_key(name: &str) -> String {
+    format!("{}_{}", NAME_KEY, name)
+  }
+}
+
+impl Display for Namespace {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}@{}", self.name, self.rule, self.activation_height)
+  }
+}
+
+impl FromStr for Namespace {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (s, activation_height) = match s.split_once('@') {
+      Some((s, height)) => (s, height.parse()?),
+      None => (s, 0),
+    };
+
+    let (name, rule) = match s.split_once(':') {
+      Some((name, rule)) => (name, rule.parse()?),
+      None => (s, NameRule::Label),
+    };
+
+    if name.is_empty()
+      || !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+      bail!("invalid namespace `{name}`, expected lowercase letters, digits, `-` or `_`");
+    }
+
+    Ok(Self {
+      name: name.into(),
+      rule,
+      activation_height,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_namespace() {
+    assert_eq!(
+      "sats".parse::<Namespace>().unwrap(),
+      Namespace {
+        name: "sats".into(),
+        rule: NameRule::Label,
+        activation_height: 0,
+      }
+    );
+    assert_eq!(
+      "bitmap:number@792435".parse::<Namespace>().unwrap(),
+      Namespace {
+        name: "bitmap".into(),
+        rule: NameRule::Number,
+        activation_height: 792435,
+      }
+    );
+    assert_eq!(
+      "unisat@100".parse::<Namespace>().unwrap().to_string(),
+      "unisat:label@100"
+    );
+    assert!("".parse::<Namespace>().is_err());
+    assert!("Sats".parse::<Namespace>().is_err());
+    assert!("sats.x".parse::<Namespace>().is_err());
+    assert!("sats:regex".parse::<Namespace>().is_err());
+    assert!("sats@-1".parse::<Namespace>().is_err());
+  }
+
+  #[test]
+  fn label_names() {
+    let namespace = "sats@10".parse::<Namespace>().unwrap();
+    assert_eq!(
+      namespace.parse_name(b" Alice.SATS\n", 10),
+      Some("alice.sats".into())
+    );
+    assert_eq!(namespace.parse_name(b"alice.sats", 9), None);
+    assert_eq!(namespace.parse_name(b".sats", 10), None);
+    assert_eq!(namespace.parse_name(b"sats", 10), None);
+    assert_eq!(namespace.parse_name(b"a.b.sats", 10), None);
+    assert_eq!(namespace.parse_name(b"a b.sats", 10), None);
+    assert_eq!(namespace.parse_name(b"alice.unisat", 10), None);
+    assert_eq!(namespace.parse_name(b"alicesats", 10), None);
+    assert_eq!(namespace.parse_name(&[0xff, b'.', b's'], 10), None);
+  }
+
+  #[test]
+  fn number_names() {
+    let namespace = "bitmap:number".parse::<Namespace>().unwrap();
+    assert_eq!(
+      namespace.parse_name(b"0.bitmap", 0),
+      Some("0.bitmap".into())
+    );
+    assert_eq!(
+      namespace.parse_name(b"40.bitmap", 40),
+      Some("40.bitmap".into())
+    );
+    assert_eq!(namespace.parse_name(b"41.bitmap", 40), None);
+    assert_eq!(namespace.parse_name(b"01.bitmap", 40), None);
+    assert_eq!(namespace.parse_name(b"c.bitmap", 40), None);
+    assert_eq!(namespace.parse_name(b" 1.bitmap", 40), None);
+    assert_eq!(namespace.parse_name(b"+1.bitmap", 40), None);
+    assert_eq!(
+      namespace.parse_name(format!("{}1.bitmap", u64::MAX).as_bytes(), u32::MAX),
+      None
+    );
+  }
+}
@@ -1,5 +1,5 @@
 use {
-  crate::{Inscription, InscriptionId, SatPoint},
+  crate::{okx::datastore::ScriptKey, Inscription, InscriptionId, SatPoint},
   bitcoin::Txid,
   serde::{Deserialize, Serialize},
 };
@@ -14,6 +14,11 @@ pub struct InscriptionOp {
   pub inscription_id: InscriptionId,
   pub old_satpoint: SatPoint,
   pub new_satpoint: Option<SatPoint>,
+  /// The owner of the output the inscription was on, recorded while indexing since spent outputs
+  /// are pruned.
+  pub from: ScriptKey,
+  /// The owner of the output the inscription moved to, `None` if it is unbound or lost.
+  pub to: Option<ScriptKey>,
 }
 
 // the act of marking an inscription.
@@ -34,8 +39,8 @@ pub enum Action {
 mod tests {
 
   use super::*;
-  use crate::test::inscription;
-  use bitcoin::OutPoint;
+  use crate::{test::inscription, Chain};
+  use bitcoin::{OutPoint, ScriptBuf};
   use std::str::FromStr;
 
   #[test]
@@ -52,6 +57,8 @@ mod tests {
       pub inscription_id: InscriptionId,
       pub old_satpoint: SatPoint,
       pub new_satpoint: Option<SatPoint>,
+      pub from: ScriptKey,
+      pub to: Option<ScriptKey>,
     }
 
     #[allow(clippy::large_enum_variant)]
@@ -100,6 +107,8 @@ mod tests {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 1,
       }),
+      from: ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet),
+      to: None,
     };
 
     let bytes = rmp_serde::to_vec(&old_operation).unwrap();
@@ -127,6 +136,8 @@ mod tests {
           outpoint: OutPoint { txid, vout: 0 },
           offset: 1,
         }),
+        from: ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet),
+        to: None,
       }
     );
   }
@@ -3,6 +3,7 @@ use crate::index::{InscriptionEntryValue, InscriptionIdValue, OutPointValue, Txi
 use crate::inscriptions::InscriptionId;
 use crate::okx::datastore::ord::collections::CollectionKind;
 use crate::okx::datastore::ord::InscriptionOp;
+use crate::okx::datastore::ScriptKey;
 use bitcoin::consensus::Decodable;
 use bitcoin::{OutPoint, TxOut, Txid};
 use redb::{MultimapTable, ReadableMultimapTable, ReadableTable, Table};
@@ -106,15 +107,135 @@ pub fn add_inscription_attributes(
   Ok(())
 }
 
+// BITMAP_DISTRICT_TO_INSCRIPTION_ID
+pub fn get_district_inscription_id<T>(
+  table: &T,
+  number: u32,
+) -> crate::Result<Option<InscriptionId>>
+where
+  T: ReadableTable<u32, InscriptionIdValue>,
+{
+  Ok(table.get(number)?.map(|v| InscriptionId::load(v.value())))
+}
+
+// BITMAP_DISTRICT_TO_INSCRIPTION_ID
+pub fn get_districts<T>(
+  table: &T,
+  start: usize,
+  limit: usize,
+) -> crate::Result<Vec<(u32, InscriptionId)>>
+where
+  T: ReadableTable<u32, InscriptionIdValue>,
+{
+  Ok(
+    table
+      .iter()?
+      .skip(start)
+      .take(limit)
+      .flat_map(|result| {
+        result.map(|(number, inscription_id)| {
+          (number.value(), InscriptionId::load(inscription_id.value()))
+        })
+      })
+      .collect(),
+  )
+}
+
+// BITMAP_INSCRIPTION_ID_TO_DISTRICT
+pub fn get_inscription_district<T>(
+  table: &T,
+  inscription_id: &InscriptionId,
+) -> crate::Result<Option<u32>>
+where
+  T: ReadableTable<InscriptionIdValue, u32>,
+{
+  Ok(table.get(&inscription_id.store())?.map(|v| v.value()))
+}
+
+// BITMAP_DISTRICT_TO_OWNER
+pub fn get_district_owner<T>(table: &T, number: u32) -> crate::Result<Option<ScriptKey>>
+where
+  T: ReadableTable<u32, &'static [u8]>,
+{
+  Ok(
+    table
+      .get(number)?
+      .map(|v| rmp_serde::from_slice::<ScriptKey>(v.value()).unwrap()),
+  )
+}
+
+// BITMAP_OWNER_TO_DISTRICTS
+pub fn get_districts_by_owner<T>(
+  table: &T,
+  owner: &ScriptKey,
+  start: usize,
+  limit: usize,
+) -> crate::Result<(Vec<u32>, u64)>
+where
+  T: ReadableMultimapTable<&'static str, u32>,
+{
+  let owner = owner.to_string();
+  let mut districts = Vec::new();
+  let mut total = 0;
+  for number in table.get(owner.as_str())? {
+    let number = number?.value();
+    if total >= start && districts.len() < limit {
+      districts.push(number);
+    }
+    total += 1;
+  }
+  Ok((districts, u64::try_from(total).unwrap()))
+}
+
+// BITMAP_DISTRICT_TO_INSCRIPTION_ID, BITMAP_INSCRIPTION_ID_TO_DISTRICT
+pub fn insert_district(
+  district_to_inscription_id: &mut Table<'_, '_, u32, InscriptionIdValue>,
+  inscription_id_to_district: &mut Table<'_, '_, InscriptionIdValue, u32>,
+  number: u32,
+  inscription_id: &InscriptionId,
+) -> crate::Result<()> {
+  district_to_inscription_id.insert(number, inscription_id.store())?;
+  inscription_id_to_district.insert(inscription_id.store(), number)?;
+  Ok(())
+}
+
+// BITMAP_DISTRICT_TO_OWNER, BITMAP_OWNER_TO_DISTRICTS
+pub fn update_district_owner(
+  district_to_owner: &mut Table<'_, '_, u32, &'static [u8]>,
+  owner_to_districts: &mut MultimapTable<'_, '_, &'static str, u32>,
+  number: u32,
+  owner: Option<&ScriptKey>,
+) -> crate::Result<()> {
+  if let Some(old_owner) = get_district_owner(district_to_owner, number)? {
+    owner_to_districts.remove(old_owner.to_string().as_str(), number)?;
+  }
+
+  match owner {
+    Some(owner) => {
+      district_to_owner.insert(number, rmp_serde::to_vec(owner)?.as_slice())?;
+      owner_to_districts.insert(owner.to_string().as_str(), number)?;
+    }
+    None => {
+      district_to_owner.remove(number)?;
+    }
+  }
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::index::{COLLECTIONS_INSCRIPTION_ID_TO_KINDS, ORD_TX_TO_OPERATIONS};
+  use crate::index::{
+    BITMAP_DISTRICT_TO_OWNER, BITMAP_OWNER_TO_DISTRICTS, COLLECTIONS_INSCRIPTION_ID_TO_KINDS,
+    ORD_TX_TO_OPERATIONS,
+  };
   use crate::okx::datastore::ord::redb::table::{
     get_transaction_operations, save_transaction_operations,
   };
   use crate::okx::datastore::ord::InscriptionOp;
+  use crate::Chain;
   use crate::{inscription, okx::datastore::ord::Action, SatPoint};
+  use bitcoin::ScriptBuf;
   use redb::Database;
   use std::str::FromStr;
   use tempfile::NamedTempFile;
@@ -150,6 +271,69 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_district_owner() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut district_to_owner = wtx.open_table(BITMAP_DISTRICT_TO_OWNER).unwrap();
+    let mut owner_to_districts = wtx.open_multimap_table(BITMAP_OWNER_TO_DISTRICTS).unwrap();
+
+    let alice = ScriptKey::from_script(
+      &bitcoin::ScriptBuf::from_bytes(vec![0x51]),
+      crate::Chain::Mainnet,
+    );
+    let bob = ScriptKey::from_script(
+      &bitcoin::ScriptBuf::from_bytes(vec![0x52]),
+      crate::Chain::Mainnet,
+    );
+
+    for number in [3, 1, 2] {
+      update_district_owner(
+        &mut district_to_owner,
+        &mut owner_to_districts,
+        number,
+        Some(&alice),
+      )
+      .unwrap();
+    }
+    assert_eq!(
+      get_districts_by_owner(&owner_to_districts, &alice, 0, 10).unwrap(),
+      (vec![1, 2, 3], 3)
+    );
+    assert_eq!(
+      get_districts_by_owner(&owner_to_districts, &alice, 1, 1).unwrap(),
+      (vec![2], 3)
+    );
+
+    update_district_owner(
+      &mut district_to_owner,
+      &mut owner_to_districts,
+      2,
+      Some(&bob),
+    )
+    .unwrap();
+    assert_eq!(
+      get_district_owner(&district_to_owner, 2).unwrap(),
+      Some(bob.clone())
+    );
+    assert_eq!(
+      get_districts_by_owner(&owner_to_districts, &alice, 0, 10).unwrap(),
+      (vec![1, 3], 2)
+    );
+    assert_eq!(
+      get_districts_by_owner(&owner_to_districts, &bob, 0, 10).unwrap(),
+      (vec![2], 1)
+    );
+
+    update_district_owner(&mut district_to_owner, &mut owner_to_districts, 2, None).unwrap();
+    assert_eq!(get_district_owner(&district_to_owner, 2).unwrap(), None);
+    assert_eq!(
+      get_districts_by_owner(&owner_to_districts, &bob, 0, 10).unwrap(),
+      (vec![], 0)
+    );
+  }
+
   #[test]
   fn test_transaction_to_operations() {
     let dbfile = NamedTempFile::new().unwrap();
@@ -177,6 +361,8 @@ mod tests {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 1,
       }),
+      from: ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet),
+      to: None,
     };
 
     save_transaction_operations(&mut table, &txid, &[operation.clone()]).unwrap();
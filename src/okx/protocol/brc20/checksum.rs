@@ -0,0 +1,222 @@
+use crate::okx::datastore::brc20::{Brc20Reader, Brc20ReaderWriter, Event, Receipt, Tick};
+use crate::okx::datastore::ScriptKey;
+use crate::okx::protocol::{brc20::tables::Brc20Tables, context::Context};
+use {
+  crate::{index::BlockData, okx::datastore::ord::operation::InscriptionOp, Result},
+  anyhow::anyhow,
+  bitcoin::{
+    hashes::{sha256, Hash, HashEngine},
+    Txid,
+  },
+  std::collections::{BTreeMap, HashMap},
+};
+
+/// Fold the BRC20 events and balances of the block into the checksum of the previous block.
+///
+/// The checksum of a block is `sha256(previous checksum || events || balances)`, which only covers
+/// state every BRC20 indexer agrees on, in the following canonical encoding:
+///
+/// - integers are big endian, strings are UTF-8 prefixed with their byte length as a `u32`;
+/// - `events` are the successful events of the block in block order, failed operations are left
+///   out. Each one is `kind (u8) || inscription txid (32 bytes) || inscription index (u32) ||
+///   from || to || tick`, followed by `supply (u128) || limit (u128) || decimal (u8) ||
+///   self_mint (u8)` for a deploy (kind 0), or by `amount (u128)` for a mint (kind 1), an
+///   inscribe transfer (kind 2) and a transfer (kind 3). Addresses and script hashes are encoded
+///   as strings and ticks as lowercase strings. The `msg` of mints and transfers is specific to
+///   this indexer and left out;
+/// - `balances` are the balances touched by the events after the block, sorted by script key
+///   and then by tick, each one being `script key || tick || overall (u128) || transferable (u128)`.
+///
+/// The checksum of the block before the first indexed one is all zeros.
+pub fn update_block_checksum(
+  context: &Context,
+  tables: &mut Brc20Tables,
+  block: &BlockData,
+  operations: &HashMap<Txid, Vec<InscriptionOp>>,
+) -> Result<sha256::Hash> {
+  let height = context.chain_conf.blockheight;
+  let previous = match height.checked_sub(1) {
+    Some(height) => tables
+      .get_block_checksum(height)
+      .map_err(|e| anyhow!("failed to get block checksum from state! error: {e}"))?,
+    None => None,
+  }
+  .unwrap_or_else(sha256::Hash::all_zeros);
+
+  let mut engine = sha256::Hash::engine();
+  engine.input(previous.as_byte_array());
+
+  let mut touched = BTreeMap::new();
+  for (_, txid) in block
+    .txdata
+    .iter()
+    .filter(|(_, txid)| operations.contains_key(txid))
+  {
+    let receipts = tables
+      .get_transaction_receipts(txid)
+      .map_err(|e| anyhow!("failed to get transaction receipts from state! error: {e}"))?
+      .unwrap_or_default();
+    for receipt in receipts {
+      let Ok(event) = &receipt.result else {
+        continue;
+      };
+      encode_event(&mut engine, &receipt, event);
+      if !matches!(event, Event::Deploy(_)) {
+        for script_key in [&receipt.from, &receipt.to] {
+          let tick = receipt.tick().unwrap();
+          touched.insert(
+            (script_key.to_string(), tick.to_lowercase().to_string()),
+            (script_key.clone(), tick.clone()),
+          );
+        }
+      }
+    }
+  }
+
+  for ((script_key, tick), (key, original_tick)) in touched {
+    let balance = tables
+      .get_balance(&key, &original_tick)
+      .map_err(|e| anyhow!("failed to get balance from state! error: {e}"))?;
+    let (overall, transferable) = balance
+      .map(|balance| (balance.overall_balance, balance.transferable_balance))
+      .unwrap_or_default();
+    encode_str(&mut engine, &script_key);
+    encode_str(&mut engine, &tick);
+    engine.input(&overall.to_be_bytes());
+    engine.input(&transferable.to_be_bytes());
+  }
+
+  let checksum = sha256::Hash::from_engine(engine);
+  tables
+    .save_block_checksum(height, &checksum)
+    .map_err(|e| anyhow!("failed to save block checksum to state! error: {e}"))?;
+
+  Ok(checksum)
+}
+
+fn encode_event(engine: &mut sha256::HashEngine, receipt: &Receipt, event: &Event) {
+  let kind: u8 = match event {
+    Event::Deploy(_) => 0,
+    Event::Mint(_) => 1,
+    Event::InscribeTransfer(_) => 2,
+    Event::Transfer(_) => 3,
+  };
+  engine.input(&[kind]);
+  engine.input(receipt.inscription_id.txid.as_byte_array());
+  engine.input(&receipt.inscription_id.index.to_be_bytes());
+  encode_script_key(engine, &receipt.from);
+  encode_script_key(engine, &receipt.to);
+
+  match event {
+    Event::Deploy(deploy) => {
+      encode_tick(engine, &deploy.tick);
+      engine.input(&deploy.supply.to_be_bytes());
+      engine.input(&deploy.limit_per_mint.to_be_bytes());
+      engine.input(&[deploy.decimal, deploy.self_mint.into()]);
+    }
+    Event::Mint(mint) => {
+      encode_tick(engine, &mint.tick);
+      engine.input(&mint.amount.to_be_bytes());
+    }
+    Event::InscribeTransfer(inscribe_transfer) => {
+      encode_tick(engine, &inscribe_transfer.tick);
+      engine.input(&inscribe_transfer.amount.to_be_bytes());
+    }
+    Event::Transfer(transfer) => {
+      encode_tick(engine, &transfer.tick);
+      engine.input(&transfer.amount.to_be_bytes());
+    }
+  }
+}
+
+fn encode_str(engine: &mut sha256::HashEngine, value: &str) {
+  engine.input(&u32::try_from(value.len()).unwrap().to_be_bytes());
+  engine.input(value.as_bytes());
+}
+
+fn encode_script_key(engine: &mut sha256::HashEngine, script_key: &ScriptKey) {
+  encode_str(engine, &script_key.to_string());
+}
+
+fn encode_tick(engine: &mut sha256::HashEngine, tick: &Tick) {
+  encode_str(engine, tick.to_lowercase().as_str());
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::{
+      okx::datastore::brc20::{MintEvent, OperationType},
+      InscriptionId, SatPoint,
+    },
+    bitcoin::Address,
+    std::str::FromStr,
+  };
+
+  fn mint(msg: Option<String>) -> Receipt {
+    let alice = ScriptKey::from_address(
+      Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+        .unwrap()
+        .assume_checked(),
+    );
+    Receipt {
+      inscription_id: InscriptionId::from_str(
+        "1111111111111111111111111111111111111111111111111111111111111111i1",
+      )
+      .unwrap(),
+      inscription_number: 1,
+      old_satpoint: SatPoint::from_str(
+        "1111111111111111111111111111111111111111111111111111111111111111:1:1",
+      )
+      .unwrap(),
+      new_satpoint: SatPoint::from_str(
+        "2111111111111111111111111111111111111111111111111111111111111111:1:1",
+      )
+      .unwrap(),
+      op: OperationType::Mint,
+      from: alice.clone(),
+      to: alice,
+      result: Ok(Event::Mint(MintEvent {
+        tick: Tick::from_str("ORDI").unwrap(),
+        amount: 1000,
+        msg,
+      })),
+    }
+  }
+
+  fn event_hash(receipt: &Receipt) -> sha256::Hash {
+    let mut engine = sha256::Hash::engine();
+    encode_event(&mut engine, receipt, receipt.result.as_ref().unwrap());
+    sha256::Hash::from_engine(engine)
+  }
+
+  #[test]
+  fn event_encoding_is_canonical() {
+    let receipt = mint(None);
+
+    let mut expected = vec![1];
+    expected.extend(receipt.inscription_id.txid.as_byte_array());
+    expected.extend(1u32.to_be_bytes());
+    for _ in 0..2 {
+      expected.extend(42u32.to_be_bytes());
+      expected.extend(b"bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4");
+    }
+    expected.extend(4u32.to_be_bytes());
+    expected.extend(b"ordi");
+    expected.extend(1000u128.to_be_bytes());
+
+    assert_eq!(event_hash(&receipt), sha256::Hash::hash(&expected));
+
+    // implementation details of the receipt are not part of the checksum.
+    let mut other = receipt.clone();
+    other.inscription_number = 2;
+    other.old_satpoint = other.new_satpoint;
+    assert_eq!(event_hash(&other), event_hash(&receipt));
+
+    assert_eq!(
+      event_hash(&mint(Some("amount exceeds the limit".into()))),
+      event_hash(&receipt)
+    );
+  }
+}
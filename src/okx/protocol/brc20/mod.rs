@@ -7,20 +7,26 @@ use crate::{
 };
 use bitcoin::Txid;
 
+mod checksum;
 mod error;
 mod msg_executor;
 mod msg_resolver;
 mod num;
 mod operation;
+pub(crate) mod overlay;
 mod params;
 mod policies;
+mod protocol;
+pub(crate) mod tables;
 
 use self::error::Error;
 pub(crate) use self::{
+  checksum::update_block_checksum,
   error::JSONError,
   msg_executor::{execute, ExecutionMessage},
   num::Num,
   operation::{deserialize_brc20_operation, Deploy, Mint, Operation, Transfer},
+  protocol::Brc20Protocol,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -6,14 +6,15 @@ use crate::{
   okx::{
     datastore::{
       brc20::{
-        BRC20Error, Balance, Brc20Reader, Brc20ReaderWriter, DeployEvent, Event,
-        InscribeTransferEvent, MintEvent, Receipt, Tick, TokenInfo, TransferEvent, TransferableLog,
+        BRC20Error, Balance, Brc20ReaderWriter, DeployEvent, Event, InscribeTransferEvent,
+        MintEvent, Receipt, Tick, TokenInfo, TransferEvent, TransferableLog,
       },
       ord::OrdReader,
     },
     protocol::{
       brc20::{Message, Mint, Operation},
       context::Context,
+      ChainContext,
     },
   },
   Chain, Result,
@@ -36,34 +37,56 @@ pub struct ExecutionMessage {
 
 impl ExecutionMessage {
   pub fn from_message(context: &mut Context, msg: &Message, chain: Chain) -> Result<Self> {
+    Self::new(
+      msg,
+      context.get_inscription_number_by_sequence_number(msg.sequence_number)?,
+      context.get_script_key_on_satpoint(&msg.old_satpoint, chain)?,
+      if msg.sat_in_outputs {
+        Some(context.get_script_key_on_satpoint(msg.new_satpoint.as_ref().unwrap(), chain)?)
+      } else {
+        None
+      },
+    )
+  }
+
+  /// Builds the message from the owners of the inscription before and after it moved, for callers
+  /// that already know them, like the mempool.
+  pub fn new(
+    msg: &Message,
+    inscription_number: i32,
+    from: ScriptKey,
+    to: Option<ScriptKey>,
+  ) -> Result<Self> {
     Ok(Self {
       txid: msg.txid,
       inscription_id: msg.inscription_id,
-      inscription_number: context.get_inscription_number_by_sequence_number(msg.sequence_number)?,
+      inscription_number,
       old_satpoint: msg.old_satpoint,
       new_satpoint: msg
         .new_satpoint
         .ok_or(anyhow!("new satpoint cannot be None"))?,
-      from: context.get_script_key_on_satpoint(&msg.old_satpoint, chain)?,
-      to: if msg.sat_in_outputs {
-        Some(context.get_script_key_on_satpoint(msg.new_satpoint.as_ref().unwrap(), chain)?)
-      } else {
-        None
-      },
+      from,
+      to,
       op: msg.op.clone(),
     })
   }
 }
 
-pub fn execute(context: &mut Context, msg: &ExecutionMessage) -> Result<Receipt> {
+pub fn execute<L: Brc20ReaderWriter<Error = anyhow::Error>>(
+  chain_conf: &ChainContext,
+  tables: &mut L,
+  msg: &ExecutionMessage,
+) -> Result<Receipt> {
   log::debug!("BRC20 execute message: {:?}", msg);
   let event = match &msg.op {
-    Operation::Deploy(deploy) => process_deploy(context, msg, deploy.clone()),
-    Operation::Mint { mint, parent } => process_mint(context, msg, mint.clone(), *parent),
+    Operation::Deploy(deploy) => process_deploy(chain_conf, tables, msg, deploy.clone()),
+    Operation::Mint { mint, parent } => {
+      process_mint(chain_conf, tables, msg, mint.clone(), *parent)
+    }
     Operation::InscribeTransfer(transfer) => {
-      process_inscribe_transfer(context, msg, transfer.clone())
+      process_inscribe_transfer(tables, msg, transfer.clone())
     }
-    Operation::Transfer(_) => process_transfer(context, msg),
+    Operation::Transfer(_) => process_transfer(tables, msg),
   };
 
   let receipt = Receipt {
@@ -86,8 +109,9 @@ pub fn execute(context: &mut Context, msg: &ExecutionMessage) -> Result<Receipt>
   Ok(receipt)
 }
 
-fn process_deploy(
-  context: &mut Context,
+fn process_deploy<L: Brc20ReaderWriter<Error = anyhow::Error>>(
+  chain_conf: &ChainContext,
+  tables: &mut L,
   msg: &ExecutionMessage,
   deploy: Deploy,
 ) -> Result<Event, Error> {
@@ -101,8 +125,8 @@ fn process_deploy(
   // proposal for issuance self mint token.
   // https://l1f.discourse.group/t/brc-20-proposal-for-issuance-and-burn-enhancements-brc20-ip-1/621
   if tick.self_issuance_tick() {
-    if context.chain_conf.blockheight
-      < policies::HardForks::self_issuance_activation_height(context.chain_conf.chain)
+    if chain_conf.blockheight
+      < policies::HardForks::self_issuance_activation_height(chain_conf.chain)
     {
       return Err(Error::BRC20Error(BRC20Error::SelfIssuanceNotActivated));
     }
@@ -115,7 +139,7 @@ fn process_deploy(
     is_self_mint = true;
   }
 
-  if let Some(stored_tick_info) = context.get_token_info(&tick).map_err(Error::LedgerError)? {
+  if let Some(stored_tick_info) = tables.get_token_info(&tick).map_err(Error::LedgerError)? {
     return Err(Error::BRC20Error(BRC20Error::DuplicateTick(
       stored_tick_info.tick.to_string(),
     )));
@@ -165,11 +189,12 @@ fn process_deploy(
     minted: 0u128,
     deploy_by: to_script_key,
     is_self_mint,
-    deployed_number: context.chain_conf.blockheight,
-    latest_mint_number: context.chain_conf.blockheight,
-    deployed_timestamp: context.chain_conf.blocktime,
+    deployed_number: chain_conf.blockheight,
+    latest_mint_number: chain_conf.blockheight,
+    deployed_timestamp: chain_conf.blocktime,
+    holders: 0,
   };
-  context
+  tables
     .insert_token_info(&tick, &new_info)
     .map_err(Error::LedgerError)?;
 
@@ -182,8 +207,9 @@ fn process_deploy(
   }))
 }
 
-fn process_mint(
-  context: &mut Context,
+fn process_mint<L: Brc20ReaderWriter<Error = anyhow::Error>>(
+  chain_conf: &ChainContext,
+  tables: &mut L,
   msg: &ExecutionMessage,
   mint: Mint,
   parent: Option<InscriptionId>,
@@ -193,7 +219,7 @@ fn process_mint(
 
   let tick = mint.tick.parse::<Tick>()?;
 
-  let tick_info = context
+  let tick_info = tables
     .get_token_info(&tick)
     .map_err(Error::LedgerError)?
     .ok_or(BRC20Error::TickNotFound(tick.to_string()))?;
@@ -245,7 +271,7 @@ fn process_mint(
   };
 
   // get or initialize user balance.
-  let mut balance = context
+  let mut balance = tables
     .get_balance(&to_script_key, &tick)
     .map_err(Error::LedgerError)?
     .map_or(Balance::new(&tick), |v| v);
@@ -256,14 +282,14 @@ fn process_mint(
     .checked_to_u128()?;
 
   // store to database.
-  context
+  tables
     .update_token_balance(&to_script_key, balance)
     .map_err(Error::LedgerError)?;
 
   // update token minted.
   let minted = minted.checked_add(&amt)?.checked_to_u128()?;
-  context
-    .update_mint_token_info(&tick, minted, context.chain_conf.blockheight)
+  tables
+    .update_mint_token_info(&tick, minted, chain_conf.blockheight)
     .map_err(Error::LedgerError)?;
 
   Ok(Event::Mint(MintEvent {
@@ -273,8 +299,8 @@ fn process_mint(
   }))
 }
 
-fn process_inscribe_transfer(
-  context: &mut Context,
+fn process_inscribe_transfer<L: Brc20ReaderWriter<Error = anyhow::Error>>(
+  tables: &mut L,
   msg: &ExecutionMessage,
   transfer: Transfer,
 ) -> Result<Event, Error> {
@@ -283,7 +309,7 @@ fn process_inscribe_transfer(
 
   let tick = transfer.tick.parse::<Tick>()?;
 
-  let token_info = context
+  let token_info = tables
     .get_token_info(&tick)
     .map_err(Error::LedgerError)?
     .ok_or(BRC20Error::TickNotFound(tick.to_string()))?;
@@ -305,7 +331,7 @@ fn process_inscribe_transfer(
     )));
   }
 
-  let mut balance = context
+  let mut balance = tables
     .get_balance(&to_script_key, &tick)
     .map_err(Error::LedgerError)?
     .map_or(Balance::new(&tick), |v| v);
@@ -323,7 +349,7 @@ fn process_inscribe_transfer(
   balance.transferable_balance = transferable.checked_add(&amt)?.checked_to_u128()?;
 
   let amt = amt.checked_to_u128()?;
-  context
+  tables
     .update_token_balance(&to_script_key, balance)
     .map_err(Error::LedgerError)?;
 
@@ -335,7 +361,7 @@ fn process_inscribe_transfer(
     owner: to_script_key,
   };
 
-  context
+  tables
     .insert_transferable_asset(msg.new_satpoint, &transferable_asset)
     .map_err(Error::LedgerError)?;
 
@@ -345,8 +371,11 @@ fn process_inscribe_transfer(
   }))
 }
 
-fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Event, Error> {
-  let transferable = context
+fn process_transfer<L: Brc20ReaderWriter<Error = anyhow::Error>>(
+  tables: &mut L,
+  msg: &ExecutionMessage,
+) -> Result<Event, Error> {
+  let transferable = tables
     .get_transferable_assets_by_satpoint(&msg.old_satpoint)
     .map_err(Error::LedgerError)?
     .ok_or(BRC20Error::TransferableNotFound(msg.inscription_id))?;
@@ -361,13 +390,13 @@ fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Eve
 
   let tick = transferable.tick;
 
-  let token_info = context
+  let token_info = tables
     .get_token_info(&tick)
     .map_err(Error::LedgerError)?
     .ok_or(BRC20Error::TickNotFound(tick.to_string()))?;
 
   // update from key balance.
-  let mut from_balance = context
+  let mut from_balance = tables
     .get_balance(&msg.from, &tick)
     .map_err(Error::LedgerError)?
     .map_or(Balance::new(&tick), |v| v);
@@ -381,7 +410,7 @@ fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Eve
   from_balance.overall_balance = from_overall;
   from_balance.transferable_balance = from_transferable;
 
-  context
+  tables
     .update_token_balance(&msg.from, from_balance)
     .map_err(Error::LedgerError)?;
 
@@ -397,7 +426,7 @@ fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Eve
   };
 
   // update to key balance.
-  let mut to_balance = context
+  let mut to_balance = tables
     .get_balance(&to_script_key, &tick)
     .map_err(Error::LedgerError)?
     .map_or(Balance::new(&tick), |v| v);
@@ -405,11 +434,11 @@ fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Eve
   let to_overall = Into::<Num>::into(to_balance.overall_balance);
   to_balance.overall_balance = to_overall.checked_add(&amt)?.checked_to_u128()?;
 
-  context
+  tables
     .update_token_balance(&to_script_key, to_balance)
     .map_err(Error::LedgerError)?;
 
-  context
+  tables
     .remove_transferable_asset(msg.old_satpoint)
     .map_err(Error::LedgerError)?;
 
@@ -419,7 +448,7 @@ fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Eve
       let burned_amt = Into::<Num>::into(token_info.burned_supply)
         .checked_add(&amt)?
         .checked_to_u128()?;
-      context
+      tables
         .update_burned_token_info(&tick, burned_amt)
         .map_err(Error::LedgerError)?;
       out_msg = Some(format!(
@@ -76,7 +76,8 @@ impl Message {
 mod tests {
   use super::*;
   use crate::okx::datastore::brc20::{Tick, TransferableLog};
-  use bitcoin::{Address, OutPoint};
+  use crate::Chain;
+  use bitcoin::{Address, OutPoint, ScriptBuf};
   use std::str::FromStr;
 
   fn create_inscription(str: &str) -> Inscription {
@@ -113,6 +114,8 @@ mod tests {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 0,
       }),
+      from: ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet),
+      to: None,
     };
     (inscriptions, op)
   }
@@ -144,6 +147,8 @@ mod tests {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 0,
       }),
+      from: ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet),
+      to: None,
     }
   }
 
@@ -187,7 +192,7 @@ mod tests {
         inscription: inscriptions.first().unwrap().clone(),
         vindicated: false,
       },
-      ..op
+      ..op.clone()
     };
     assert_matches!(
       Message::resolve(&op2, &inscriptions, transfer_assets_cache.clone()),
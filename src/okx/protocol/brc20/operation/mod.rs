@@ -3,7 +3,10 @@ mod mint;
 mod transfer;
 
 use super::{params::*, *};
-use crate::{okx::datastore::ord::Action, Inscription};
+use crate::{
+  okx::datastore::{brc20::Tick, ord::Action},
+  Inscription,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -29,6 +32,16 @@ impl Operation {
       Operation::Transfer(_) => OperationType::Transfer,
     }
   }
+
+  /// The tick the operation names, also for operations that fail to execute.
+  pub fn tick(&self) -> Option<Tick> {
+    let tick = match self {
+      Operation::Deploy(deploy) => &deploy.tick,
+      Operation::Mint { mint, .. } => &mint.tick,
+      Operation::InscribeTransfer(transfer) | Operation::Transfer(transfer) => &transfer.tick,
+    };
+    tick.parse().ok()
+  }
 }
 
 #[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
@@ -300,4 +313,25 @@ mod tests {
       })
     );
   }
+
+  #[test]
+  fn test_operation_tick() {
+    let mint = |tick: &str| Operation::Mint {
+      mint: Mint {
+        tick: tick.to_string(),
+        amount: "1000".to_string(),
+      },
+      parent: None,
+    };
+    assert_eq!(mint("ORDI").tick(), Some("ORDI".parse().unwrap()));
+    assert_eq!(mint("abcdef").tick(), None);
+    assert_eq!(
+      Operation::Transfer(Transfer {
+        tick: "abcd".to_string(),
+        amount: "12000".to_string()
+      })
+      .tick(),
+      Some("abcd".parse().unwrap())
+    );
+  }
 }
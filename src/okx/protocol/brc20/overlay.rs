@@ -0,0 +1,334 @@
+use crate::{
+  index::{
+    entry::{Entry, SatPointValue},
+    rtx::Rtx,
+    BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, BRC20_BALANCES, BRC20_EVENTS,
+    BRC20_HEIGHT_TO_CHECKSUM, BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, BRC20_TOKEN,
+  },
+  okx::datastore::{
+    brc20::{
+      redb::table::{
+        get_balance, get_balances, get_block_checksum, get_token_info, get_tokens_info,
+        get_transaction_receipts, get_transferable_assets_by_account,
+        get_transferable_assets_by_account_ticker, get_transferable_assets_by_outpoint,
+        get_transferable_assets_by_satpoint,
+      },
+      Balance, Brc20Reader, Brc20ReaderWriter, Receipt, Tick, TokenInfo, TransferableLog,
+    },
+    ScriptKey,
+  },
+  SatPoint,
+};
+use anyhow::anyhow;
+use bitcoin::{hashes::sha256, OutPoint, Txid};
+use redb::ReadOnlyTable;
+use std::collections::HashMap;
+
+/// The BRC20 state of a read transaction with the changes of unconfirmed transactions kept in
+/// memory on top of it, so that they are never written to the index.
+///
+/// Receipts, checksums and burns are not kept, only the state later messages read.
+#[allow(non_snake_case)]
+pub(crate) struct Brc20Overlay<'db, 'txn> {
+  /// The read transaction the tables which are rarely read are opened on.
+  rtx: &'txn Rtx<'db>,
+  BRC20_BALANCES: ReadOnlyTable<'txn, &'static str, &'static [u8]>,
+  BRC20_TOKEN: ReadOnlyTable<'txn, &'static str, &'static [u8]>,
+  BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: ReadOnlyTable<'txn, &'static SatPointValue, &'static [u8]>,
+  /// Balances by owner and lowercase tick.
+  balances: HashMap<(ScriptKey, String), Balance>,
+  /// Tokens by lowercase tick.
+  tokens: HashMap<String, TokenInfo>,
+  /// Transferable assets by satpoint, `None` once they are transferred.
+  transferable_assets: HashMap<SatPointValue, Option<TransferableLog>>,
+}
+
+impl<'db, 'txn> Brc20Overlay<'db, 'txn> {
+  pub(crate) fn open(rtx: &'txn Rtx<'db>) -> crate::Result<Self> {
+    Ok(Self {
+      rtx,
+      BRC20_BALANCES: rtx.0.open_table(BRC20_BALANCES)?,
+      BRC20_TOKEN: rtx.0.open_table(BRC20_TOKEN)?,
+      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: rtx
+        .0
+        .open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
+      balances: HashMap::new(),
+      tokens: HashMap::new(),
+      transferable_assets: HashMap::new(),
+    })
+  }
+
+  /// Drop the confirmed transferable assets which have been transferred since and add the new
+  /// ones accepted by `filter`.
+  fn overlay_transferable_assets(
+    &self,
+    confirmed: Vec<(SatPoint, TransferableLog)>,
+    filter: impl Fn(&SatPoint, &TransferableLog) -> bool,
+  ) -> Vec<(SatPoint, TransferableLog)> {
+    let mut assets = confirmed
+      .into_iter()
+      .filter(|(satpoint, _)| !self.transferable_assets.contains_key(&satpoint.store()))
+      .collect::<Vec<_>>();
+
+    let mut unconfirmed = self
+      .transferable_assets
+      .iter()
+      .filter_map(|(satpoint, asset)| {
+        let satpoint = SatPoint::load(*satpoint);
+        asset
+          .as_ref()
+          .filter(|asset| filter(&satpoint, asset))
+          .map(|asset| (satpoint, asset.clone()))
+      })
+      .collect::<Vec<_>>();
+    unconfirmed.sort_by_key(|(satpoint, _)| satpoint.store());
+
+    assets.extend(unconfirmed);
+    assets
+  }
+}
+
+impl<'db, 'txn> Brc20Reader for Brc20Overlay<'db, 'txn> {
+  type Error = anyhow::Error;
+
+  fn get_balances(&self, script_key: &ScriptKey) -> crate::Result<Vec<Balance>, Self::Error> {
+    let mut balances = get_balances(&self.BRC20_BALANCES, script_key)?
+      .into_iter()
+      .filter(|balance| {
+        !self
+          .balances
+          .contains_key(&(script_key.clone(), balance.tick.to_lowercase().hex()))
+      })
+      .collect::<Vec<_>>();
+
+    balances.extend(
+      self
+        .balances
+        .iter()
+        .filter(|((owner, _), _)| owner == script_key)
+        .map(|(_, balance)| balance.clone()),
+    );
+
+    Ok(balances)
+  }
+
+  fn get_balance(
+    &self,
+    script_key: &ScriptKey,
+    tick: &Tick,
+  ) -> crate::Result<Option<Balance>, Self::Error> {
+    match self
+      .balances
+      .get(&(script_key.clone(), tick.to_lowercase().hex()))
+    {
+      Some(balance) => Ok(Some(balance.clone())),
+      None => get_balance(&self.BRC20_BALANCES, script_key, tick),
+    }
+  }
+
+  fn get_token_info(&self, tick: &Tick) -> crate::Result<Option<TokenInfo>, Self::Error> {
+    match self.tokens.get(&tick.to_lowercase().hex()) {
+      Some(info) => Ok(Some(info.clone())),
+      None => get_token_info(&self.BRC20_TOKEN, tick),
+    }
+  }
+
+  fn get_block_checksum(&self, height: u32) -> crate::Result<Option<sha256::Hash>, Self::Error> {
+    get_block_checksum(&self.rtx.0.open_table(BRC20_HEIGHT_TO_CHECKSUM)?, height)
+  }
+
+  fn get_tokens_info(&self) -> crate::Result<Vec<TokenInfo>, Self::Error> {
+    let mut tokens = get_tokens_info(&self.BRC20_TOKEN)?
+      .into_iter()
+      .filter(|info| !self.tokens.contains_key(&info.tick.to_lowercase().hex()))
+      .collect::<Vec<_>>();
+    tokens.extend(self.tokens.values().cloned());
+    Ok(tokens)
+  }
+
+  fn get_transaction_receipts(
+    &self,
+    txid: &Txid,
+  ) -> crate::Result<Option<Vec<Receipt>>, Self::Error> {
+    get_transaction_receipts(&self.rtx.0.open_table(BRC20_EVENTS)?, txid)
+  }
+
+  fn get_transferable_assets_by_satpoint(
+    &self,
+    satpoint: &SatPoint,
+  ) -> crate::Result<Option<TransferableLog>, Self::Error> {
+    match self.transferable_assets.get(&satpoint.store()) {
+      Some(asset) => Ok(asset.clone()),
+      None => {
+        get_transferable_assets_by_satpoint(&self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, satpoint)
+      }
+    }
+  }
+
+  fn get_transferable_assets_by_account(
+    &self,
+    script: &ScriptKey,
+  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
+    Ok(
+      self.overlay_transferable_assets(
+        get_transferable_assets_by_account(
+          &self
+            .rtx
+            .0
+            .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
+          &self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+          script,
+        )?,
+        |_, asset| asset.owner == *script,
+      ),
+    )
+  }
+
+  fn get_transferable_assets_by_account_ticker(
+    &self,
+    script: &ScriptKey,
+    tick: &Tick,
+  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
+    Ok(
+      self.overlay_transferable_assets(
+        get_transferable_assets_by_account_ticker(
+          &self
+            .rtx
+            .0
+            .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
+          &self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+          script,
+          tick,
+        )?,
+        |_, asset| asset.owner == *script && asset.tick.to_lowercase() == tick.to_lowercase(),
+      ),
+    )
+  }
+
+  fn get_transferable_assets_by_outpoint(
+    &self,
+    outpoint: OutPoint,
+  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
+    Ok(self.overlay_transferable_assets(
+      get_transferable_assets_by_outpoint(&self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, outpoint)?,
+      |satpoint, _| satpoint.outpoint == outpoint,
+    ))
+  }
+}
+
+impl<'db, 'txn> Brc20ReaderWriter for Brc20Overlay<'db, 'txn> {
+  fn update_token_balance(
+    &mut self,
+    script_key: &ScriptKey,
+    new_balance: Balance,
+  ) -> crate::Result<(), Self::Error> {
+    self.balances.insert(
+      (script_key.clone(), new_balance.tick.to_lowercase().hex()),
+      new_balance,
+    );
+    Ok(())
+  }
+
+  fn save_block_checksum(
+    &mut self,
+    _height: u32,
+    _checksum: &sha256::Hash,
+  ) -> crate::Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn insert_token_info(
+    &mut self,
+    tick: &Tick,
+    new_info: &TokenInfo,
+  ) -> crate::Result<(), Self::Error> {
+    self
+      .tokens
+      .insert(tick.to_lowercase().hex(), new_info.clone());
+    Ok(())
+  }
+
+  fn update_mint_token_info(
+    &mut self,
+    tick: &Tick,
+    minted_amt: u128,
+    minted_block_number: u32,
+  ) -> crate::Result<(), Self::Error> {
+    let mut info = self
+      .get_token_info(tick)?
+      .ok_or_else(|| anyhow!("token {} not exist", tick.as_str()))?;
+    info.minted = minted_amt;
+    info.latest_mint_number = minted_block_number;
+    self.insert_token_info(tick, &info)
+  }
+
+  fn update_burned_token_info(
+    &mut self,
+    tick: &Tick,
+    burned_amt: u128,
+  ) -> crate::Result<(), Self::Error> {
+    let mut info = self
+      .get_token_info(tick)?
+      .ok_or_else(|| anyhow!("token {} not exist", tick.as_str()))?;
+    info.burned_supply = burned_amt;
+    self.insert_token_info(tick, &info)
+  }
+
+  fn save_transaction_receipts(
+    &mut self,
+    _txid: &Txid,
+    _receipt: &[Receipt],
+  ) -> crate::Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn save_address_receipt(
+    &mut self,
+    _script_key: &ScriptKey,
+    _height: u32,
+    _tx_index: u32,
+    _txid: &Txid,
+    _index: u32,
+    _receipt: &Receipt,
+  ) -> crate::Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn save_tick_receipt(
+    &mut self,
+    _tick: &Tick,
+    _height: u32,
+    _tx_index: u32,
+    _txid: &Txid,
+    _index: u32,
+    _receipt: &Receipt,
+  ) -> crate::Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn save_tick_burn(
+    &mut self,
+    _tick: &Tick,
+    _height: u32,
+    _tx_index: u32,
+    _index: u32,
+  ) -> crate::Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn insert_transferable_asset(
+    &mut self,
+    satpoint: SatPoint,
+    inscription: &TransferableLog,
+  ) -> crate::Result<(), Self::Error> {
+    self
+      .transferable_assets
+      .insert(satpoint.store(), Some(inscription.clone()));
+    Ok(())
+  }
+
+  fn remove_transferable_asset(&mut self, satpoint: SatPoint) -> crate::Result<(), Self::Error> {
+    self.transferable_assets.insert(satpoint.store(), None);
+    Ok(())
+  }
+}
@@ -0,0 +1,175 @@
+use super::*;
+use crate::{
+  index::{
+    entry::{Entry, SatPointValue},
+    BlockData, TableInfo,
+  },
+  okx::{
+    datastore::{
+      brc20::{
+        redb::table::get_transferable_assets_by_outpoint, Brc20ReaderWriter, TransferableLog,
+      },
+      ord::{collections::CollectionKind, operation::InscriptionOp, OrdReaderWriter},
+    },
+    protocol::{brc20::tables::Brc20Tables, context::Context, Protocol},
+  },
+  Inscription,
+};
+use anyhow::anyhow;
+use redb::WriteTransaction;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The BRC20 protocol, indexed from `first_brc20_height` on.
+pub struct Brc20Protocol {
+  first_brc20_height: u32,
+}
+
+impl Brc20Protocol {
+  pub fn new(first_brc20_height: u32) -> Self {
+    Self { first_brc20_height }
+  }
+}
+
+impl Protocol for Brc20Protocol {
+  type Message = Message;
+  type Tables<'db, 'txn>
+    = Brc20Tables<'db, 'txn>
+  where
+    'db: 'txn;
+
+  fn name(&self) -> &'static str {
+    "BRC20"
+  }
+
+  fn is_active(&self, context: &Context) -> bool {
+    context.chain_conf.blockheight >= self.first_brc20_height
+  }
+
+  fn create_tables(&self, wtx: &WriteTransaction) -> Result {
+    Brc20Tables::open(wtx, 0)?;
+    Ok(())
+  }
+
+  fn insert_table_info(
+    &self,
+    tables: &mut BTreeMap<String, TableInfo>,
+    wtx: &WriteTransaction,
+    database_total_bytes: u64,
+  ) {
+    Brc20Tables::insert_table_info(tables, wtx, database_total_bytes);
+  }
+
+  fn open_tables<'db, 'txn>(
+    &self,
+    context: &Context<'_, 'db, 'txn>,
+  ) -> Result<Brc20Tables<'db, 'txn>> {
+    Brc20Tables::open(context.wtx, context.chain_conf.blockheight)
+  }
+
+  fn resolve(
+    &self,
+    _context: &Context,
+    tables: &Brc20Tables,
+    operation: &InscriptionOp,
+    new_inscriptions: &[Inscription],
+  ) -> Result<Option<Message>> {
+    let satpoint_to_transfer_assets: HashMap<SatPointValue, TransferableLog> =
+      get_transferable_assets_by_outpoint(
+        &tables.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+        operation.old_satpoint.outpoint,
+      )?
+      .into_iter()
+      .map(|(satpoint, asset)| (satpoint.store(), asset))
+      .collect();
+
+    Message::resolve(operation, new_inscriptions, satpoint_to_transfer_assets)
+  }
+
+  fn execute(
+    &self,
+    context: &mut Context,
+    tables: &mut Brc20Tables,
+    tx_index: u32,
+    txid: &Txid,
+    messages: &[Message],
+  ) -> Result {
+    let mut receipts = vec![];
+    let mut ticks = vec![];
+    for msg in messages {
+      let execution_msg = ExecutionMessage::from_message(context, msg, context.chain_conf.chain)?;
+      let receipt = execute(&context.chain_conf, tables, &execution_msg)?;
+      // failed receipts carry no event, their tick is the one named by the operation.
+      ticks.push(receipt.tick().cloned().or_else(|| msg.op.tick()));
+      receipts.push(receipt);
+    }
+
+    tables
+      .save_transaction_receipts(txid, &receipts)
+      .map_err(|e| anyhow!("failed to add transaction receipt to state! error: {e}"))?;
+
+    // index receipts by the addresses of both sides.
+    for (index, (receipt, tick)) in receipts.iter().zip(&ticks).enumerate() {
+      let index = u32::try_from(index).unwrap();
+      let mut script_keys = vec![&receipt.from];
+      if receipt.to != receipt.from {
+        script_keys.push(&receipt.to);
+      }
+      for script_key in script_keys {
+        tables
+          .save_address_receipt(
+            script_key,
+            context.chain_conf.blockheight,
+            tx_index,
+            txid,
+            index,
+            receipt,
+          )
+          .map_err(|e| anyhow!("failed to add address receipt to state! error: {e}"))?;
+      }
+
+      // index receipts by the ticker they touch, whether they succeeded or not.
+      if let Some(tick) = tick {
+        tables
+          .save_tick_receipt(
+            tick,
+            context.chain_conf.blockheight,
+            tx_index,
+            txid,
+            index,
+            receipt,
+          )
+          .map_err(|e| anyhow!("failed to add tick receipt to state! error: {e}"))?;
+
+        if receipt.is_burn() {
+          tables
+            .save_tick_burn(tick, context.chain_conf.blockheight, tx_index, index)
+            .map_err(|e| anyhow!("failed to add tick burn to state! error: {e}"))?;
+        }
+      }
+    }
+
+    let brc20_inscriptions = receipts
+      .into_iter()
+      .map(|receipt| receipt.inscription_id)
+      .collect::<HashSet<_>>();
+
+    for inscription_id in brc20_inscriptions {
+      context
+        .add_inscription_attributes(&inscription_id, CollectionKind::BRC20)
+        .map_err(|e| anyhow!("failed to add inscription attributes to state! error: {e}"))?;
+    }
+    Ok(())
+  }
+
+  fn finalize_block(
+    &self,
+    context: &mut Context,
+    tables: &mut Brc20Tables,
+    block: &BlockData,
+    operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result {
+    // commit the BRC20 state of the block so indexers can compare it with each other.
+    update_block_checksum(context, tables, block, operations)?;
+    Ok(())
+  }
+}
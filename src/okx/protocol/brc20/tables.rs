@@ -0,0 +1,306 @@
+use crate::{
+  index::{
+    entry::SatPointValue, insert_multimap_table_info, insert_table_info, TableInfo, TxidValue,
+    BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, BRC20_ADDRESS_TO_RECEIPTS, BRC20_BALANCES,
+    BRC20_BALANCE_JOURNAL, BRC20_EVENTS, BRC20_HEIGHT_TO_CHECKSUM,
+    BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, BRC20_TICKER_TO_BURNS, BRC20_TICKER_TO_HOLDERS,
+    BRC20_TICKER_TO_RECEIPTS, BRC20_TOKEN,
+  },
+  okx::datastore::{
+    brc20::{
+      redb::table::{
+        get_balance, get_balances, get_block_checksum, get_token_info, get_tokens_info,
+        get_transaction_receipts, get_transferable_assets_by_account,
+        get_transferable_assets_by_account_ticker, get_transferable_assets_by_outpoint,
+        get_transferable_assets_by_satpoint, insert_token_info, insert_transferable_asset,
+        remove_transferable_asset, save_address_receipt, save_block_checksum, save_tick_burn,
+        save_tick_receipt, save_transaction_receipts, update_burned_token_info,
+        update_mint_token_info, update_token_balance,
+      },
+      Balance, Brc20Reader, Brc20ReaderWriter, Receipt, Tick, TokenInfo, TransferableLog,
+    },
+    ScriptKey,
+  },
+  SatPoint,
+};
+use bitcoin::{hashes::sha256, OutPoint, Txid};
+use redb::{MultimapTable, Table, WriteTransaction};
+use std::collections::BTreeMap;
+
+/// The tables the BRC20 protocol keeps its state in, opened once per block.
+#[allow(non_snake_case)]
+pub struct Brc20Tables<'db, 'txn> {
+  /// The height of the block being indexed, at which balance changes are journaled.
+  pub(crate) height: u32,
+  pub(crate) BRC20_BALANCES: Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_TOKEN: Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_EVENTS: Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
+  pub(crate) BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS:
+    Table<'db, 'txn, &'static SatPointValue, &'static [u8]>,
+  pub(crate) BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS:
+    MultimapTable<'db, 'txn, &'static str, &'static SatPointValue>,
+  pub(crate) BRC20_ADDRESS_TO_RECEIPTS: Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_TICKER_TO_HOLDERS: Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_TICKER_TO_RECEIPTS: Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_TICKER_TO_BURNS: Table<'db, 'txn, &'static str, ()>,
+  pub(crate) BRC20_BALANCE_JOURNAL: Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_HEIGHT_TO_CHECKSUM: Table<'db, 'txn, u32, &'static [u8; 32]>,
+}
+
+impl<'db, 'txn> Brc20Tables<'db, 'txn> {
+  pub(crate) fn open(wtx: &'txn WriteTransaction<'db>, height: u32) -> crate::Result<Self> {
+    Ok(Self {
+      height,
+      BRC20_BALANCES: wtx.open_table(BRC20_BALANCES)?,
+      BRC20_TOKEN: wtx.open_table(BRC20_TOKEN)?,
+      BRC20_EVENTS: wtx.open_table(BRC20_EVENTS)?,
+      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: wtx
+        .open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
+      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS: wtx
+        .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
+      BRC20_ADDRESS_TO_RECEIPTS: wtx.open_table(BRC20_ADDRESS_TO_RECEIPTS)?,
+      BRC20_TICKER_TO_HOLDERS: wtx.open_table(BRC20_TICKER_TO_HOLDERS)?,
+      BRC20_TICKER_TO_RECEIPTS: wtx.open_table(BRC20_TICKER_TO_RECEIPTS)?,
+      BRC20_TICKER_TO_BURNS: wtx.open_table(BRC20_TICKER_TO_BURNS)?,
+      BRC20_BALANCE_JOURNAL: wtx.open_table(BRC20_BALANCE_JOURNAL)?,
+      BRC20_HEIGHT_TO_CHECKSUM: wtx.open_table(BRC20_HEIGHT_TO_CHECKSUM)?,
+    })
+  }
+
+  pub(crate) fn insert_table_info(
+    tables: &mut BTreeMap<String, TableInfo>,
+    wtx: &WriteTransaction,
+    total_bytes: u64,
+  ) {
+    insert_table_info(tables, wtx, total_bytes, BRC20_BALANCES);
+    insert_table_info(tables, wtx, total_bytes, BRC20_TOKEN);
+    insert_table_info(tables, wtx, total_bytes, BRC20_EVENTS);
+    insert_table_info(
+      tables,
+      wtx,
+      total_bytes,
+      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+    );
+    insert_multimap_table_info(
+      tables,
+      wtx,
+      total_bytes,
+      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+    );
+    insert_table_info(tables, wtx, total_bytes, BRC20_ADDRESS_TO_RECEIPTS);
+    insert_table_info(tables, wtx, total_bytes, BRC20_TICKER_TO_HOLDERS);
+    insert_table_info(tables, wtx, total_bytes, BRC20_TICKER_TO_RECEIPTS);
+    insert_table_info(tables, wtx, total_bytes, BRC20_TICKER_TO_BURNS);
+    insert_table_info(tables, wtx, total_bytes, BRC20_BALANCE_JOURNAL);
+    insert_table_info(tables, wtx, total_bytes, BRC20_HEIGHT_TO_CHECKSUM);
+  }
+}
+
+impl<'db, 'txn> Brc20Reader for Brc20Tables<'db, 'txn> {
+  type Error = anyhow::Error;
+
+  fn get_balances(&self, script_key: &ScriptKey) -> crate::Result<Vec<Balance>, Self::Error> {
+    get_balances(&self.BRC20_BALANCES, script_key)
+  }
+
+  fn get_balance(
+    &self,
+    script_key: &ScriptKey,
+    tick: &Tick,
+  ) -> crate::Result<Option<Balance>, Self::Error> {
+    get_balance(&self.BRC20_BALANCES, script_key, tick)
+  }
+
+  fn get_token_info(&self, tick: &Tick) -> crate::Result<Option<TokenInfo>, Self::Error> {
+    get_token_info(&self.BRC20_TOKEN, tick)
+  }
+
+  fn get_block_checksum(&self, height: u32) -> crate::Result<Option<sha256::Hash>, Self::Error> {
+    get_block_checksum(&self.BRC20_HEIGHT_TO_CHECKSUM, height)
+  }
+
+  fn get_tokens_info(&self) -> crate::Result<Vec<TokenInfo>, Self::Error> {
+    get_tokens_info(&self.BRC20_TOKEN)
+  }
+
+  fn get_transaction_receipts(
+    &self,
+    txid: &Txid,
+  ) -> crate::Result<Option<Vec<Receipt>>, Self::Error> {
+    get_transaction_receipts(&self.BRC20_EVENTS, txid)
+  }
+
+  fn get_transferable_assets_by_account(
+    &self,
+    script: &ScriptKey,
+  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
+    get_transferable_assets_by_account(
+      &self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      &self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+      script,
+    )
+  }
+
+  fn get_transferable_assets_by_account_ticker(
+    &self,
+    script: &ScriptKey,
+    tick: &Tick,
+  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
+    get_transferable_assets_by_account_ticker(
+      &self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      &self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+      script,
+      tick,
+    )
+  }
+
+  fn get_transferable_assets_by_satpoint(
+    &self,
+    satpoint: &SatPoint,
+  ) -> crate::Result<Option<TransferableLog>, Self::Error> {
+    get_transferable_assets_by_satpoint(&self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, satpoint)
+  }
+
+  fn get_transferable_assets_by_outpoint(
+    &self,
+    outpoint: OutPoint,
+  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
+    get_transferable_assets_by_outpoint(&self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, outpoint)
+  }
+}
+
+impl<'db, 'txn> Brc20ReaderWriter for Brc20Tables<'db, 'txn> {
+  fn update_token_balance(
+    &mut self,
+    script_key: &ScriptKey,
+    new_balance: Balance,
+  ) -> crate::Result<(), Self::Error> {
+    update_token_balance(
+      &mut self.BRC20_BALANCES,
+      &mut self.BRC20_TICKER_TO_HOLDERS,
+      &mut self.BRC20_TOKEN,
+      &mut self.BRC20_BALANCE_JOURNAL,
+      script_key,
+      new_balance,
+      self.height,
+    )
+  }
+
+  fn save_block_checksum(
+    &mut self,
+    height: u32,
+    checksum: &sha256::Hash,
+  ) -> crate::Result<(), Self::Error> {
+    save_block_checksum(&mut self.BRC20_HEIGHT_TO_CHECKSUM, height, checksum)
+  }
+
+  fn insert_token_info(
+    &mut self,
+    tick: &Tick,
+    new_info: &TokenInfo,
+  ) -> crate::Result<(), Self::Error> {
+    insert_token_info(&mut self.BRC20_TOKEN, tick, new_info)
+  }
+
+  fn update_mint_token_info(
+    &mut self,
+    tick: &Tick,
+    minted_amt: u128,
+    minted_block_number: u32,
+  ) -> crate::Result<(), Self::Error> {
+    update_mint_token_info(&mut self.BRC20_TOKEN, tick, minted_amt, minted_block_number)
+  }
+
+  fn update_burned_token_info(
+    &mut self,
+    tick: &Tick,
+    burned_amt: u128,
+  ) -> crate::Result<(), Self::Error> {
+    update_burned_token_info(&mut self.BRC20_TOKEN, tick, burned_amt)
+  }
+
+  fn save_transaction_receipts(
+    &mut self,
+    txid: &Txid,
+    receipt: &[Receipt],
+  ) -> crate::Result<(), Self::Error> {
+    save_transaction_receipts(&mut self.BRC20_EVENTS, txid, receipt)
+  }
+
+  fn save_address_receipt(
+    &mut self,
+    script_key: &ScriptKey,
+    height: u32,
+    tx_index: u32,
+    txid: &Txid,
+    index: u32,
+    receipt: &Receipt,
+  ) -> crate::Result<(), Self::Error> {
+    save_address_receipt(
+      &mut self.BRC20_ADDRESS_TO_RECEIPTS,
+      script_key,
+      height,
+      tx_index,
+      txid,
+      index,
+      receipt,
+    )
+  }
+
+  fn save_tick_receipt(
+    &mut self,
+    tick: &Tick,
+    height: u32,
+    tx_index: u32,
+    txid: &Txid,
+    index: u32,
+    receipt: &Receipt,
+  ) -> crate::Result<(), Self::Error> {
+    save_tick_receipt(
+      &mut self.BRC20_TICKER_TO_RECEIPTS,
+      tick,
+      height,
+      tx_index,
+      txid,
+      index,
+      receipt,
+    )
+  }
+
+  fn save_tick_burn(
+    &mut self,
+    tick: &Tick,
+    height: u32,
+    tx_index: u32,
+    index: u32,
+  ) -> crate::Result<(), Self::Error> {
+    save_tick_burn(
+      &mut self.BRC20_TICKER_TO_BURNS,
+      tick,
+      height,
+      tx_index,
+      index,
+    )
+  }
+
+  fn insert_transferable_asset(
+    &mut self,
+    satpoint: SatPoint,
+    transferable_asset: &TransferableLog,
+  ) -> crate::Result<(), Self::Error> {
+    insert_transferable_asset(
+      &mut self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+      &mut self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      satpoint,
+      transferable_asset,
+    )
+  }
+
+  fn remove_transferable_asset(&mut self, satpoint: SatPoint) -> crate::Result<(), Self::Error> {
+    remove_transferable_asset(
+      &mut self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+      &mut self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      satpoint,
+    )
+  }
+}
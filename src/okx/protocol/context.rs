@@ -1,28 +1,16 @@
 use crate::{
-  index::{
-    entry::SatPointValue, InscriptionEntryValue, InscriptionIdValue, OutPointValue, TxidValue,
-  },
+  index::{InscriptionEntryValue, InscriptionIdValue, OutPointValue, TxidValue},
   inscriptions::InscriptionId,
   okx::{
     datastore::{
-      brc20::{
-        redb::table::{
-          get_balance, get_balances, get_token_info, get_tokens_info, get_transaction_receipts,
-          get_transferable_assets_by_account, get_transferable_assets_by_account_ticker,
-          get_transferable_assets_by_outpoint, get_transferable_assets_by_satpoint,
-          insert_token_info, insert_transferable_asset, remove_transferable_asset,
-          save_transaction_receipts, update_burned_token_info, update_mint_token_info,
-          update_token_balance,
-        },
-        Balance, Brc20Reader, Brc20ReaderWriter, Receipt, Tick, TokenInfo, TransferableLog,
-      },
       ord::{
         collections::CollectionKind,
         redb::table::{
           add_inscription_attributes, get_collection_inscription_id,
-          get_collections_of_inscription, get_inscription_number_by_sequence_number,
-          get_transaction_operations, get_txout_by_outpoint, save_transaction_operations,
-          set_inscription_by_collection_key,
+          get_collections_of_inscription, get_inscription_district,
+          get_inscription_number_by_sequence_number, get_transaction_operations,
+          get_txout_by_outpoint, insert_district, save_transaction_operations,
+          set_inscription_by_collection_key, update_district_owner,
         },
         InscriptionOp, OrdReader, OrdReaderWriter,
       },
@@ -35,7 +23,7 @@ use crate::{
 };
 use anyhow::anyhow;
 use bitcoin::{OutPoint, TxOut, Txid};
-use redb::{MultimapTable, Table};
+use redb::{MultimapTable, Table, WriteTransaction};
 
 #[allow(non_snake_case)]
 pub struct Context<'a, 'db, 'txn> {
@@ -43,6 +31,8 @@ pub struct Context<'a, 'db, 'txn> {
   pub(crate) tx_out_cache: &'a mut SimpleLru<OutPoint, TxOut>,
   pub(crate) hit: u64,
   pub(crate) miss: u64,
+  /// The write transaction of the block, through which protocols open the tables they own.
+  pub(crate) wtx: &'txn WriteTransaction<'db>,
 
   // ord tables
   pub(crate) ORD_TX_TO_OPERATIONS: &'a mut Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
@@ -50,18 +40,13 @@ pub struct Context<'a, 'db, 'txn> {
     &'a mut Table<'db, 'txn, &'static str, InscriptionIdValue>,
   pub(crate) COLLECTIONS_INSCRIPTION_ID_TO_KINDS:
     &'a mut MultimapTable<'db, 'txn, InscriptionIdValue, &'static [u8]>,
+  pub(crate) BITMAP_DISTRICT_TO_INSCRIPTION_ID: &'a mut Table<'db, 'txn, u32, InscriptionIdValue>,
+  pub(crate) BITMAP_INSCRIPTION_ID_TO_DISTRICT: &'a mut Table<'db, 'txn, InscriptionIdValue, u32>,
+  pub(crate) BITMAP_DISTRICT_TO_OWNER: &'a mut Table<'db, 'txn, u32, &'static [u8]>,
+  pub(crate) BITMAP_OWNER_TO_DISTRICTS: &'a mut MultimapTable<'db, 'txn, &'static str, u32>,
   pub(crate) SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY:
     &'a mut Table<'db, 'txn, u32, InscriptionEntryValue>,
   pub(crate) OUTPOINT_TO_ENTRY: &'a mut Table<'db, 'txn, &'static OutPointValue, &'static [u8]>,
-
-  // BRC20 tables
-  pub(crate) BRC20_BALANCES: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
-  pub(crate) BRC20_TOKEN: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
-  pub(crate) BRC20_EVENTS: &'a mut Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
-  pub(crate) BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS:
-    &'a mut Table<'db, 'txn, &'static SatPointValue, &'static [u8]>,
-  pub(crate) BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS:
-    &'a mut MultimapTable<'db, 'txn, &'static str, &'static SatPointValue>,
 }
 
 impl<'a, 'db, 'txn> OrdReader for Context<'a, 'db, 'txn> {
@@ -122,6 +107,13 @@ impl<'a, 'db, 'txn> OrdReader for Context<'a, 'db, 'txn> {
   ) -> crate::Result<Option<InscriptionId>, Self::Error> {
     get_collection_inscription_id(self.COLLECTIONS_KEY_TO_INSCRIPTION_ID, collection_key)
   }
+
+  fn get_inscription_district(
+    &self,
+    inscription_id: &InscriptionId,
+  ) -> crate::Result<Option<u32>, Self::Error> {
+    get_inscription_district(self.BITMAP_INSCRIPTION_ID_TO_DISTRICT, inscription_id)
+  }
 }
 
 impl<'a, 'db, 'txn> OrdReaderWriter for Context<'a, 'db, 'txn> {
@@ -152,137 +144,30 @@ impl<'a, 'db, 'txn> OrdReaderWriter for Context<'a, 'db, 'txn> {
       kind,
     )
   }
-}
-
-impl<'a, 'db, 'txn> Brc20Reader for Context<'a, 'db, 'txn> {
-  type Error = anyhow::Error;
-
-  fn get_balances(&self, script_key: &ScriptKey) -> crate::Result<Vec<Balance>, Self::Error> {
-    get_balances(self.BRC20_BALANCES, script_key)
-  }
-
-  fn get_balance(
-    &self,
-    script_key: &ScriptKey,
-    tick: &Tick,
-  ) -> crate::Result<Option<Balance>, Self::Error> {
-    get_balance(self.BRC20_BALANCES, script_key, tick)
-  }
-
-  fn get_token_info(&self, tick: &Tick) -> crate::Result<Option<TokenInfo>, Self::Error> {
-    get_token_info(self.BRC20_TOKEN, tick)
-  }
-
-  fn get_tokens_info(&self) -> crate::Result<Vec<TokenInfo>, Self::Error> {
-    get_tokens_info(self.BRC20_TOKEN)
-  }
-
-  fn get_transaction_receipts(
-    &self,
-    txid: &Txid,
-  ) -> crate::Result<Option<Vec<Receipt>>, Self::Error> {
-    get_transaction_receipts(self.BRC20_EVENTS, txid)
-  }
-
-  fn get_transferable_assets_by_account(
-    &self,
-    script: &ScriptKey,
-  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
-    get_transferable_assets_by_account(
-      self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
-      self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-      script,
-    )
-  }
-
-  fn get_transferable_assets_by_account_ticker(
-    &self,
-    script: &ScriptKey,
-    tick: &Tick,
-  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
-    get_transferable_assets_by_account_ticker(
-      self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
-      self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-      script,
-      tick,
-    )
-  }
-
-  fn get_transferable_assets_by_satpoint(
-    &self,
-    satpoint: &SatPoint,
-  ) -> crate::Result<Option<TransferableLog>, Self::Error> {
-    get_transferable_assets_by_satpoint(self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, satpoint)
-  }
-
-  fn get_transferable_assets_by_outpoint(
-    &self,
-    outpoint: OutPoint,
-  ) -> crate::Result<Vec<(SatPoint, TransferableLog)>, Self::Error> {
-    get_transferable_assets_by_outpoint(self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, outpoint)
-  }
-}
-
-impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
-  fn update_token_balance(
-    &mut self,
-    script_key: &ScriptKey,
-    new_balance: Balance,
-  ) -> crate::Result<(), Self::Error> {
-    update_token_balance(self.BRC20_BALANCES, script_key, new_balance)
-  }
-
-  fn insert_token_info(
-    &mut self,
-    tick: &Tick,
-    new_info: &TokenInfo,
-  ) -> crate::Result<(), Self::Error> {
-    insert_token_info(self.BRC20_TOKEN, tick, new_info)
-  }
-
-  fn update_mint_token_info(
-    &mut self,
-    tick: &Tick,
-    minted_amt: u128,
-    minted_block_number: u32,
-  ) -> crate::Result<(), Self::Error> {
-    update_mint_token_info(self.BRC20_TOKEN, tick, minted_amt, minted_block_number)
-  }
-
-  fn update_burned_token_info(
-    &mut self,
-    tick: &Tick,
-    burned_amt: u128,
-  ) -> crate::Result<(), Self::Error> {
-    update_burned_token_info(self.BRC20_TOKEN, tick, burned_amt)
-  }
 
-  fn save_transaction_receipts(
+  fn insert_district(
     &mut self,
-    txid: &Txid,
-    receipt: &[Receipt],
+    number: u32,
+    inscription_id: &InscriptionId,
   ) -> crate::Result<(), Self::Error> {
-    save_transaction_receipts(self.BRC20_EVENTS, txid, receipt)
+    insert_district(
+      self.BITMAP_DISTRICT_TO_INSCRIPTION_ID,
+      self.BITMAP_INSCRIPTION_ID_TO_DISTRICT,
+      number,
+      inscription_id,
+    )
   }
 
-  fn insert_transferable_asset(
+  fn update_district_owner(
     &mut self,
-    satpoint: SatPoint,
-    transferable_asset: &TransferableLog,
+    number: u32,
+    owner: Option<&ScriptKey>,
   ) -> crate::Result<(), Self::Error> {
-    insert_transferable_asset(
-      self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-      self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
-      satpoint,
-      transferable_asset,
-    )
-  }
-
-  fn remove_transferable_asset(&mut self, satpoint: SatPoint) -> crate::Result<(), Self::Error> {
-    remove_transferable_asset(
-      self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-      self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
-      satpoint,
+    update_district_owner(
+      self.BITMAP_DISTRICT_TO_OWNER,
+      self.BITMAP_OWNER_TO_DISTRICTS,
+      number,
+      owner,
     )
   }
 }
@@ -2,20 +2,25 @@ use crate::okx::datastore::ord::{OrdReader, OrdReaderWriter};
 use crate::okx::protocol::context::Context;
 use {
   crate::{
-    okx::datastore::ord::{
-      bitmap::District,
-      collections::CollectionKind,
-      operation::{Action, InscriptionOp},
+    index::BlockData,
+    okx::datastore::{
+      ord::{
+        bitmap::District,
+        collections::CollectionKind,
+        operation::{Action, InscriptionOp},
+      },
+      ScriptKey,
     },
-    Inscription, InscriptionId, Result,
+    unbound_outpoint, Inscription, InscriptionId, Result,
   },
   anyhow::anyhow,
-  bitcoin::Txid,
+  bitcoin::{OutPoint, Txid},
   std::collections::HashMap,
 };
 
 pub fn index_bitmap(
   context: &mut Context,
+  block: &BlockData,
   operations: &HashMap<Txid, Vec<InscriptionOp>>,
 ) -> Result<u64> {
   let mut count = 0;
@@ -47,6 +52,7 @@ pub fn index_bitmap(
           let key = district.to_collection_key();
           context.set_inscription_by_collection_key(&key, &inscription_id)?;
           context.add_inscription_attributes(&inscription_id, CollectionKind::BitMap)?;
+          context.insert_district(district.number, &inscription_id)?;
 
           count += 1;
         }
@@ -54,9 +60,32 @@ pub fn index_bitmap(
       _ => unreachable!(),
     }
   }
+
+  // follow the district inscriptions in transaction order, so the last move of the block wins.
+  for (_, txid) in &block.txdata {
+    for op in operations.get(txid).into_iter().flatten() {
+      if let Some(number) = context.get_inscription_district(&op.inscription_id)? {
+        let owner = district_owner(context, op)?;
+        context.update_district_owner(number, owner.as_ref())?;
+      }
+    }
+  }
+
   Ok(count)
 }
 
+fn district_owner(context: &mut Context, op: &InscriptionOp) -> Result<Option<ScriptKey>> {
+  match op.new_satpoint {
+    Some(satpoint)
+      if satpoint.outpoint != OutPoint::null() && satpoint.outpoint != unbound_outpoint() =>
+    {
+      let chain = context.chain_conf.chain;
+      Ok(Some(context.get_script_key_on_satpoint(&satpoint, chain)?))
+    }
+    _ => Ok(None),
+  }
+}
+
 fn index_district(
   context: &mut Context,
   inscription: Inscription,
@@ -0,0 +1,85 @@
+use crate::okx::datastore::ord::{OrdReader, OrdReaderWriter};
+use crate::okx::protocol::context::Context;
+use {
+  crate::{
+    okx::datastore::ord::{
+      collections::CollectionKind,
+      names::Namespace,
+      operation::{Action, InscriptionOp},
+    },
+    Result,
+  },
+  anyhow::anyhow,
+  bitcoin::Txid,
+  std::collections::HashMap,
+};
+
+/// Register the names inscribed in the block, the inscription with the lowest number wins.
+pub fn index_names(
+  context: &mut Context,
+  namespaces: &[Namespace],
+  operations: &HashMap<Txid, Vec<InscriptionOp>>,
+) -> Result<u64> {
+  let mut count = 0;
+
+  // ignore transferred or cursed inscriptions.
+  let mut positive_inscriptions = operations
+    .values()
+    .flatten()
+    .filter(|op| {
+      !op.inscription_number.unwrap().is_negative() && matches!(op.action, Action::New { .. })
+    })
+    .collect::<Vec<_>>();
+
+  // sort by inscription number.
+  positive_inscriptions.sort_by_key(|op| op.inscription_number.unwrap());
+
+  for op in positive_inscriptions {
+    let Action::New { inscription, .. } = &op.action else {
+      unreachable!()
+    };
+
+    let Some(content) = inscription.body() else {
+      continue;
+    };
+
+    let Some((namespace, name)) = namespaces.iter().find_map(|namespace| {
+      namespace
+        .parse_name(content, context.chain_conf.blockheight)
+        .map(|name| (namespace, name))
+    }) else {
+      continue;
+    };
+
+    let collection_key = Namespace::to_collection_key(&name);
+
+    if context
+      .get_collection_inscription_id(&collection_key)
+      .map_err(|e| {
+        anyhow!("failed to get collection inscription! key: {collection_key} error: {e}")
+      })?
+      .is_some()
+    {
+      log::info!(
+        "duplicate name! name: {name} inscription_id {}",
+        op.inscription_id
+      );
+      continue;
+    }
+
+    log::info!(
+      "found valid name! name: {name} inscription_id {}",
+      op.inscription_id
+    );
+
+    context.set_inscription_by_collection_key(&collection_key, &op.inscription_id)?;
+    context.add_inscription_attributes(
+      &op.inscription_id,
+      CollectionKind::Name(namespace.name.clone()),
+    )?;
+
+    count += 1;
+  }
+
+  Ok(count)
+}
@@ -4,8 +4,13 @@ use {
   super::*,
   crate::{
     index::BlockData,
-    okx::{datastore::ord::operation::InscriptionOp, protocol::ord as ord_proto},
-    Instant, Result,
+    inscriptions::ParsedEnvelope,
+    metrics::BlockMetrics,
+    okx::{
+      datastore::ord::operation::InscriptionOp,
+      protocol::{ord as ord_proto, registry::BlockTransaction},
+    },
+    Duration, Inscription, Instant, Result,
   },
   bitcoin::Txid,
   std::collections::HashMap,
@@ -13,8 +18,8 @@ use {
 
 pub struct ProtocolManager {
   config: ProtocolConfig,
-  call_man: CallManager,
   resolve_man: MsgResolveManager,
+  registry: ProtocolRegistry,
 }
 
 impl ProtocolManager {
@@ -22,25 +27,31 @@ impl ProtocolManager {
   pub fn new(config: ProtocolConfig) -> Self {
     Self {
       config,
-      call_man: CallManager::new(),
-      resolve_man: MsgResolveManager::new(config),
+      resolve_man: MsgResolveManager::new(),
+      registry: ProtocolRegistry::default(),
     }
   }
 
+  /// Register an additional protocol, indexed after the ones registered before it.
+  pub fn register<P: Protocol + 'static>(&mut self, protocol: P) {
+    self.registry.register(protocol);
+  }
+
   pub(crate) fn index_block(
     &self,
     context: &mut Context,
     block: &BlockData,
     operations: HashMap<Txid, Vec<InscriptionOp>>,
+    metrics: &mut BlockMetrics,
   ) -> Result {
     let start = Instant::now();
     let mut inscriptions_size = 0;
     let mut messages_size = 0;
-    let mut cost1 = 0u128;
-    let mut cost2 = 0u128;
-    let mut cost3 = 0u128;
-    // skip the coinbase transaction.
-    for (tx, txid) in block.txdata.iter() {
+    let mut cost1 = Duration::ZERO;
+    let mut cost2 = Duration::ZERO;
+    let protocols = self.registry.active(context);
+    let mut transactions = Vec::new();
+    for (tx_index, (tx, txid)) in block.txdata.iter().enumerate() {
       // skip coinbase transaction.
       if tx
         .input
@@ -59,41 +70,70 @@ impl ProtocolManager {
           let start = Instant::now();
           context.save_transaction_operations(txid, tx_operations)?;
           inscriptions_size += tx_operations.len();
-          cost1 += start.elapsed().as_micros();
+          cost1 += start.elapsed();
         }
 
-        let start = Instant::now();
-        // Resolve and execute messages.
-        let messages = self
-          .resolve_man
-          .resolve_message(context, tx, tx_operations)?;
-        cost2 += start.elapsed().as_micros();
+        if protocols.is_empty() {
+          continue;
+        }
 
         let start = Instant::now();
-        self.call_man.execute_message(context, txid, &messages)?;
-        cost3 += start.elapsed().as_micros();
-        messages_size += messages.len();
+        transactions.push(BlockTransaction {
+          tx_index: u32::try_from(tx_index).unwrap(),
+          tx,
+          txid,
+          operations: tx_operations,
+          new_inscriptions: ParsedEnvelope::from_transaction(tx)
+            .into_iter()
+            .map(|v| v.payload)
+            .collect::<Vec<Inscription>>(),
+        });
+        cost2 += start.elapsed();
       }
     }
 
+    // Resolve and execute messages of every protocol.
+    let start_messages = Instant::now();
+    for protocol in &protocols {
+      messages_size += protocol.index_block(
+        &self.resolve_man,
+        context,
+        block,
+        &transactions,
+        &operations,
+      )?;
+    }
+    let cost3 = start_messages.elapsed();
+
     let bitmap_start = Instant::now();
     let mut bitmap_count = 0;
     if self.config.enable_index_bitmap {
-      bitmap_count = ord_proto::bitmap::index_bitmap(context, &operations)?;
+      bitmap_count = ord_proto::bitmap::index_bitmap(context, block, &operations)?;
     }
-    let cost4 = bitmap_start.elapsed().as_millis();
+    let mut names_count = 0;
+    if !self.config.name_namespaces.is_empty() {
+      names_count =
+        ord_proto::names::index_names(context, &self.config.name_namespaces, &operations)?;
+    }
+    let cost4 = bitmap_start.elapsed();
+
+    metrics.phase("ord_receipts", cost1);
+    metrics.phase("envelopes", cost2);
+    metrics.phase("protocol_messages", cost3);
+    metrics.phase("bitmap_and_names", cost4);
 
     log::info!(
-      "Protocol Manager indexed block {} with ord inscriptions {}, messages {}, bitmap {} in {} ms, {}/{}/{}/{}",
+      "Protocol Manager indexed block {} with ord inscriptions {}, messages {}, bitmap {}, names {} in {} ms, {}/{}/{}/{}",
       context.chain_conf.blockheight,
       inscriptions_size,
       messages_size,
       bitmap_count,
+      names_count,
       start.elapsed().as_millis(),
-      cost1/1000,
-      cost2/1000,
-      cost3/1000,
-      cost4,
+      cost1.as_millis(),
+      cost2.as_millis(),
+      cost3.as_millis(),
+      cost4.as_millis(),
     );
     Ok(())
   }
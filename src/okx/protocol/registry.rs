@@ -0,0 +1,338 @@
+use {
+  super::{brc20::Brc20Protocol, context::Context, resolve_manager::MsgResolveManager},
+  crate::{
+    index::{BlockData, TableInfo},
+    okx::datastore::ord::operation::InscriptionOp,
+    Inscription, Options, Result,
+  },
+  bitcoin::{Transaction, Txid},
+  redb::WriteTransaction,
+  std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+  },
+};
+
+/// An inscription based meta-protocol indexed by the `ProtocolManager`.
+///
+/// Messages are resolved from the inscription operations of a transaction and then executed in
+/// order. A protocol keeps its state in its own tables, which are created with the index in
+/// `create_tables`, reported by `ord index info` through `insert_table_info`, and opened once per
+/// block in `open_tables`.
+pub trait Protocol {
+  type Message: Debug;
+
+  /// The handles of the tables owned by the protocol.
+  type Tables<'db, 'txn>
+  where
+    'db: 'txn;
+
+  /// Unique name of the protocol.
+  fn name(&self) -> &'static str;
+
+  /// Whether the protocol is indexed at the height of the current block.
+  fn is_active(&self, context: &Context) -> bool;
+
+  /// Create the tables of the protocol, called when the index is created.
+  fn create_tables(&self, wtx: &WriteTransaction) -> Result;
+
+  /// Add the statistics of the tables of the protocol to `tables`, with
+  /// `crate::index::insert_table_info`.
+  fn insert_table_info(
+    &self,
+    tables: &mut BTreeMap<String, TableInfo>,
+    wtx: &WriteTransaction,
+    database_total_bytes: u64,
+  );
+
+  /// Open the tables of the protocol in the write transaction of the block.
+  fn open_tables<'db, 'txn>(
+    &self,
+    context: &Context<'_, 'db, 'txn>,
+  ) -> Result<Self::Tables<'db, 'txn>>;
+
+  /// Resolve the message of an inscription operation, if any.
+  ///
+  /// `new_inscriptions` are the inscriptions revealed by the transaction of the operation.
+  fn resolve(
+    &self,
+    context: &Context,
+    tables: &Self::Tables<'_, '_>,
+    operation: &InscriptionOp,
+    new_inscriptions: &[Inscription],
+  ) -> Result<Option<Self::Message>>;
+
+  /// Execute the messages resolved from the `tx_index`th transaction of the block.
+  fn execute(
+    &self,
+    context: &mut Context,
+    tables: &mut Self::Tables<'_, '_>,
+    tx_index: u32,
+    txid: &Txid,
+    messages: &[Self::Message],
+  ) -> Result;
+
+  /// Called once all transactions of the block have been executed.
+  fn finalize_block(
+    &self,
+    _context: &mut Context,
+    _tables: &mut Self::Tables<'_, '_>,
+    _block: &BlockData,
+    _operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result {
+    Ok(())
+  }
+}
+
+/// A transaction of the block with inscription operations.
+pub(crate) struct BlockTransaction<'a> {
+  pub(crate) tx_index: u32,
+  pub(crate) tx: &'a Transaction,
+  pub(crate) txid: &'a Txid,
+  pub(crate) operations: &'a [InscriptionOp],
+  /// The inscriptions revealed by the transaction.
+  pub(crate) new_inscriptions: Vec<Inscription>,
+}
+
+/// Object safe form of `Protocol`, so that protocols with different messages and tables can be
+/// registered together.
+pub(crate) trait RegisteredProtocol {
+  fn name(&self) -> &'static str;
+
+  fn is_active(&self, context: &Context) -> bool;
+
+  fn create_tables(&self, wtx: &WriteTransaction) -> Result;
+
+  fn insert_table_info(
+    &self,
+    tables: &mut BTreeMap<String, TableInfo>,
+    wtx: &WriteTransaction,
+    database_total_bytes: u64,
+  );
+
+  /// Resolve and execute the messages of the transactions of the block, returning the number of
+  /// messages.
+  fn index_block(
+    &self,
+    resolve_man: &MsgResolveManager,
+    context: &mut Context,
+    block: &BlockData,
+    transactions: &[BlockTransaction],
+    operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<usize>;
+}
+
+impl<P: Protocol> RegisteredProtocol for P {
+  fn name(&self) -> &'static str {
+    Protocol::name(self)
+  }
+
+  fn is_active(&self, context: &Context) -> bool {
+    Protocol::is_active(self, context)
+  }
+
+  fn create_tables(&self, wtx: &WriteTransaction) -> Result {
+    Protocol::create_tables(self, wtx)
+  }
+
+  fn insert_table_info(
+    &self,
+    tables: &mut BTreeMap<String, TableInfo>,
+    wtx: &WriteTransaction,
+    database_total_bytes: u64,
+  ) {
+    Protocol::insert_table_info(self, tables, wtx, database_total_bytes)
+  }
+
+  fn index_block(
+    &self,
+    resolve_man: &MsgResolveManager,
+    context: &mut Context,
+    block: &BlockData,
+    transactions: &[BlockTransaction],
+    operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<usize> {
+    let mut tables = self.open_tables(context)?;
+    let mut messages_size = 0;
+    for transaction in transactions {
+      let messages = resolve_man.resolve_message(
+        self,
+        context,
+        &tables,
+        transaction.tx,
+        transaction.operations,
+        &transaction.new_inscriptions,
+      )?;
+      self.execute(
+        context,
+        &mut tables,
+        transaction.tx_index,
+        transaction.txid,
+        &messages,
+      )?;
+      messages_size += messages.len();
+    }
+    self.finalize_block(context, &mut tables, block, operations)?;
+    Ok(messages_size)
+  }
+}
+
+/// Protocols indexed by the `ProtocolManager`, in registration order.
+#[derive(Default)]
+pub struct ProtocolRegistry {
+  protocols: Vec<Box<dyn RegisteredProtocol>>,
+}
+
+impl ProtocolRegistry {
+  /// Every protocol built into the indexer. Their tables exist in every index, whether the
+  /// protocols are indexed or not.
+  pub(crate) fn builtin(options: &Options) -> Self {
+    let mut registry = Self::default();
+    registry.register(Brc20Protocol::new(options.first_brc20_height()));
+    registry
+  }
+
+  pub fn register<P: Protocol + 'static>(&mut self, protocol: P) {
+    assert!(
+      !self.contains(Protocol::name(&protocol)),
+      "protocol {} is already registered",
+      Protocol::name(&protocol)
+    );
+    self.protocols.push(Box::new(protocol));
+  }
+
+  pub fn contains(&self, name: &str) -> bool {
+    self
+      .protocols
+      .iter()
+      .any(|protocol| protocol.name() == name)
+  }
+
+  pub(crate) fn active(&self, context: &Context) -> Vec<&dyn RegisteredProtocol> {
+    self
+      .protocols
+      .iter()
+      .map(|protocol| protocol.as_ref())
+      .filter(|protocol| protocol.is_active(context))
+      .collect()
+  }
+
+  pub(crate) fn create_tables(&self, wtx: &WriteTransaction) -> Result {
+    for protocol in &self.protocols {
+      protocol.create_tables(wtx)?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn insert_table_info(
+    &self,
+    tables: &mut BTreeMap<String, TableInfo>,
+    wtx: &WriteTransaction,
+    database_total_bytes: u64,
+  ) {
+    for protocol in &self.protocols {
+      protocol.insert_table_info(tables, wtx, database_total_bytes);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::insert_table_info,
+    redb::{Database, TableDefinition, TableHandle},
+    tempfile::NamedTempFile,
+  };
+
+  const DUMMY: TableDefinition<u32, u32> = TableDefinition::new("DUMMY");
+
+  struct Dummy;
+
+  impl Protocol for Dummy {
+    type Message = ();
+    type Tables<'db, 'txn>
+      = ()
+    where
+      'db: 'txn;
+
+    fn name(&self) -> &'static str {
+      "dummy"
+    }
+
+    fn is_active(&self, _: &Context) -> bool {
+      true
+    }
+
+    fn create_tables(&self, wtx: &WriteTransaction) -> Result {
+      wtx.open_table(DUMMY)?;
+      Ok(())
+    }
+
+    fn insert_table_info(
+      &self,
+      tables: &mut BTreeMap<String, TableInfo>,
+      wtx: &WriteTransaction,
+      database_total_bytes: u64,
+    ) {
+      insert_table_info(tables, wtx, database_total_bytes, DUMMY);
+    }
+
+    fn open_tables(&self, _: &Context) -> Result {
+      Ok(())
+    }
+
+    fn resolve(
+      &self,
+      _: &Context,
+      _: &(),
+      _: &InscriptionOp,
+      _: &[Inscription],
+    ) -> Result<Option<()>> {
+      Ok(None)
+    }
+
+    fn execute(&self, _: &mut Context, _: &mut (), _: u32, _: &Txid, _: &[()]) -> Result {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn register_protocol() {
+    let mut registry = ProtocolRegistry::default();
+    assert!(!registry.contains("dummy"));
+    registry.register(Dummy);
+    assert!(registry.contains("dummy"));
+  }
+
+  #[test]
+  #[should_panic(expected = "protocol dummy is already registered")]
+  fn register_duplicate_protocol() {
+    let mut registry = ProtocolRegistry::default();
+    registry.register(Dummy);
+    registry.register(Dummy);
+  }
+
+  #[test]
+  fn registry_creates_and_reports_protocol_tables() {
+    let mut registry = ProtocolRegistry::default();
+    registry.register(Dummy);
+
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    registry.create_tables(&wtx).unwrap();
+    assert_eq!(
+      wtx
+        .list_tables()
+        .unwrap()
+        .map(|table| table.name().to_string())
+        .collect::<Vec<String>>(),
+      ["DUMMY"]
+    );
+
+    let mut tables = BTreeMap::new();
+    registry.insert_table_info(&mut tables, &wtx, 1);
+    assert!(tables.contains_key("DUMMY"));
+  }
+}
@@ -1,48 +1,39 @@
 use {
-  super::*,
   crate::{
-    index::entry::{Entry, SatPointValue},
-    inscriptions::ParsedEnvelope,
     okx::{
-      datastore::{
-        brc20::{redb::table::get_transferable_assets_by_outpoint, TransferableLog},
-        ord::operation::InscriptionOp,
-      },
-      protocol::{context::Context, Message},
+      datastore::ord::operation::InscriptionOp,
+      protocol::{context::Context, Protocol},
     },
     Inscription, Result,
   },
   bitcoin::Transaction,
-  std::collections::HashMap,
 };
 
-pub struct MsgResolveManager {
-  config: ProtocolConfig,
-}
+pub struct MsgResolveManager {}
 
 impl MsgResolveManager {
-  pub fn new(config: ProtocolConfig) -> Self {
-    Self { config }
+  pub fn new() -> Self {
+    Self {}
   }
 
-  pub fn resolve_message(
+  pub fn resolve_message<P: Protocol + ?Sized>(
     &self,
+    protocol: &P,
     context: &Context,
+    tables: &P::Tables<'_, '_>,
     tx: &Transaction,
     operations: &[InscriptionOp],
-  ) -> Result<Vec<Message>> {
+    new_inscriptions: &[Inscription],
+  ) -> Result<Vec<P::Message>> {
     log::debug!(
-      "Resolve Manager indexed transaction {}, operations size: {}, data: {:?}",
+      "Resolve Manager indexed transaction {} for {}, operations size: {}, data: {:?}",
       tx.txid(),
+      protocol.name(),
       operations.len(),
       operations
     );
     let mut messages = Vec::new();
     let mut operation_iter = operations.iter().peekable();
-    let new_inscriptions = ParsedEnvelope::from_transaction(tx)
-      .into_iter()
-      .map(|v| v.payload)
-      .collect::<Vec<Inscription>>();
 
     for input in &tx.input {
       // "operations" is a list of all the operations in the current block, and they are ordered.
@@ -53,33 +44,14 @@ impl MsgResolveManager {
         }
         let operation = operation_iter.next().unwrap();
 
-        // Parse BRC20 message through inscription operation.
-        if self
-          .config
-          .first_brc20_height
-          .map(|height| context.chain_conf.blockheight >= height)
-          .unwrap_or(false)
-        {
-          let satpoint_to_transfer_assets: HashMap<SatPointValue, TransferableLog> =
-            get_transferable_assets_by_outpoint(
-              context.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-              input.previous_output,
-            )?
-            .into_iter()
-            .map(|(satpoint, asset)| (satpoint.store(), asset))
-            .collect();
-
-          if let Some(msg) =
-            brc20::Message::resolve(operation, &new_inscriptions, satpoint_to_transfer_assets)?
-          {
-            log::debug!(
-              "BRC20 resolved the message from {:?}, msg {:?}",
-              operation,
-              msg
-            );
-            messages.push(Message::BRC20(msg));
-            continue;
-          }
+        if let Some(msg) = protocol.resolve(context, tables, operation, new_inscriptions)? {
+          log::debug!(
+            "{} resolved the message from {:?}, msg {:?}",
+            protocol.name(),
+            operation,
+            msg
+          );
+          messages.push(msg);
         }
       }
     }
@@ -1,4 +1,4 @@
-use {super::*, bitcoincore_rpc::Auth};
+use {super::*, crate::okx::datastore::ord::names::Namespace, bitcoincore_rpc::Auth};
 
 #[derive(Clone, Default, Debug, Parser)]
 #[command(group(
@@ -14,6 +14,11 @@ pub struct Options {
   pub(crate) log_dir: Option<PathBuf>,
   #[arg(long, help = "Load Bitcoin Core data dir from <BITCOIN_DATA_DIR>.")]
   pub(crate) bitcoin_data_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Subscribe to the `hashblock` and `rawblock` ZMQ notifications of Bitcoin Core at <BITCOIN_ZMQ_URL>, e.g. `tcp://127.0.0.1:28332`, to index new blocks as soon as they arrive. The server keeps polling for new blocks in case the socket drops."
+  )]
+  pub(crate) bitcoin_zmq_url: Option<String>,
   #[arg(long, help = "Authenticate to Bitcoin Core RPC with <RPC_PASS>.")]
   pub(crate) bitcoin_rpc_pass: Option<String>,
   #[arg(long, help = "Authenticate to Bitcoin Core RPC as <RPC_USER>.")]
@@ -58,6 +63,11 @@ pub struct Options {
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
   )]
   pub(crate) index_runes: bool,
+  #[arg(
+    long,
+    help = "Read blocks from the blk*.dat files of the Bitcoin Core data dir instead of fetching them over RPC, except for the blocks closest to the chain tip. The outputs spent by those blocks are still fetched over RPC. Bitcoin Core's LevelDB block index is not read, blocks are located by scanning the headers in the blk files once, and then only the blocks written since on later updates."
+  )]
+  pub(crate) index_from_blk_files: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
@@ -81,6 +91,12 @@ pub struct Options {
   pub(crate) enable_save_ord_receipts: bool,
   #[arg(long, help = "Enable Index Bitmap Collection.")]
   pub(crate) enable_index_bitmap: bool,
+  #[arg(
+    long = "index-name",
+    value_name = "NAMESPACE",
+    help = "Index first-is-first names of <NAMESPACE>, formatted as `<NAME>[:<RULE>][@<ACTIVATION_HEIGHT>]`, e.g. `sats@779832`. <RULE> is `label` (default) or `number`. May be repeated."
+  )]
+  pub(crate) index_names: Vec<Namespace>,
   // OKX defined options.
   #[arg(long, help = "Enable Index all of BRC20 Protocol")]
   pub(crate) enable_index_brc20: bool,
@@ -89,6 +105,25 @@ pub struct Options {
     help = "Don't look for BRC20 messages below <FIRST_BRC20_HEIGHT>."
   )]
   pub(crate) first_brc20_height: Option<u32>,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Keep <MAX_SAVEPOINTS> savepoints to recover from reorgs. [default: 2]"
+  )]
+  pub(crate) max_savepoints: Option<u32>,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Create a savepoint every <SAVEPOINT_INTERVAL> blocks. [default: 10]"
+  )]
+  pub(crate) savepoint_interval: Option<u32>,
+  #[arg(
+    long,
+    conflicts_with_all = &["max_savepoints", "savepoint_interval"],
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Keep a savepoint at each of the last <BLOCK_SAVEPOINTS> blocks, same as `--max-savepoints <BLOCK_SAVEPOINTS> --savepoint-interval 1`, so that `ord index rollback` can go back to any of them. Each savepoint keeps the database pages it references from being reused, so the index grows with the number of savepoints."
+  )]
+  pub(crate) block_savepoints: Option<u32>,
   #[clap(long, default_value = "200", help = "DB commit interval.")]
   pub(crate) commit_height_interval: u64,
   #[clap(
@@ -163,6 +198,20 @@ impl Options {
     }
   }
 
+  pub(crate) fn max_savepoints(&self) -> u32 {
+    self
+      .block_savepoints
+      .unwrap_or_else(|| self.max_savepoints.unwrap_or(2))
+  }
+
+  pub(crate) fn savepoint_interval(&self) -> u32 {
+    if self.block_savepoints.is_some() {
+      1
+    } else {
+      self.savepoint_interval.unwrap_or(10)
+    }
+  }
+
   pub(crate) fn first_rune_height(&self) -> u32 {
     if integration_test() {
       0
@@ -192,21 +241,24 @@ impl Options {
       return Ok(cookie_file.clone());
     }
 
+    Ok(self.bitcoin_data_dir()?.join(".cookie"))
+  }
+
+  /// The Bitcoin Core data directory of the chain.
+  pub(crate) fn bitcoin_data_dir(&self) -> Result<PathBuf> {
     let path = if let Some(bitcoin_data_dir) = &self.bitcoin_data_dir {
       bitcoin_data_dir.clone()
     } else if cfg!(target_os = "linux") {
       dirs::home_dir()
-        .ok_or_else(|| anyhow!("failed to get cookie file path: could not get home dir"))?
+        .ok_or_else(|| anyhow!("failed to get Bitcoin Core data dir: could not get home dir"))?
         .join(".bitcoin")
     } else {
       dirs::data_dir()
-        .ok_or_else(|| anyhow!("failed to get cookie file path: could not get data dir"))?
+        .ok_or_else(|| anyhow!("failed to get Bitcoin Core data dir: could not get data dir"))?
         .join("Bitcoin")
     };
 
-    let path = self.chain().join_with_data_dir(&path);
-
-    Ok(path.join(".cookie"))
+    Ok(self.chain().join_with_data_dir(&path))
   }
 
   fn default_data_dir() -> PathBuf {
@@ -373,6 +425,69 @@ mod tests {
     );
   }
 
+  #[test]
+  fn index_names() {
+    assert_eq!(
+      Arguments::try_parse_from([
+        "ord",
+        "--index-name",
+        "sats@779832",
+        "--index-name",
+        "bitmap:number",
+        "index",
+        "update"
+      ])
+      .unwrap()
+      .options
+      .index_names
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<String>>(),
+      ["sats:label@779832", "bitmap:number@0"]
+    );
+
+    assert!(Arguments::try_parse_from(["ord", "--index-name", "SATS", "index", "update"]).is_err());
+  }
+
+  #[test]
+  fn savepoints() {
+    let options = Arguments::try_parse_from(["ord", "index", "update"])
+      .unwrap()
+      .options;
+    assert_eq!(options.max_savepoints(), 2);
+    assert_eq!(options.savepoint_interval(), 10);
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--max-savepoints=5",
+      "--savepoint-interval=3",
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options;
+    assert_eq!(options.max_savepoints(), 5);
+    assert_eq!(options.savepoint_interval(), 3);
+
+    let options = Arguments::try_parse_from(["ord", "--block-savepoints=100", "index", "update"])
+      .unwrap()
+      .options;
+    assert_eq!(options.max_savepoints(), 100);
+    assert_eq!(options.savepoint_interval(), 1);
+
+    assert!(Arguments::try_parse_from([
+      "ord",
+      "--block-savepoints=100",
+      "--max-savepoints=5",
+      "index",
+      "update"
+    ])
+    .is_err());
+    assert!(
+      Arguments::try_parse_from(["ord", "--savepoint-interval=0", "index", "update"]).is_err()
+    );
+  }
+
   #[test]
   fn cookie_file_overrides_network() {
     assert_eq!(
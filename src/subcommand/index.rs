@@ -1,15 +1,39 @@
 use super::*;
 
+pub mod brc20_checksum;
+mod brc20_export;
+pub mod brc20_import;
+pub mod compact_outputs;
 mod export;
 pub mod info;
+pub mod rollback;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
+  #[command(about = "Print BRC20 state checksums of blocks")]
+  Brc20Checksum(brc20_checksum::Brc20Checksum),
+  #[command(about = "Write a snapshot of the BRC20 state at the index tip")]
+  Brc20Export(brc20_export::Brc20Export),
+  #[command(
+    about = "Restore the BRC20 state from a snapshot taken at the index tip",
+    long_about = "Restore the BRC20 state from a snapshot taken at the index tip and print the BRC20 checksum of the snapshot block. The index must have been built up to the snapshot block without BRC20 state, for example without --enable-index-brc20. BRC20 messages are indexed from the block following the snapshot on, whatever --first-brc20-height is set to."
+  )]
+  Brc20Import(brc20_import::Brc20Import),
+  #[command(
+    about = "Prune spent outputs from the index",
+    long_about = "Prune spent outputs from the index. The blocks indexed before spent outputs were pruned while indexing are read from bitcoind in chunks. The height reached is recorded in the index, so an interrupted run resumes where it stopped, and once compacted the index is kept compact while indexing."
+  )]
+  CompactOutputs,
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(
+    about = "Roll the index back to a savepoint",
+    long_about = "Roll the index back to the most recent savepoint at or below a height. How far back savepoints reach is set with --max-savepoints and --savepoint-interval, or with --block-savepoints to be able to roll back to each of the most recent blocks. The index can not be rolled back past its oldest savepoint, going further back requires a reindex."
+  )]
+  Rollback(rollback::Rollback),
   #[command(about = "Update the index", alias = "run")]
   Update,
 }
@@ -17,8 +41,13 @@ pub(crate) enum IndexSubcommand {
 impl IndexSubcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
+      Self::Brc20Checksum(brc20_checksum) => brc20_checksum.run(options),
+      Self::Brc20Export(brc20_export) => brc20_export.run(options),
+      Self::Brc20Import(brc20_import) => brc20_import.run(options),
+      Self::CompactOutputs => compact_outputs::run(options),
       Self::Export(export) => export.run(options),
       Self::Info(info) => info.run(options),
+      Self::Rollback(rollback) => rollback.run(options),
       Self::Update => update::run(options),
     }
   }
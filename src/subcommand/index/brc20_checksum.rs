@@ -0,0 +1,41 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Brc20Checksum {
+  #[arg(long, help = "Print checksums starting at block <FROM>.")]
+  from: u32,
+  #[arg(long, help = "Print checksums up to and including block <TO>.")]
+  to: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockChecksum {
+  pub height: u32,
+  pub checksum: String,
+}
+
+impl Brc20Checksum {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if self.from > self.to {
+      bail!("--from {} is greater than --to {}", self.from, self.to);
+    }
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let rtx = index.begin_read()?;
+
+    let mut output = Vec::new();
+    for height in self.from..=self.to {
+      if let Some(checksum) = rtx.brc20_get_block_checksum(height)? {
+        output.push(BlockChecksum {
+          height,
+          checksum: checksum.to_string(),
+        });
+      }
+    }
+
+    Ok(Box::new(output))
+  }
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Brc20Export {
+  #[arg(long, help = "Write the BRC20 snapshot to <FILE>.")]
+  file: PathBuf,
+}
+
+impl Brc20Export {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    index.update()?;
+    index.brc20_export(&self.file)?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
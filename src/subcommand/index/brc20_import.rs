@@ -0,0 +1,25 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Brc20Import {
+  #[arg(long, help = "Read the BRC20 snapshot from <FILE>.")]
+  file: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub checksum: Option<String>,
+}
+
+impl Brc20Import {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    // the index must stay at the snapshot block, so it is not updated here.
+    let checksum = index.brc20_import(&self.file)?;
+
+    Ok(Box::new(Output {
+      checksum: checksum.map(|checksum| checksum.to_string()),
+    }))
+  }
+}
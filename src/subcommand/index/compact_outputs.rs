@@ -0,0 +1,17 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub blocks: u64,
+  pub pruned: u64,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+
+  index.update()?;
+
+  let (blocks, pruned) = index.compact_outputs()?;
+
+  Ok(Box::new(Output { blocks, pruned }))
+}
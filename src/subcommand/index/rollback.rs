@@ -0,0 +1,26 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Rollback {
+  #[arg(
+    long,
+    help = "Roll back to the most recent savepoint at or below block <HEIGHT>. <HEIGHT> must not be below the oldest savepoint."
+  )]
+  height: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub block_count: u32,
+}
+
+impl Rollback {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    // the index is not updated, it would move forward again.
+    let block_count = index.rollback(self.height)?;
+
+    Ok(Box::new(Output { block_count }))
+  }
+}
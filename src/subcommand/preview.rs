@@ -111,6 +111,7 @@ impl Preview {
               compress: false,
               destination: None,
               dry_run: false,
+              psbt: false,
               fee_rate: FeeRate::try_from(1.0).unwrap(),
               file: Some(file),
               json_metadata: None,
@@ -144,6 +145,7 @@ impl Preview {
               compress: false,
               destination: None,
               dry_run: false,
+              psbt: false,
               fee_rate: FeeRate::try_from(1.0).unwrap(),
               file: None,
               json_metadata: None,
@@ -5,18 +5,21 @@ use {
     accept_json::AcceptJson,
     deserialize_from_str::DeserializeFromStr,
     error::{ApiError, OptionExt, ServerError, ServerResult},
+    snapshot::Snapshot,
   },
   super::*,
   crate::{
+    okx::datastore::brc20 as brc20_store,
     server_config::ServerConfig,
     templates::{
-      BlockHtml, BlockJson, BlocksHtml, ChildrenHtml, ChildrenJson, ClockSvg, CollectionsHtml,
-      HomeHtml, InputHtml, InscriptionHtml, InscriptionJson, InscriptionsBlockHtml,
-      InscriptionsHtml, InscriptionsJson, OutputHtml, OutputJson, PageContent, PageHtml,
-      PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-      PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, RuneHtml, RuneJson, RunesHtml, RunesJson, SatHtml, SatInscriptionJson,
-      SatInscriptionsJson, SatJson, TransactionHtml,
+      BlockHtml, BlockJson, BlocksHtml, Brc20ReceiptsHtml, Brc20TokenHtml, Brc20TokensHtml,
+      ChildrenHtml, ChildrenJson, ClockSvg, CollectionsHtml, DistrictHtml, HomeHtml, InputHtml,
+      InscriptionHtml, InscriptionJson, InscriptionsBlockHtml, InscriptionsHtml, InscriptionsJson,
+      OutputHtml, OutputJson, PageContent, PageHtml, PreviewAudioHtml, PreviewCodeHtml,
+      PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml,
+      PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml,
+      RuneJson, RunesHtml, RunesJson, SatHtml, SatInscriptionJson, SatInscriptionsJson, SatJson,
+      TransactionHtml,
     },
   },
   axum::{
@@ -37,7 +40,12 @@ use {
     caches::DirCache,
     AcmeConfig,
   },
-  std::{cmp::Ordering, io::Read, str, sync::Arc},
+  std::{
+    cmp::Ordering,
+    io::Read,
+    str,
+    sync::{mpsc, Arc},
+  },
   tokio_stream::StreamExt,
   tower_http::{
     compression::CompressionLayer,
@@ -50,18 +58,25 @@ use {
 mod accept_encoding;
 mod accept_json;
 mod api;
+mod bitmap;
 mod brc20;
 mod error;
+mod events;
 mod info;
+mod mempool;
+mod metrics;
+mod names;
 mod ord;
 mod response;
 mod sat;
+mod snapshot;
 mod types;
 mod utils;
 mod wallet;
+mod zmq;
 
 use self::api::*;
-use self::response::ApiResponse;
+use self::response::{ApiBatchItem, ApiResponse};
 
 #[derive(Copy, Clone)]
 pub(crate) enum InscriptionQuery {
@@ -184,12 +199,54 @@ pub(crate) struct Server {
     help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
   )]
   pub(crate) decompress: bool,
+  #[arg(
+    long,
+    help = "Simulate the transactions of the Bitcoin Core mempool on top of the index every 5 seconds and serve their inscription actions and BRC20 events under /api/v1/mempool."
+  )]
+  pub(crate) track_mempool: bool,
 }
 
 impl Server {
   pub(crate) fn run(self, options: Options, index: Arc<Index>, handle: Handle) -> SubcommandResult {
     Runtime::new()?.block_on(async {
       let index_clone = index.clone();
+      let track_mempool = self.track_mempool;
+
+      let (block_sender, block_receiver) = mpsc::sync_channel(1);
+      match options.bitcoin_zmq_url.clone() {
+        Some(url) => {
+          tokio::spawn(zmq::block_notifications(url, block_sender));
+        }
+        // without a ZMQ url the sender is dropped and the index thread only polls.
+        None => drop(block_sender),
+      }
+
+      if track_mempool {
+        index.track_mempool()?;
+      }
+
+      let snapshots = Arc::new(snapshot::Snapshots::new(index.clone()));
+
+      // release snapshots as the index advances, even if no newer block is requested.
+      {
+        let snapshots = snapshots.clone();
+        tokio::spawn(async move {
+          let mut interval = tokio::time::interval(Duration::from_secs(5));
+          loop {
+            interval.tick().await;
+            if let Err(error) = snapshots.release(Instant::now()) {
+              log::warn!("Releasing snapshots: {error}");
+            }
+          }
+        });
+      }
+
+      // subscribe before indexing starts so that no event is missed.
+      let event_payloads = events::event_payloads();
+      tokio::spawn(events::relay(
+        index.subscribe_events(),
+        event_payloads.clone(),
+      ));
 
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
@@ -198,21 +255,50 @@ impl Server {
         if let Err(error) = index_clone.update() {
           log::warn!("Updating index: {error}");
         }
-        thread::sleep(Duration::from_millis(5000));
+        if let Err(mpsc::RecvTimeoutError::Disconnected) =
+          block_receiver.recv_timeout(Duration::from_millis(5000))
+        {
+          thread::sleep(Duration::from_millis(5000));
+        }
       });
       INDEXER.lock().unwrap().replace(index_thread);
 
+      // the mempool is simulated on read transactions, so it never holds up indexing.
+      if track_mempool {
+        let index = index.clone();
+        thread::spawn(move || {
+          while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            if let Err(error) = index.update_mempool() {
+              log::warn!("Updating mempool: {error}");
+            }
+            thread::sleep(Duration::from_millis(5000));
+          }
+        });
+      }
+
       #[derive(OpenApi)]
       #[openapi(
       paths(
       brc20::brc20_balance,
       brc20::brc20_all_balance,
+      brc20::brc20_script_balance,
+      brc20::brc20_script_all_balance,
       brc20::brc20_tick_info,
       brc20::brc20_all_tick_info,
+      brc20::brc20_tick_holders,
       brc20::brc20_tx_events,
       brc20::brc20_block_events,
+      brc20::brc20_address_events,
+      brc20::brc20_tick_events,
+      brc20::brc20_tick_burns,
+      brc20::brc20_block_checksum,
       brc20::brc20_transferable,
       brc20::brc20_all_transferable,
+      brc20::brc20_script_transferable,
+      brc20::brc20_script_all_transferable,
+      brc20::brc20_batch_all_balance,
+      brc20::brc20_batch_balance,
+      brc20::brc20_batch_tick_info,
 
       wallet::available_unspent_outputs,
 
@@ -221,13 +307,31 @@ impl Server {
       ord::ord_outpoint,
       ord::ord_txid_inscriptions,
       ord::ord_block_inscriptions,
+      ord::ord_batch_inscription,
+      ord::ord_batch_outpoint,
+
+      bitmap::bitmap_districts,
+      bitmap::bitmap_district,
+      bitmap::bitmap_inscription_district,
+      bitmap::bitmap_address_districts,
+
+      names::names_namespaces,
+      names::names_resolve,
 
       info::node_info,
+      info::node_reorgs,
+
+      events::events,
+
+      mempool::mempool_inscriptions,
+      mempool::mempool_brc20_address_events,
       ),
       components(schemas(
       // BRC20 schemas
       brc20::ApiTickInfo,
       brc20::ApiTickInfos,
+      brc20::ApiTickHolder,
+      brc20::ApiTickHolders,
       brc20::ApiBalance,
       brc20::ApiBalances,
       brc20::ApiTxEvent,
@@ -238,17 +342,37 @@ impl Server {
       brc20::ApiErrorEvent,
       brc20::ApiTxEvents,
       brc20::ApiBlockEvents,
+      brc20::ApiAddressEvent,
+      brc20::ApiAddressEvents,
+      brc20::ApiTickEvents,
+      brc20::ApiTickBurns,
+      brc20::ApiBlockChecksum,
       brc20::ApiTransferableAsset,
       brc20::ApiTransferableAssets,
+      brc20::BatchAddressesRequest,
+      brc20::BatchTickBalanceQuery,
+      brc20::BatchTickBalancesRequest,
+      brc20::BatchTicksRequest,
 
       // BRC20 responses schemas
       response::ApiBRC20Tick,
       response::ApiBRC20AllTick,
+      response::ApiBRC20TickHolders,
       response::ApiBRC20Balance,
       response::ApiBRC20AllBalance,
       response::ApiBRC20TxEvents,
       response::ApiBRC20BlockEvents,
+      response::ApiBRC20AddressEvents,
+      response::ApiBRC20TickEvents,
+      response::ApiBRC20TickBurns,
+      response::ApiBRC20BlockChecksum,
       response::ApiBRC20Transferable,
+      response::BatchItemBRC20Balance,
+      response::BatchItemBRC20AllBalance,
+      response::BatchItemBRC20Tick,
+      response::BatchBRC20Balance,
+      response::BatchBRC20AllBalance,
+      response::BatchBRC20Tick,
 
       // Wallet schemas
       wallet::ApiAvailableUnspentOutputs,
@@ -266,18 +390,61 @@ impl Server {
       ord::ApiTxInscription,
       ord::ApiTxInscriptions,
       ord::ApiBlockInscriptions,
+      ord::BatchInscriptionsRequest,
+      ord::BatchOutPointsRequest,
 
       // Ord responses schemas
       response::ApiOrdInscription,
       response::ApiOrdTxInscriptions,
       response::ApiOrdBlockInscriptions,
       response::ApiOrdOutPointResult,
+      response::BatchItemOrdInscription,
+      response::BatchItemOrdOutPointResult,
+      response::BatchOrdInscription,
+      response::BatchOrdOutPointResult,
+
+      // Bitmap schemas
+      bitmap::ApiDistrict,
+      bitmap::ApiDistricts,
+
+      // Bitmap responses schemas
+      response::BitmapDistrict,
+      response::BitmapDistricts,
+
+      // Name schemas
+      names::ApiNamespace,
+      names::ApiNamespaces,
+      names::ApiName,
+
+      // Name responses schemas
+      response::NameNamespaces,
+      response::NameName,
 
       // Node Info schemas
       info::NodeInfo,
       info::ChainInfo,
+      info::Reorgs,
+      info::Reorg,
+      info::OrphanedBlock,
       types::ScriptPubkey,
+
+      // Event stream schemas
+      events::ApiBRC20Event,
+      events::ApiInscriptionEvent,
+      events::ApiRollback,
+
+      // Mempool schemas
+      mempool::ApiMempoolInscriptions,
+      mempool::ApiMempoolBRC20Events,
+
+      // Mempool responses schemas
+      response::MempoolInscriptions,
+      response::MempoolBRC20Events,
+
       response::Node,
+      response::NodeReorgs,
+      snapshot::ApiSnapshot,
+      types::SortOrder,
       ApiError
       ))
       )]
@@ -301,12 +468,21 @@ impl Server {
           get(|| async { ApiDoc::openapi().to_pretty_json().unwrap() }),
         )
         .route("/node/info", get(info::node_info))
+        .route("/node/reorgs", get(info::node_reorgs))
+        .route("/events", get(events::events))
+        .route("/mempool/inscriptions", get(mempool::mempool_inscriptions))
+        .route(
+          "/mempool/brc20/address/:address",
+          get(mempool::mempool_brc20_address_events),
+        )
         .route("/ord/id/:id/inscription", get(ord::ord_inscription_id))
         .route(
           "/ord/number/:number/inscription",
           get(ord::ord_inscription_number),
         )
         .route("/ord/outpoint/:outpoint/info", get(ord::ord_outpoint))
+        .route("/ord/batch/inscription", post(ord::ord_batch_inscription))
+        .route("/ord/batch/outpoint", post(ord::ord_batch_outpoint))
         .route(
           "/ord/tx/:txid/inscriptions",
           get(ord::ord_txid_inscriptions),
@@ -319,8 +495,32 @@ impl Server {
           "/ord/debug/bitmap/district/:number",
           get(ord::ord_debug_bitmap_district),
         )
+        .route("/bitmap/districts", get(bitmap::bitmap_districts))
+        .route("/bitmap/district/:number", get(bitmap::bitmap_district))
+        .route(
+          "/bitmap/inscription/:id/district",
+          get(bitmap::bitmap_inscription_district),
+        )
+        .route(
+          "/bitmap/address/:address/districts",
+          get(bitmap::bitmap_address_districts),
+        )
+        .route("/names/namespaces", get(names::names_namespaces))
+        .route("/names/resolve/:name", get(names::names_resolve))
         .route("/brc20/tick/:tick", get(brc20::brc20_tick_info))
         .route("/brc20/tick", get(brc20::brc20_all_tick_info))
+        .route("/brc20/tick/:tick/holders", get(brc20::brc20_tick_holders))
+        .route("/brc20/tick/:tick/events", get(brc20::brc20_tick_events))
+        .route("/brc20/tick/:tick/burns", get(brc20::brc20_tick_burns))
+        .route("/brc20/batch/tick", post(brc20::brc20_batch_tick_info))
+        .route(
+          "/brc20/batch/tick/balance",
+          post(brc20::brc20_batch_balance),
+        )
+        .route(
+          "/brc20/batch/balance",
+          post(brc20::brc20_batch_all_balance),
+        )
         .route(
           "/brc20/tick/:tick/address/:address/balance",
           get(brc20::brc20_balance),
@@ -337,6 +537,26 @@ impl Server {
           "/brc20/address/:address/transferable",
           get(brc20::brc20_all_transferable),
         )
+        .route(
+          "/brc20/tick/:tick/script/:script_pubkey/balance",
+          get(brc20::brc20_script_balance),
+        )
+        .route(
+          "/brc20/script/:script_pubkey/balance",
+          get(brc20::brc20_script_all_balance),
+        )
+        .route(
+          "/brc20/tick/:tick/script/:script_pubkey/transferable",
+          get(brc20::brc20_script_transferable),
+        )
+        .route(
+          "/brc20/script/:script_pubkey/transferable",
+          get(brc20::brc20_script_all_transferable),
+        )
+        .route(
+          "/brc20/address/:address/events",
+          get(brc20::brc20_address_events),
+        )
         .route(
           "/brc20/outpoint/:outpoint/transferable",
           get(brc20::brc20_outpoint),
@@ -346,6 +566,11 @@ impl Server {
           "/brc20/block/:block_hash/events",
           get(brc20::brc20_block_events),
         )
+        // the router requires the same parameter name as the sibling route, the segment is a block height here.
+        .route(
+          "/brc20/block/:height/checksum",
+          get(brc20::brc20_block_checksum),
+        )
         .route(
           "/sat/outpoint/:outpoint/info",
           get(sat::sat_range_by_outpoint),
@@ -361,7 +586,9 @@ impl Server {
         .route(
           "/wallet/:address/available_unspent_outputs",
           get(wallet::available_unspent_outputs),
-        );
+        )
+        .route_layer(axum::middleware::from_fn(snapshot::snapshot))
+        .route_layer(axum::middleware::from_fn(metrics::track));
 
       let api_router = Router::new().nest("/v1", api_v1_router);
 
@@ -373,6 +600,8 @@ impl Server {
         .route("/blockhash/:height", get(Self::block_hash_from_height))
         .route("/blockheight", get(Self::block_height))
         .route("/blocks", get(Self::blocks))
+        .route("/brc20", get(Self::brc20_tokens))
+        .route("/brc20/:tick", get(Self::brc20_token))
         .route("/blocktime", get(Self::block_time))
         .route("/bounties", get(Self::bounties))
         .route("/children/:inscription_id", get(Self::children))
@@ -384,6 +613,7 @@ impl Server {
         .route("/collections", get(Self::collections))
         .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
+        .route("/district/:number", get(Self::district))
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
         .route("/feed.xml", get(Self::feed))
@@ -400,6 +630,7 @@ impl Server {
           get(Self::inscriptions_in_block_paginated),
         )
         .route("/install.sh", get(Self::install_script))
+        .route("/metrics", get(metrics::metrics))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/preview/:inscription_id", get(Self::preview))
@@ -437,6 +668,8 @@ impl Server {
         .route("/tx/:txid", get(Self::transaction))
         .nest("/api", api_router)
         .layer(Extension(index))
+        .layer(Extension(snapshots))
+        .layer(Extension(event_payloads))
         .layer(Extension(server_config.clone()))
         .layer(Extension(config))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -823,6 +1056,91 @@ impl Server {
     })
   }
 
+  async fn brc20_tokens(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<PageHtml<Brc20TokensHtml>> {
+    let mut tokens = index.begin_read()?.brc20_get_all_tick_info()?;
+
+    tokens.sort_by_key(|token| token.deployed_number);
+
+    Ok(Brc20TokensHtml { tokens }.page(server_config))
+  }
+
+  async fn brc20_token(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(tick): Path<String>,
+  ) -> ServerResult<PageHtml<Brc20TokenHtml>> {
+    let tick = brc20_store::Tick::from_str(&tick)
+      .map_err(|_| ServerError::BadRequest(format!("invalid BRC20 ticker {tick}")))?;
+
+    let rtx = index.begin_read()?;
+
+    let token = rtx
+      .brc20_get_tick_info(&tick)?
+      .ok_or_not_found(|| format!("BRC20 token {tick}"))?;
+
+    let holders = rtx.brc20_get_tick_holders(&tick, 0..100)?;
+
+    Ok(Brc20TokenHtml { token, holders }.page(server_config))
+  }
+
+  fn brc20_receipts(
+    index: &Index,
+    txids: &[Txid],
+    filter: impl Fn(&brc20_store::Receipt) -> bool,
+  ) -> ServerResult<Brc20ReceiptsHtml> {
+    let rtx = index.begin_read()?;
+
+    let mut html = Brc20ReceiptsHtml::default();
+    for txid in txids {
+      for receipt in rtx
+        .brc20_transaction_id_to_transaction_receipt(*txid)?
+        .unwrap_or_default()
+      {
+        if !filter(&receipt) {
+          continue;
+        }
+
+        if let Some(tick) = receipt.tick() {
+          if let std::collections::btree_map::Entry::Vacant(entry) =
+            html.decimals.entry(tick.to_lowercase().to_string())
+          {
+            if let Some(token) = rtx.brc20_get_tick_info(tick)? {
+              entry.insert(token.decimal);
+            }
+          }
+        }
+
+        html.receipts.push((*txid, receipt));
+      }
+    }
+
+    Ok(html)
+  }
+
+  async fn district(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(number): Path<u32>,
+  ) -> ServerResult<PageHtml<DistrictHtml>> {
+    let (inscription_id, owner) = index
+      .begin_read()?
+      .bitmap_get_district(number)?
+      .ok_or_not_found(|| format!("district {number}"))?;
+
+    Ok(
+      DistrictHtml {
+        number,
+        blockhash: index.block_hash(Some(number))?,
+        inscription_id,
+        owner,
+      }
+      .page(server_config),
+    )
+  }
+
   async fn home(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -927,6 +1245,7 @@ impl Server {
         inscription_count,
         chain: server_config.chain,
         etching: index.get_etching(txid)?,
+        brc20_receipts: Self::brc20_receipts(&index, &[txid], |_| true)?,
       }
       .page(server_config),
     )
@@ -1435,7 +1754,16 @@ impl Server {
       })
       .into_response()
     } else {
+      // the receipts of a transfer inscription are in the transactions that inscribed and sent it.
+      let mut txids = vec![info.entry.id.txid];
+      if info.satpoint.outpoint.txid != info.entry.id.txid {
+        txids.push(info.satpoint.outpoint.txid);
+      }
+
       InscriptionHtml {
+        brc20_receipts: Self::brc20_receipts(&index, &txids, |receipt| {
+          receipt.inscription_id == info.entry.id
+        })?,
         chain: server_config.chain,
         charms: info.charms,
         children: info.children,
@@ -2278,6 +2606,493 @@ mod tests {
     );
   }
 
+  #[test]
+  fn brc20_tokens_are_displayed_on_brc20_pages() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &[],
+    );
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/brc20",
+      StatusCode::OK,
+      ".*<title>BRC20 Tokens</title>.*<h1>BRC20 Tokens</h1>\n<ul>\n</ul>.*",
+    );
+
+    server.assert_response_regex(
+      "/brc20/ordi",
+      StatusCode::NOT_FOUND,
+      "BRC20 token ordi not found",
+    );
+
+    server.assert_response_regex(
+      "/brc20/toolongticker",
+      StatusCode::BAD_REQUEST,
+      "invalid BRC20 ticker toolongticker",
+    );
+
+    let deploy = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let mint = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/brc20",
+      StatusCode::OK,
+      ".*<h1>BRC20 Tokens</h1>\n<ul>\n  <li><a href=\"/brc20/ordi\">ordi</a></li>\n</ul>.*",
+    );
+
+    server.assert_response_regex(
+      "/brc20/ORDI",
+      StatusCode::OK,
+      format!(
+        ".*<title>BRC20 ordi</title>.*
+  <dt>supply</dt>
+  <dd>1000</dd>
+  <dt>minted</dt>
+  <dd>10 \\(1.00%\\)</dd>
+.*
+  <dt>deploy inscription</dt>
+  <dd><a class=monospace href=/inscription/{deploy}i0>{deploy}i0</a></dd>
+  <dt>deploy height</dt>
+  <dd><a href=/block/2>2</a></dd>
+.*<h2>Top Holders</h2>
+<ul>
+  <li><span class=monospace>.*</span> 10</li>
+</ul>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{mint}"),
+      StatusCode::OK,
+      format!(
+        ".*<h2>BRC20 Events</h2>.*<dt>operation</dt><dd>mint</dd>
+      <dt>transaction</dt><dd><a class=monospace href=/tx/{mint}>{mint}</a></dd>.*
+      <dt>ticker</dt><dd><a href=\"/brc20/ordi\">ordi</a></dd>
+      <dt>amount</dt><dd>10</dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{deploy}i0"),
+      StatusCode::OK,
+      ".*<h2>BRC20 Events</h2>.*<dt>operation</dt><dd>deploy</dd>.*<dt>supply</dt><dd>1000</dd>.*",
+    );
+  }
+
+  #[test]
+  fn api_requests_can_be_pinned_to_a_block() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &[],
+    );
+
+    let first = server.mine_blocks(1)[0].block_hash();
+
+    let response = server.get("/api/v1/brc20/tick");
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["block"]["height"], 1);
+    assert_eq!(json["block"]["hash"], first.to_string());
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    let second = server.mine_blocks(1)[0].block_hash();
+
+    // requests pinned to the first block are answered from its state.
+    for at_block in ["1".to_string(), first.to_string()] {
+      let response = server.get(format!("/api/v1/brc20/tick?at_block={at_block}"));
+      assert_eq!(response.status(), StatusCode::OK);
+      let json = response.json::<serde_json::Value>().unwrap();
+      assert_eq!(json["block"]["height"], 1);
+      assert_eq!(json["data"]["tokens"].as_array().unwrap().len(), 0);
+    }
+
+    let response = server.get(format!("/api/v1/brc20/tick?at_block={second}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["block"]["height"], 2);
+    assert_eq!(json["data"]["tokens"].as_array().unwrap().len(), 1);
+
+    // unknown blocks are not answered.
+    let response = server.get("/api/v1/brc20/tick?at_block=3");
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["code"], 4);
+    assert_eq!(
+      json["msg"],
+      format!("snapshot at block 3 has been released, the index is at block 2 {second}")
+    );
+
+    // the snapshot of the first block is released once enough newer blocks have been requested,
+    // requests that are not pinned are always answered.
+    let mut latest = second;
+    for height in 3..3 + u32::try_from(snapshot::MAX_SNAPSHOTS).unwrap() {
+      latest = server.mine_blocks(1)[0].block_hash();
+      let response = server.get("/api/v1/node/info");
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(
+        response.json::<serde_json::Value>().unwrap()["block"]["height"],
+        height
+      );
+    }
+
+    let response = server.get("/api/v1/brc20/tick?at_block=1");
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      format!(
+        "snapshot at block 1 has been released, the index is at block {} {latest}",
+        2 + snapshot::MAX_SNAPSHOTS
+      )
+    );
+
+    let response = server.get("/api/v1/brc20/tick?at_block=3");
+    assert_eq!(response.status(), StatusCode::OK);
+  }
+
+  #[test]
+  fn snapshots_are_released_without_newer_requests() {
+    let server = TestServer::new_with_regtest();
+    let snapshots = snapshot::Snapshots::new(server.index.clone());
+
+    server.mine_blocks(1);
+    let first = snapshots.get(None).unwrap().unwrap();
+    assert_eq!(first.block.as_ref().unwrap().height, 1);
+    drop(first);
+    assert_eq!(snapshots.len(), 1);
+
+    // a snapshot is kept while the index is less than `MAX_SNAPSHOTS` blocks ahead of it.
+    server.mine_blocks(u64::try_from(snapshot::MAX_SNAPSHOTS).unwrap() - 1);
+    snapshots.release(Instant::now()).unwrap();
+    assert_eq!(snapshots.len(), 1);
+
+    server.mine_blocks(1);
+    snapshots.release(Instant::now()).unwrap();
+    assert_eq!(snapshots.len(), 0);
+
+    // and for no longer than `SNAPSHOT_LIFETIME`.
+    assert!(snapshots.get(Some("1")).unwrap().is_none());
+    snapshots.get(None).unwrap().unwrap();
+    snapshots.release(Instant::now()).unwrap();
+    assert_eq!(snapshots.len(), 1);
+    snapshots
+      .release(Instant::now() + snapshot::SNAPSHOT_LIFETIME)
+      .unwrap();
+    assert_eq!(snapshots.len(), 0);
+  }
+
+  #[test]
+  fn api_list_endpoints_are_paginated_with_a_cursor() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &[],
+    );
+
+    server.mine_blocks(3);
+
+    for (i, tick) in ["ordi", "sats", "rats"].into_iter().enumerate() {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          i + 1,
+          0,
+          0,
+          inscription(
+            "text/plain;charset=utf-8",
+            format!(r#"{{"p":"brc-20","op":"deploy","tick":"{tick}","max":"1000","lim":"10"}}"#),
+          )
+          .to_witness(),
+        )],
+        ..Default::default()
+      });
+      server.mine_blocks(1);
+    }
+
+    let page = |query: &str| {
+      let response = server.get(format!("/api/v1/brc20/tick?{query}"));
+      assert_eq!(response.status(), StatusCode::OK);
+      let json = response.json::<serde_json::Value>().unwrap();
+      (
+        json["data"]["tokens"]
+          .as_array()
+          .unwrap()
+          .iter()
+          .map(|token| token["tick"].as_str().unwrap().to_string())
+          .collect::<Vec<String>>(),
+        json["nextCursor"].as_str().map(str::to_string),
+      )
+    };
+
+    assert_eq!(
+      page("limit=2"),
+      (vec!["ordi".into(), "sats".into()], Some("2".into()))
+    );
+    assert_eq!(page("limit=2&cursor=2"), (vec!["rats".into()], None));
+    assert_eq!(
+      page("limit=2&order=desc"),
+      (vec!["rats".into(), "sats".into()], Some("2".into()))
+    );
+    assert_eq!(
+      page(""),
+      (vec!["ordi".into(), "sats".into(), "rats".into()], None)
+    );
+
+    for query in ["cursor=x", "limit=0"] {
+      let response = server.get(format!("/api/v1/brc20/tick?{query}"));
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // the outputs of the test transactions have no address.
+    let events = format!(
+      "/api/v1/brc20/address/{}/events",
+      ScriptBuf::new().script_hash()
+    );
+
+    let response = server.get(format!("{events}?limit=2"));
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["data"]["events"].as_array().unwrap().len(), 2);
+    assert_eq!(json["data"].get("nextCursor"), None);
+    let cursor = json["nextCursor"].as_str().unwrap();
+
+    let response = server.get(format!("{events}?limit=2&cursor={cursor}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["data"]["events"][0]["tick"], "rats");
+    assert_eq!(json["nextCursor"], serde_json::Value::Null);
+
+    let response = server.get(format!("{events}?order=desc"));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = server.get("/api/v1/bitmap/districts?limit=0");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn mempool_endpoints_serve_unconfirmed_operations() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &["--track-mempool"],
+    );
+
+    server.mine_blocks(2);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    let mint = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    // the outputs of the test transactions have no address.
+    let address = server.bitcoin_rpc_server.mempool()[0].output[0]
+      .script_pubkey
+      .script_hash();
+
+    server.index.update_mempool().unwrap();
+
+    let response = server.get("/api/v1/mempool/inscriptions");
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["data"]["height"], 4);
+    assert_eq!(json["data"]["txs"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"]["txs"][0]["txid"], mint.to_string());
+    assert_eq!(
+      json["data"]["txs"][0]["inscriptions"][0]["inscriptionId"],
+      format!("{mint}i0")
+    );
+
+    let response = server.get(format!("/api/v1/mempool/brc20/address/{address}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["data"]["events"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"]["events"][0]["type"], "mint");
+    assert_eq!(json["data"]["events"][0]["txid"], mint.to_string());
+    assert_eq!(json["data"]["events"][0]["amount"], "10000000000000000000");
+
+    // the unconfirmed mint is not committed to the index.
+    let response = server.get(format!("/api/v1/brc20/tick/ordi/address/{address}/balance"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["data"]["overallBalance"],
+      "0"
+    );
+
+    server.mine_blocks(1);
+    server.index.update_mempool().unwrap();
+
+    let response = server.get("/api/v1/mempool/inscriptions");
+    let json = response.json::<serde_json::Value>().unwrap();
+    assert_eq!(json["data"]["height"], 5);
+    assert_eq!(json["data"]["txs"].as_array().unwrap().len(), 0);
+  }
+
+  #[test]
+  fn mempool_endpoints_require_mempool_tracking() {
+    let server = TestServer::new();
+
+    let response = server.get("/api/v1/mempool/inscriptions");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[test]
+  fn metrics_are_exported() {
+    let server = TestServer::new();
+
+    server.mine_blocks(1);
+
+    let response = server.get("/api/v1/brc20/tick/ordi");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = server.get("/metrics");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/plain; version=0.0.4"
+    );
+
+    let metrics = response.text().unwrap();
+    assert!(metrics.contains("# TYPE ord_blocks_indexed_total counter\n"));
+    assert!(metrics.contains("ord_block_phase_duration_seconds_count{phase=\"block\"}"));
+    assert!(metrics.contains("ord_redb_commit_duration_seconds_count"));
+    // metrics are process wide, other tests index concurrently.
+    assert!(metrics.contains("# TYPE ord_chain_tip_lag_blocks gauge\n"));
+    assert!(
+      metrics.contains("ord_api_errors_total{route=\"/api/v1/brc20/tick/:tick\",status=\"404\"}")
+    );
+    assert!(metrics
+      .contains("ord_api_request_duration_seconds_count{route=\"/api/v1/brc20/tick/:tick\"}"));
+  }
+
+  #[test]
+  fn district_page() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-bitmap"],
+      &[],
+    );
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex("/district/1", StatusCode::NOT_FOUND, "district 1 not found");
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "1.bitmap").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      "/district/1",
+      StatusCode::OK,
+      format!(
+        ".*<title>District 1</title>.*<h1>District 1</h1>
+<iframe .* src=/preview/{id}></iframe>
+<dl>
+  <dt>block</dt>
+  <dd><a href=/block/1>1</a></dd>
+  <dt>block hash</dt>
+  <dd><a class=monospace href=/block/{}>.*</a></dd>
+  <dt>inscription</dt>
+  <dd><a class=monospace href=/inscription/{id}>{id}</a></dd>
+  <dt>owner</dt>
+  <dd class=monospace>[[:xdigit:]]{{40}}</dd>
+</dl>.*",
+        server.index.block_hash(Some(1)).unwrap().unwrap(),
+      ),
+    );
+  }
+
   #[test]
   fn runes_are_displayed_on_runes_page() {
     let server = TestServer::new_with_regtest_with_index_runes();
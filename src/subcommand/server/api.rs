@@ -1,15 +1,26 @@
-use {super::*, utoipa::IntoParams};
-
-#[derive(Deserialize, IntoParams)]
-pub struct Pagination {
-  /// Start index of the result.
-  pub start: Option<usize>,
-  /// Limit of the result.
-  pub limit: Option<usize>,
-}
+use super::*;
 
 pub(crate) type ApiResult<T> = Result<axum::Json<ApiResponse<T>>, ApiError>;
 
+/// Maximum number of items of a batch request.
+pub(crate) const MAX_BATCH_SIZE: usize = 1000;
+
+/// Answer every item of a batch request with `f`, keeping the order of the request.
+pub(crate) fn batch<I, T, F>(items: Vec<I>, f: F) -> Result<Vec<ApiBatchItem<T>>, ApiError>
+where
+  T: Serialize,
+  F: FnMut(I) -> Result<T, ApiError>,
+{
+  if items.len() > MAX_BATCH_SIZE {
+    return Err(ApiError::bad_request(format!(
+      "batch of {} items exceeds the maximum of {MAX_BATCH_SIZE}",
+      items.len()
+    )));
+  }
+
+  Ok(items.into_iter().map(f).map(ApiBatchItem::from).collect())
+}
+
 pub(super) trait ApiOptionExt<T> {
   fn ok_or_api_err<F: FnOnce() -> ApiError>(self, f: F) -> Result<T, ApiError>;
   fn ok_or_api_not_found<S: ToString>(self, s: S) -> Result<T, ApiError>;
@@ -0,0 +1,199 @@
+use {
+  super::{
+    types::{PageQuery, ScriptPubkey},
+    *,
+  },
+  crate::{index::rtx::Rtx, okx::datastore::ScriptKey},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = bitmap::District)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDistrict {
+  /// The district number, which is the height of the block it claims.
+  #[schema(format = "uint32")]
+  pub number: u32,
+  /// The id of the inscription that claimed the district.
+  pub inscription_id: String,
+  /// The current owner of the district inscription, none if it has been lost.
+  pub owner: Option<ScriptPubkey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = bitmap::Districts)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDistricts {
+  #[schema(value_type = Vec<bitmap::District>)]
+  pub districts: Vec<ApiDistrict>,
+  /// The total number of districts matching the query.
+  #[schema(format = "uint64")]
+  pub total: u64,
+}
+
+fn district(rtx: &Rtx, number: u32) -> Result<Option<ApiDistrict>> {
+  Ok(
+    rtx
+      .bitmap_get_district(number)?
+      .map(|(inscription_id, owner)| ApiDistrict {
+        number,
+        inscription_id: inscription_id.to_string(),
+        owner: owner.map(ScriptKey::into),
+      }),
+  )
+}
+
+/// Get the districts.
+///
+/// Retrieve the claimed bitmap districts ordered by number.
+#[utoipa::path(
+    get,
+    path = "/api/v1/bitmap/districts",
+    params(
+      PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain the districts.", body = BitmapDistricts),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn bitmap_districts(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiDistricts> {
+  log::debug!("rpc: get bitmap_districts");
+
+  snapshot
+    .read(move |rtx| {
+      let total = rtx.bitmap_count_districts()?;
+      let (positions, next_cursor) = page.range(usize::try_from(total).unwrap())?;
+
+      let mut districts = Vec::new();
+      for (number, _) in page.arrange(rtx.bitmap_get_districts(positions)?) {
+        districts.extend(district(rtx, number)?);
+      }
+
+      Ok(Json(ApiResponse::page(
+        ApiDistricts { districts, total },
+        next_cursor,
+      )))
+    })
+    .await
+}
+
+/// Get the district.
+///
+/// Retrieve the inscription and the current owner of the district.
+#[utoipa::path(
+    get,
+    path = "/api/v1/bitmap/district/{number}",
+    params(
+      ("number" = u32, Path, description = "District number"),
+  ),
+    responses(
+      (status = 200, description = "Obtain the district.", body = BitmapDistrict),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "District not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn bitmap_district(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(number): Path<u32>,
+) -> ApiResult<ApiDistrict> {
+  log::debug!("rpc: get bitmap_district: {number}");
+
+  snapshot
+    .read(move |rtx| {
+      let district =
+        district(rtx, number)?.ok_or_api_not_found(format!("district {number} not found."))?;
+
+      Ok(Json(ApiResponse::ok(district)))
+    })
+    .await
+}
+
+/// Get the district of the inscription.
+///
+/// Retrieve the district claimed by the inscription.
+#[utoipa::path(
+    get,
+    path = "/api/v1/bitmap/inscription/{id}/district",
+    params(
+      ("id" = String, Path, description = "Inscription ID"),
+  ),
+    responses(
+      (status = 200, description = "Obtain the district of the inscription.", body = BitmapDistrict),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "The inscription is not a district.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn bitmap_inscription_district(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(id): Path<String>,
+) -> ApiResult<ApiDistrict> {
+  log::debug!("rpc: get bitmap_inscription_district: {id}");
+
+  let inscription_id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
+
+  snapshot
+    .read(move |rtx| {
+      let district = rtx
+        .bitmap_get_inscription_district(inscription_id)?
+        .map(|number| district(rtx, number))
+        .transpose()?
+        .flatten()
+        .ok_or_api_not_found(format!("inscription {inscription_id} is not a district."))?;
+
+      Ok(Json(ApiResponse::ok(district)))
+    })
+    .await
+}
+
+/// Get the districts of the address.
+///
+/// Retrieve the districts currently owned by the address, ordered by number.
+#[utoipa::path(
+    get,
+    path = "/api/v1/bitmap/address/{address}/districts",
+    params(
+      ("address" = String, Path, description = "Address"),
+      PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain the districts of the address.", body = BitmapDistricts),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn bitmap_address_districts(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(address): Path<String>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiDistricts> {
+  log::debug!("rpc: get bitmap_address_districts: {address}");
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  snapshot
+    .read(move |rtx| {
+      let total = rtx.bitmap_count_districts_by_owner(&script_key)?;
+      let (positions, next_cursor) = page.range(usize::try_from(total).unwrap())?;
+
+      let mut districts = Vec::new();
+      for number in page.arrange(rtx.bitmap_get_districts_by_owner(&script_key, positions)?) {
+        districts.extend(district(rtx, number)?);
+      }
+
+      Ok(Json(ApiResponse::page(
+        ApiDistricts { districts, total },
+        next_cursor,
+      )))
+    })
+    .await
+}
@@ -1,4 +1,12 @@
-use {super::*, crate::okx::datastore::brc20::Tick, axum::Json, utoipa::ToSchema};
+use {
+  super::*,
+  crate::{
+    index::rtx::Rtx,
+    okx::datastore::{brc20::Tick, ScriptKey},
+  },
+  axum::{extract::Query, Json},
+  utoipa::{IntoParams, ToSchema},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -17,15 +25,22 @@ pub struct ApiBalance {
   pub overall_balance: String,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct BalanceQuery {
+  /// Return the balance at the end of the block height instead of the latest one.
+  pub height: Option<u32>,
+}
+
 /// Get the ticker balance of the address.
 ///
-/// Retrieve the asset balance of the 'ticker' for the address.
+/// Retrieve the asset balance of the 'ticker' for the address, optionally at a block height.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/tick/{ticker}/address/{address}/balance",
     params(
         ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
-        ("address" = String, Path, description = "Address")
+        ("address" = String, Path, description = "Address or script hash"),
+        BalanceQuery
   ),
     responses(
       (status = 200, description = "Obtain account balance by query ticker.", body = BRC20Balance),
@@ -36,30 +51,111 @@ pub struct ApiBalance {
   )]
 pub(crate) async fn brc20_balance(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path((tick, address)): Path<(String, String)>,
+  Query(query): Query<BalanceQuery>,
 ) -> ApiResult<ApiBalance> {
-  log::debug!("rpc: get brc20_balance: {} {}", tick, address);
+  log::debug!("rpc: get brc20_balance: {} {} {:?}", tick, address, query);
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  snapshot
+    .read(move |rtx| {
+      Ok(Json(ApiResponse::ok(tick_balance(
+        rtx,
+        &tick,
+        script_key,
+        query.height,
+      )?)))
+    })
+    .await
+}
 
-  let rtx = index.begin_read()?;
-  let chain = index.get_chain();
+/// Get the ticker balance of the script pubkey.
+///
+/// Retrieve the asset balance of the 'ticker' held by outputs with the script pubkey, optionally at
+/// a block height. Use this for outputs without an address, e.g. P2PK, bare multisig or OP_RETURN.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/script/{script_pubkey}/balance",
+    params(
+        ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
+        ("script_pubkey" = String, Path, description = "Hex encoded script pubkey"),
+        BalanceQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain script pubkey balance by query ticker.", body = BRC20Balance),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_script_balance(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path((tick, script_pubkey)): Path<(String, String)>,
+  Query(query): Query<BalanceQuery>,
+) -> ApiResult<ApiBalance> {
+  log::debug!(
+    "rpc: get brc20_script_balance: {} {} {:?}",
+    tick,
+    script_pubkey,
+    query
+  );
 
-  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
-  let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
+  let script_key = utils::parse_script_key_from_script_pubkey(&script_pubkey, index.get_chain())
     .map_err(ApiError::bad_request)?;
 
-  let balance = Index::get_brc20_balance_by_tick_and_address(ticker, script_key, &rtx)?
-    .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
+  snapshot
+    .read(move |rtx| {
+      Ok(Json(ApiResponse::ok(tick_balance(
+        rtx,
+        &tick,
+        script_key,
+        query.height,
+      )?)))
+    })
+    .await
+}
+
+pub(crate) fn tick_balance(
+  rtx: &Rtx,
+  tick: &str,
+  script_key: ScriptKey,
+  height: Option<u32>,
+) -> Result<ApiBalance, ApiError> {
+  let ticker = Tick::from_str(tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.into()))?;
+
+  let balance = match height {
+    Some(height) => {
+      let indexed_height = rtx.block_height()?.map(|height| height.n());
+      if indexed_height.map_or(true, |indexed_height| height > indexed_height) {
+        return Err(ApiError::bad_request(format!(
+          "height {height} has not been indexed yet"
+        )));
+      }
+      Index::get_brc20_balance_at_by_tick_and_address(ticker, script_key.clone(), height, rtx)?
+    }
+    None => Index::get_brc20_balance_by_tick_and_address(ticker, script_key.clone(), rtx)?,
+  }
+  .ok_or(BRC20ApiError::UnknownTicker(tick.into()))?;
 
   let available_balance = balance.overall_balance - balance.transferable_balance;
 
-  log::debug!("rpc: get brc20_balance: {} {} {:?}", tick, address, balance);
+  log::debug!(
+    "rpc: get brc20_balance: {} {} {:?}",
+    tick,
+    script_key,
+    balance
+  );
 
-  Ok(Json(ApiResponse::ok(ApiBalance {
+  Ok(ApiBalance {
     tick: balance.tick.to_string(),
     available_balance: available_balance.to_string(),
     transferable_balance: balance.transferable_balance.to_string(),
     overall_balance: balance.overall_balance.to_string(),
-  })))
+  })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -77,7 +173,8 @@ pub struct ApiBalances {
     get,
     path = "/api/v1/brc20/address/{address}/balance",
     params(
-        ("address" = String, Path, description = "Address")
+        ("address" = String, Path, description = "Address or script hash"),
+        PageQuery
   ),
     responses(
       (status = 200, description = "Obtain account balances by query address.", body = BRC20AllBalance),
@@ -88,20 +185,74 @@ pub struct ApiBalances {
   )]
 pub(crate) async fn brc20_all_balance(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(account): Path<String>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiBalances> {
-  log::debug!("rpc: get brc20_all_balance: {}", account);
+  log::debug!("rpc: get brc20_all_balance: {} {:?}", account, page);
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&account, index.get_chain())
+    .map_err(ApiError::bad_request)?;
 
-  let rtx = index.begin_read()?;
-  let chain = index.get_chain();
+  snapshot
+    .read(move |rtx| balance_page(all_balance(rtx, script_key)?, &page))
+    .await
+}
+
+/// Get all ticker balances of the script pubkey.
+///
+/// Retrieve all BRC20 protocol asset balances held by outputs with the script pubkey.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/script/{script_pubkey}/balance",
+    params(
+        ("script_pubkey" = String, Path, description = "Hex encoded script pubkey"),
+        PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain script pubkey balances.", body = BRC20AllBalance),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_script_all_balance(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(script_pubkey): Path<String>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiBalances> {
+  log::debug!(
+    "rpc: get brc20_script_all_balance: {} {:?}",
+    script_pubkey,
+    page
+  );
 
-  let script_key = utils::parse_and_validate_script_key_with_chain(&account, chain)
+  let script_key = utils::parse_script_key_from_script_pubkey(&script_pubkey, index.get_chain())
     .map_err(ApiError::bad_request)?;
 
-  let all_balance = rtx.brc20_get_all_balance_by_address(script_key)?;
-  log::debug!("rpc: get brc20_all_balance: {} {:?}", account, all_balance);
+  snapshot
+    .read(move |rtx| balance_page(all_balance(rtx, script_key)?, &page))
+    .await
+}
+
+fn balance_page(balances: ApiBalances, page: &PageQuery) -> ApiResult<ApiBalances> {
+  let (balance, next_cursor) = page.paginate(balances.balance)?;
+  Ok(Json(ApiResponse::page(
+    ApiBalances { balance },
+    next_cursor,
+  )))
+}
+
+pub(crate) fn all_balance(rtx: &Rtx, script_key: ScriptKey) -> Result<ApiBalances, ApiError> {
+  let all_balance = rtx.brc20_get_all_balance_by_address(script_key.clone())?;
+  log::debug!(
+    "rpc: get brc20_all_balance: {} {:?}",
+    script_key,
+    all_balance
+  );
 
-  Ok(Json(ApiResponse::ok(ApiBalances {
+  Ok(ApiBalances {
     balance: all_balance
       .into_iter()
       .map(|bal| ApiBalance {
@@ -111,5 +262,5 @@ pub(crate) async fn brc20_all_balance(
         overall_balance: bal.overall_balance.to_string(),
       })
       .collect(),
-  })))
+  })
 }
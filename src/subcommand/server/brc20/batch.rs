@@ -0,0 +1,127 @@
+use {super::*, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchAddressesRequest {
+  /// Addresses or script hashes.
+  pub addresses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTickBalanceQuery {
+  /// Address or script hash.
+  pub address: String,
+  /// Token ticker.
+  pub tick: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTickBalancesRequest {
+  pub queries: Vec<BatchTickBalanceQuery>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTicksRequest {
+  /// Token tickers.
+  pub ticks: Vec<String>,
+}
+
+/// Get all ticker balances of many addresses.
+///
+/// Retrieve all BRC20 balances of each address from the same snapshot of the index. The results
+/// are in the order of the request, each with its own code and message.
+#[utoipa::path(
+  post,
+  path = "/api/v1/brc20/batch/balance",
+  request_body = BatchAddressesRequest,
+  responses(
+    (status = 200, description = "Obtain the balances of each address.", body = BatchBRC20AllBalance),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn brc20_batch_all_balance(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Json(req): Json<BatchAddressesRequest>,
+) -> ApiResult<Vec<ApiBatchItem<ApiBalances>>> {
+  log::debug!("rpc: post brc20_batch_all_balance: {:?}", req.addresses);
+
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+
+      Ok(Json(ApiResponse::ok(batch(req.addresses, |address| {
+        let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
+          .map_err(ApiError::bad_request)?;
+        all_balance(rtx, script_key)
+      })?)))
+    })
+    .await
+}
+
+/// Get the ticker balances of many addresses.
+///
+/// Retrieve the balance of each (address, ticker) pair from the same snapshot of the index. The
+/// results are in the order of the request, each with its own code and message.
+#[utoipa::path(
+  post,
+  path = "/api/v1/brc20/batch/tick/balance",
+  request_body = BatchTickBalancesRequest,
+  responses(
+    (status = 200, description = "Obtain the balance of each query.", body = BatchBRC20Balance),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn brc20_batch_balance(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Json(req): Json<BatchTickBalancesRequest>,
+) -> ApiResult<Vec<ApiBatchItem<ApiBalance>>> {
+  log::debug!("rpc: post brc20_batch_balance: {:?}", req.queries);
+
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+
+      Ok(Json(ApiResponse::ok(batch(req.queries, |query| {
+        let script_key = utils::parse_and_validate_script_key_with_chain(&query.address, chain)
+          .map_err(ApiError::bad_request)?;
+        tick_balance(rtx, &query.tick, script_key, None)
+      })?)))
+    })
+    .await
+}
+
+/// Get the info of many tickers.
+///
+/// Retrieve the information of each ticker from the same snapshot of the index. The results are in
+/// the order of the request, each with its own code and message.
+#[utoipa::path(
+  post,
+  path = "/api/v1/brc20/batch/tick",
+  request_body = BatchTicksRequest,
+  responses(
+    (status = 200, description = "Obtain the info of each ticker.", body = BatchBRC20Tick),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn brc20_batch_tick_info(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Json(req): Json<BatchTicksRequest>,
+) -> ApiResult<Vec<ApiBatchItem<ApiTickInfo>>> {
+  log::debug!("rpc: post brc20_batch_tick_info: {:?}", req.ticks);
+
+  snapshot
+    .read(move |rtx| {
+      Ok(Json(ApiResponse::ok(batch(req.ticks, |tick| {
+        tick_info(rtx, &tick)
+      })?)))
+    })
+    .await
+}
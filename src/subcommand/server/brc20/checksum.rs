@@ -0,0 +1,52 @@
+use {super::*, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::BlockChecksum)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiBlockChecksum {
+  /// The height of the block.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The hex encoded sha256 checksum of the BRC20 state up to the block.
+  pub checksum: String,
+}
+
+/// Get the BRC20 checksum of the block.
+///
+/// Retrieve the rolling checksum over the successful BRC20 events and the balances they touched up to the block, which can be compared with other indexers to detect divergence.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/block/{height}/checksum",
+    params(
+        ("height" = u32, Path, description = "Block height")
+  ),
+    responses(
+      (status = 200, description = "Obtain the BRC20 checksum of the block.", body = BRC20BlockChecksum),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_block_checksum(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(height): Path<String>,
+) -> ApiResult<ApiBlockChecksum> {
+  log::debug!("rpc: get brc20_block_checksum: {}", height);
+
+  let height = height.parse::<u32>().map_err(ApiError::bad_request)?;
+
+  snapshot
+    .read(move |rtx| {
+      let checksum = rtx
+        .brc20_get_block_checksum(height)?
+        .ok_or_api_not_found(format!("checksum of block {height} not found"))?;
+
+      log::debug!("rpc: get brc20_block_checksum: {} {}", height, checksum);
+
+      Ok(Json(ApiResponse::ok(ApiBlockChecksum {
+        height,
+        checksum: checksum.to_string(),
+      })))
+    })
+    .await
+}
@@ -1,5 +1,10 @@
-use super::{types::ScriptPubkey, *};
+use super::{
+  types::{PageQuery, ScriptPubkey},
+  *,
+};
 mod balance;
+mod batch;
+mod checksum;
 mod inscribe_brc20_transferable;
 mod outpoint;
 mod receipt;
@@ -7,7 +12,8 @@ mod ticker;
 mod transferable;
 
 pub(super) use {
-  balance::*, inscribe_brc20_transferable::*, outpoint::*, receipt::*, ticker::*, transferable::*,
+  balance::*, batch::*, checksum::*, inscribe_brc20_transferable::*, outpoint::*, receipt::*,
+  ticker::*, transferable::*,
 };
 
 #[derive(Debug, thiserror::Error)]
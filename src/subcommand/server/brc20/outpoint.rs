@@ -27,45 +27,49 @@ pub struct ApiOutPointResult {
   )
 )]
 pub(crate) async fn brc20_outpoint(
-  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(outpoint): Path<OutPoint>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get brc20_outpoint: {outpoint}");
 
-  let rtx = index.begin_read()?;
+  snapshot
+    .read(move |rtx| {
+      let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
+        BRC20ApiError::Internal(
+          "Failed to retrieve the latest block from the database.".to_string(),
+        )
+        .into()
+      })?;
 
-  let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
-    BRC20ApiError::Internal("Failed to retrieve the latest block from the database.".to_string())
-      .into()
-  })?;
+      let transferable_assets_with_satpoints =
+        rtx.brc20_transferable_assets_on_output_with_satpoints(outpoint)?;
 
-  let transferable_assets_with_satpoints =
-    rtx.brc20_transferable_assets_on_output_with_satpoints(outpoint)?;
+      // If there are no inscriptions on the output, return None and parsed block states.
+      if transferable_assets_with_satpoints.is_empty() {
+        return Ok(Json(ApiResponse::ok(ApiOutPointResult {
+          result: None,
+          latest_height: latest_height.n(),
+          latest_blockhash: latest_blockhash.to_string(),
+        })));
+      }
 
-  // If there are no inscriptions on the output, return None and parsed block states.
-  if transferable_assets_with_satpoints.is_empty() {
-    return Ok(Json(ApiResponse::ok(ApiOutPointResult {
-      result: None,
-      latest_height: latest_height.n(),
-      latest_blockhash: latest_blockhash.to_string(),
-    })));
-  }
-
-  Ok(Json(ApiResponse::ok(ApiOutPointResult {
-    result: Some(
-      transferable_assets_with_satpoints
-        .into_iter()
-        .map(|(satpoint, asset)| ApiTransferableAsset {
-          inscription_id: asset.inscription_id.to_string(),
-          inscription_number: asset.inscription_number,
-          amount: asset.amount.to_string(),
-          tick: asset.tick.as_str().to_string(),
-          owner: asset.owner.to_string(),
-          location: satpoint,
-        })
-        .collect(),
-    ),
-    latest_height: latest_height.n(),
-    latest_blockhash: latest_blockhash.to_string(),
-  })))
+      Ok(Json(ApiResponse::ok(ApiOutPointResult {
+        result: Some(
+          transferable_assets_with_satpoints
+            .into_iter()
+            .map(|(satpoint, asset)| ApiTransferableAsset {
+              inscription_id: asset.inscription_id.to_string(),
+              inscription_number: asset.inscription_number,
+              amount: asset.amount.to_string(),
+              tick: asset.tick.as_str().to_string(),
+              owner: asset.owner.to_string(),
+              location: satpoint,
+            })
+            .collect(),
+        ),
+        latest_height: latest_height.n(),
+        latest_blockhash: latest_blockhash.to_string(),
+      })))
+    })
+    .await
 }
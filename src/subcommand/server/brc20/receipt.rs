@@ -1,6 +1,9 @@
 use {
-  self::okx::datastore::brc20::OperationType, super::*,
-  crate::okx::datastore::brc20 as brc20_store, axum::Json, utoipa::ToSchema,
+  self::okx::datastore::brc20::OperationType,
+  super::*,
+  crate::okx::datastore::brc20::{self as brc20_store, redb::parse_receipt_cursor},
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -313,23 +316,27 @@ pub struct ApiTxEvents {
   )]
 pub(crate) async fn brc20_tx_events(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(txid): Path<String>,
 ) -> ApiResult<ApiTxEvents> {
   log::debug!("rpc: get brc20_tx_events: {}", txid);
 
   let txid = bitcoin::Txid::from_str(&txid).map_err(ApiError::bad_request)?;
-  let rtx = index.begin_read()?;
-  let client = index.bitcoin_rpc_client()?;
+  snapshot
+    .read(move |rtx| {
+      let client = index.bitcoin_rpc_client()?;
 
-  let tx_events = Index::get_brc20_transaction_receipts(txid, &rtx, &client)?
-    .ok_or(BRC20ApiError::TransactionReceiptNotFound(txid))?;
+      let tx_events = Index::get_brc20_transaction_receipts(txid, rtx, &client)?
+        .ok_or(BRC20ApiError::TransactionReceiptNotFound(txid))?;
 
-  log::debug!("rpc: get brc20_tx_events: {} {:?}", txid, tx_events);
+      log::debug!("rpc: get brc20_tx_events: {} {:?}", txid, tx_events);
 
-  Ok(Json(ApiResponse::ok(ApiTxEvents {
-    txid: txid.to_string(),
-    events: tx_events.into_iter().map(|e| e.into()).collect(),
-  })))
+      Ok(Json(ApiResponse::ok(ApiTxEvents {
+        txid: txid.to_string(),
+        events: tx_events.into_iter().map(|e| e.into()).collect(),
+      })))
+    })
+    .await
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -347,7 +354,8 @@ pub struct ApiBlockEvents {
     get,
     path = "/api/v1/brc20/block/{blockhash}/events",
     params(
-        ("blockhash" = String, Path, description = "block hash")
+        ("blockhash" = String, Path, description = "block hash"),
+        PageQuery
   ),
     responses(
       (status = 200, description = "Obtain block events by block hash", body = BRC20BlockEvents),
@@ -358,30 +366,323 @@ pub struct ApiBlockEvents {
   )]
 pub(crate) async fn brc20_block_events(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(blockhash): Path<String>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiBlockEvents> {
   log::debug!("rpc: get brc20_block_events: {}", blockhash);
 
   let blockhash = bitcoin::BlockHash::from_str(&blockhash).map_err(ApiError::bad_request)?;
 
-  let rtx = index.begin_read()?;
-  let client = index.bitcoin_rpc_client()?;
+  snapshot
+    .read(move |rtx| {
+      let client = index.bitcoin_rpc_client()?;
 
-  let block_events = Index::get_brc20_block_receipts(blockhash, &rtx, &client)?;
+      let block_events = Index::get_brc20_block_receipts(blockhash, rtx, &client)?;
 
-  log::debug!(
-    "rpc: get brc20_block_events: {} {:?}",
-    blockhash,
-    block_events
-  );
+      log::debug!(
+        "rpc: get brc20_block_events: {} {:?}",
+        blockhash,
+        block_events
+      );
 
-  Ok(Json(ApiResponse::ok(ApiBlockEvents {
-    block: block_events
-      .into_iter()
-      .map(|(txid, events)| ApiTxEvents {
-        txid: txid.to_string(),
-        events: events.into_iter().map(|e| e.into()).collect(),
-      })
-      .collect(),
-  })))
+      let (block_events, next_cursor) = page.paginate(block_events)?;
+
+      Ok(Json(ApiResponse::page(
+        ApiBlockEvents {
+          block: block_events
+            .into_iter()
+            .map(|(txid, events)| ApiTxEvents {
+              txid: txid.to_string(),
+              events: events.into_iter().map(|e| e.into()).collect(),
+            })
+            .collect(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
+}
+
+/// The cursor and the limit of a page of receipts, which are only listed in chain order.
+fn receipt_page(page: &PageQuery) -> Result<(Option<&str>, usize), ApiError> {
+  page.natural_order()?;
+
+  if let Some(cursor) = &page.cursor {
+    parse_receipt_cursor(cursor)
+      .ok_or_api_err(|| ApiError::bad_request(format!("invalid cursor {cursor}")))?;
+  }
+
+  Ok((page.cursor.as_deref(), page.page_limit()?))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct AddressEventsQuery {
+  /// Only return events of the ticker.
+  pub tick: Option<String>,
+  /// Only return events of the operation type, one of `deploy`, `mint`, `inscribeTransfer` or `transfer`.
+  pub op: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::AddressEvent)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressEvent {
+  /// The height of the block that contains the event.
+  pub height: u32,
+  /// The transaction ID that generated the event.
+  pub txid: String,
+  #[serde(flatten)]
+  #[schema(value_type = brc20::TxEvent)]
+  pub event: ApiTxEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::AddressEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressEvents {
+  #[schema(value_type = Vec<brc20::AddressEvent>)]
+  pub events: Vec<ApiAddressEvent>,
+}
+
+/// Get the events of the address.
+///
+/// Retrieve the BRC20 events sent or received by the address in chain order, ordered by block height,
+/// transaction index within the block and event index within the transaction.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/address/{address}/events",
+    params(
+        ("address" = String, Path, description = "Address"),
+        AddressEventsQuery,
+        PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain account events by query address.", body = BRC20AddressEvents),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_address_events(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(address): Path<String>,
+  Query(query): Query<AddressEventsQuery>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiAddressEvents> {
+  log::debug!("rpc: get brc20_address_events: {} {:?}", address, query);
+
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+
+      let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
+        .map_err(ApiError::bad_request)?;
+
+      let (cursor, limit) = receipt_page(&page)?;
+
+      let tick = query
+        .tick
+        .as_ref()
+        .map(|tick| {
+          brc20_store::Tick::from_str(tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))
+        })
+        .transpose()?
+        .map(|tick| tick.to_lowercase());
+
+      let op = query
+        .op
+        .as_ref()
+        .map(|op| {
+          OperationType::from_str(op)
+            .map_err(|_| ApiError::bad_request(format!("invalid operation type {op}")))
+        })
+        .transpose()?;
+
+      let (receipts, next_cursor) =
+        rtx.brc20_get_address_receipts(script_key, cursor, limit, |receipt| {
+          op.as_ref().map_or(true, |op| receipt.op == *op)
+            && tick.as_ref().map_or(true, |tick| {
+              receipt
+                .tick()
+                .map_or(false, |receipt_tick| receipt_tick.to_lowercase() == *tick)
+            })
+        })?;
+
+      log::debug!("rpc: get brc20_address_events: {} {:?}", address, receipts);
+
+      Ok(Json(ApiResponse::page(
+        ApiAddressEvents {
+          events: receipts
+            .into_iter()
+            .map(|(height, txid, receipt)| ApiAddressEvent {
+              height,
+              txid: txid.to_string(),
+              event: receipt.into(),
+            })
+            .collect(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct TickEventsQuery {
+  /// Only return events at or above the block height.
+  pub from_height: Option<u32>,
+  /// Only return events at or below the block height.
+  pub to_height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickEvents {
+  #[schema(value_type = Vec<brc20::AddressEvent>)]
+  pub events: Vec<ApiAddressEvent>,
+}
+
+/// Get the events of the ticker.
+///
+/// Retrieve the successful BRC20 events of the ticker in chain order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/events",
+    params(
+        ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
+        TickEventsQuery,
+        PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain ticker events by query.", body = BRC20TickEvents),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_tick_events(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(tick): Path<String>,
+  Query(query): Query<TickEventsQuery>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiTickEvents> {
+  log::debug!("rpc: get brc20_tick_events: {} {:?}", tick, query);
+
+  snapshot
+    .read(move |rtx| {
+      let ticker = brc20_store::Tick::from_str(&tick)
+        .map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+
+      let from_height = query.from_height.unwrap_or_default();
+      let to_height = query.to_height.unwrap_or(u32::MAX);
+      if from_height > to_height {
+        return Err(ApiError::bad_request(format!(
+          "from_height {from_height} is greater than to_height {to_height}"
+        )));
+      }
+
+      let (cursor, limit) = receipt_page(&page)?;
+
+      let (receipts, next_cursor) =
+        rtx.brc20_get_tick_receipts(&ticker, from_height, to_height, cursor, limit)?;
+
+      log::debug!("rpc: get brc20_tick_events: {} {:?}", tick, receipts);
+
+      Ok(Json(ApiResponse::page(
+        ApiTickEvents {
+          events: receipts
+            .into_iter()
+            .map(|(height, txid, receipt)| ApiAddressEvent {
+              height,
+              txid: txid.to_string(),
+              event: receipt.into(),
+            })
+            .collect(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickBurns)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickBurns {
+  #[schema(value_type = Vec<brc20::AddressEvent>)]
+  pub burns: Vec<ApiAddressEvent>,
+  /// The total amount of the ticker that has been burned.
+  #[schema(format = "uint64")]
+  pub burned_supply: String,
+}
+
+/// Get the burns of the ticker.
+///
+/// Retrieve the transfers of the ticker to OP_RETURN outputs, which burned their amount, in chain order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/burns",
+    params(
+        ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
+        TickEventsQuery,
+        PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain ticker burns by query.", body = BRC20TickBurns),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Ticker not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_tick_burns(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(tick): Path<String>,
+  Query(query): Query<TickEventsQuery>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiTickBurns> {
+  log::debug!("rpc: get brc20_tick_burns: {} {:?}", tick, query);
+
+  snapshot
+    .read(move |rtx| {
+      let ticker = brc20_store::Tick::from_str(&tick)
+        .map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+
+      let tick_info = rtx
+        .brc20_get_tick_info(&ticker)?
+        .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
+
+      let from_height = query.from_height.unwrap_or_default();
+      let to_height = query.to_height.unwrap_or(u32::MAX);
+      if from_height > to_height {
+        return Err(ApiError::bad_request(format!(
+          "from_height {from_height} is greater than to_height {to_height}"
+        )));
+      }
+
+      let (cursor, limit) = receipt_page(&page)?;
+
+      let (receipts, next_cursor) =
+        rtx.brc20_get_tick_burns(&ticker, from_height, to_height, cursor, limit)?;
+
+      log::debug!("rpc: get brc20_tick_burns: {} {:?}", tick, receipts);
+
+      Ok(Json(ApiResponse::page(
+        ApiTickBurns {
+          burns: receipts
+            .into_iter()
+            .map(|(height, txid, receipt)| ApiAddressEvent {
+              height,
+              txid: txid.to_string(),
+              event: receipt.into(),
+            })
+            .collect(),
+          burned_supply: tick_info.burned_supply.to_string(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
 }
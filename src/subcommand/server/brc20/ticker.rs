@@ -1,7 +1,10 @@
 use {
   super::*,
-  crate::okx::datastore::brc20::{Tick, TokenInfo},
-  axum::Json,
+  crate::{
+    index::rtx::Rtx,
+    okx::datastore::brc20::{Tick, TokenInfo},
+  },
+  axum::{extract::Query, Json},
   utoipa::ToSchema,
 };
 
@@ -26,6 +29,9 @@ pub struct ApiTickInfo {
   /// The amount of the ticker that has been burned.
   #[schema(format = "uint64")]
   pub burned_supply: String,
+  /// The amount of the ticker in circulation, which is the minted amount minus the burned amount.
+  #[schema(format = "uint64")]
+  pub circulating_supply: String,
   /// Whether the ticker is self minted.
   pub self_mint: bool,
   /// The maximum amount of each mining.
@@ -55,6 +61,9 @@ pub struct ApiTickInfo {
   /// The timestamp of the block that the ticker deployed.
   #[schema(format = "uint32")]
   pub deploy_blocktime: u32,
+  /// The number of addresses holding a non-zero balance of the ticker.
+  #[schema(format = "uint64")]
+  pub holders: u64,
 }
 
 impl From<TokenInfo> for ApiTickInfo {
@@ -65,6 +74,10 @@ impl From<TokenInfo> for ApiTickInfo {
       inscription_number: tick_info.inscription_number,
       supply: tick_info.supply.to_string(),
       burned_supply: tick_info.burned_supply.to_string(),
+      circulating_supply: tick_info
+        .minted
+        .saturating_sub(tick_info.burned_supply)
+        .to_string(),
       limit_per_mint: tick_info.limit_per_mint.to_string(),
       minted: tick_info.minted.to_string(),
       decimal: tick_info.decimal,
@@ -73,6 +86,7 @@ impl From<TokenInfo> for ApiTickInfo {
       txid: tick_info.inscription_id.txid.to_string(),
       deploy_height: tick_info.deployed_number,
       deploy_blocktime: tick_info.deployed_timestamp,
+      holders: tick_info.holders,
     }
   }
 }
@@ -94,21 +108,26 @@ impl From<TokenInfo> for ApiTickInfo {
     )
   )]
 pub(crate) async fn brc20_tick_info(
-  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(tick): Path<String>,
 ) -> ApiResult<ApiTickInfo> {
   log::debug!("rpc: get brc20_tick_info: {}", tick);
 
-  let rtx = index.begin_read()?;
-  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+  snapshot
+    .read(move |rtx| Ok(Json(ApiResponse::ok(tick_info(rtx, &tick)?))))
+    .await
+}
+
+pub(crate) fn tick_info(rtx: &Rtx, tick: &str) -> Result<ApiTickInfo, ApiError> {
+  let ticker = Tick::from_str(tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.into()))?;
 
   let tick_info = rtx
     .brc20_get_tick_info(&ticker)?
-    .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
+    .ok_or(BRC20ApiError::UnknownTicker(tick.into()))?;
 
   log::debug!("rpc: get brc20_tick_info: {:?} {:?}", tick, tick_info);
 
-  Ok(Json(ApiResponse::ok(tick_info.into())))
+  Ok(tick_info.into())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -121,10 +140,13 @@ pub struct ApiTickInfos {
 
 /// Get all tickers info.
 ///
-/// Retrieve detailed information about all tickers.
+/// Retrieve detailed information about all tickers in the order they were deployed.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/tick",
+    params(
+      PageQuery
+  ),
     responses(
       (status = 200, description = "Obtain matching all BRC20 tickers.", body = BRC20AllTick),
       (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
@@ -133,15 +155,110 @@ pub struct ApiTickInfos {
     )
   )]
 pub(crate) async fn brc20_all_tick_info(
-  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiTickInfos> {
-  log::debug!("rpc: get brc20_all_tick_info");
+  log::debug!("rpc: get brc20_all_tick_info: {:?}", page);
+
+  snapshot
+    .read(move |rtx| {
+      let mut all_tick_info = rtx.brc20_get_all_tick_info()?;
+      log::debug!("rpc: get brc20_all_tick_info: {:?}", all_tick_info);
+
+      all_tick_info.sort_by_key(|token| (token.deployed_number, token.inscription_number));
+      let (tokens, next_cursor) = page.paginate(all_tick_info)?;
+
+      Ok(Json(ApiResponse::page(
+        ApiTickInfos {
+          tokens: tokens.into_iter().map(|t| t.into()).collect(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickHolder)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickHolder {
+  /// The holder's script pubkey.
+  pub address: ScriptPubkey,
+  /// Overall balance.
+  #[schema(format = "uint64")]
+  pub overall_balance: String,
+  /// Transferable balance.
+  #[schema(format = "uint64")]
+  pub transferable_balance: String,
+  /// Available balance.
+  #[schema(format = "uint64")]
+  pub available_balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickHolders)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickHolders {
+  #[schema(value_type = Vec<brc20::TickHolder>)]
+  pub holders: Vec<ApiTickHolder>,
+  /// The total number of holders of the ticker.
+  #[schema(format = "uint64")]
+  pub total: u64,
+}
+
+/// Get the holders of the ticker.
+///
+/// Retrieve the addresses holding the ticker, ordered by overall balance descending.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/holders",
+    params(
+      ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
+      PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain the holders of the ticker.", body = BRC20TickHolders),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Ticker not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_tick_holders(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(tick): Path<String>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiTickHolders> {
+  log::debug!("rpc: get brc20_tick_holders: {}", tick);
+
+  snapshot
+    .read(move |rtx| {
+      let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+
+      let tick_info = rtx
+        .brc20_get_tick_info(&ticker)?
+        .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
+
+      let (positions, next_cursor) = page.range(usize::try_from(tick_info.holders).unwrap())?;
+      let holders = page.arrange(rtx.brc20_get_tick_holders(&ticker, positions)?);
 
-  let rtx = index.begin_read()?;
-  let all_tick_info = rtx.brc20_get_all_tick_info()?;
-  log::debug!("rpc: get brc20_all_tick_info: {:?}", all_tick_info);
+      log::debug!("rpc: get brc20_tick_holders: {:?} {:?}", tick, holders);
 
-  Ok(Json(ApiResponse::ok(ApiTickInfos {
-    tokens: all_tick_info.into_iter().map(|t| t.into()).collect(),
-  })))
+      Ok(Json(ApiResponse::page(
+        ApiTickHolders {
+          holders: holders
+            .into_iter()
+            .map(|(script_key, balance)| ApiTickHolder {
+              address: script_key.into(),
+              overall_balance: balance.overall_balance.to_string(),
+              transferable_balance: balance.transferable_balance.to_string(),
+              available_balance: (balance.overall_balance - balance.transferable_balance)
+                .to_string(),
+            })
+            .collect(),
+          total: tick_info.holders,
+        },
+        next_cursor,
+      )))
+    })
+    .await
 }
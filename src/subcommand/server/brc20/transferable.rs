@@ -1,4 +1,15 @@
-use {super::*, crate::okx::datastore::brc20::Tick, axum::Json, utoipa::ToSchema};
+use {
+  super::*,
+  crate::{
+    index::rtx::Rtx,
+    okx::datastore::{
+      brc20::{Tick, TransferableLog},
+      ScriptKey,
+    },
+  },
+  axum::{extract::Query, Json},
+  utoipa::ToSchema,
+};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[schema(as = brc20::TransferableAsset)]
@@ -27,7 +38,8 @@ pub struct ApiTransferableAsset {
   path = "/api/v1/brc20/tick/{ticker}/address/{address}/transferable",
   params(
       ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
-      ("address" = String, Path, description = "Address")
+      ("address" = String, Path, description = "Address or script hash"),
+      PageQuery
 ),
   responses(
     (status = 200, description = "Obtain account transferable inscriptions of ticker.", body = BRC20Transferable),
@@ -38,28 +50,81 @@ pub struct ApiTransferableAsset {
 )]
 pub(crate) async fn brc20_transferable(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path((tick, address)): Path<(String, String)>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiTransferableAssets> {
   log::debug!("rpc: get brc20_transferable: {tick} {address}");
 
-  let rtx = index.begin_read()?;
-  let chain = index.get_chain();
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
 
-  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
-  let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
+  snapshot
+    .read(move |rtx| tick_transferable(rtx, tick, script_key, &page))
+    .await
+}
+
+/// Get the transferable inscriptions of the script pubkey.
+///
+/// Retrieve the transferable inscriptions with the ticker held by outputs with the script pubkey.
+/// Use this for outputs without an address, e.g. P2PK, bare multisig or OP_RETURN.
+#[utoipa::path(
+  get,
+  path = "/api/v1/brc20/tick/{ticker}/script/{script_pubkey}/transferable",
+  params(
+      ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
+      ("script_pubkey" = String, Path, description = "Hex encoded script pubkey"),
+      PageQuery
+),
+  responses(
+    (status = 200, description = "Obtain script pubkey transferable inscriptions of ticker.", body = BRC20Transferable),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn brc20_script_transferable(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path((tick, script_pubkey)): Path<(String, String)>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiTransferableAssets> {
+  log::debug!("rpc: get brc20_script_transferable: {tick} {script_pubkey}");
+
+  let script_key = utils::parse_script_key_from_script_pubkey(&script_pubkey, index.get_chain())
     .map_err(ApiError::bad_request)?;
 
+  snapshot
+    .read(move |rtx| tick_transferable(rtx, tick, script_key, &page))
+    .await
+}
+
+fn tick_transferable(
+  rtx: &Rtx,
+  tick: String,
+  script_key: ScriptKey,
+  page: &PageQuery,
+) -> ApiResult<ApiTransferableAssets> {
+  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+
   let brc20_transferable_assets =
-    Index::get_brc20_transferable_utxo_by_tick_and_address(ticker, script_key, &rtx)?
+    Index::get_brc20_transferable_utxo_by_tick_and_address(ticker, script_key.clone(), rtx)?
       .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
 
   log::debug!(
-    "rpc: get brc20_transferable: {tick} {address} {:?}",
+    "rpc: get brc20_transferable: {tick} {script_key} {:?}",
     brc20_transferable_assets
   );
 
+  transferable_page(brc20_transferable_assets, page)
+}
+
+fn transferable_page(
+  assets: Vec<(SatPoint, TransferableLog)>,
+  page: &PageQuery,
+) -> ApiResult<ApiTransferableAssets> {
   let mut api_transferable_assets = Vec::new();
-  for (satpoint, transferable_asset) in brc20_transferable_assets {
+  for (satpoint, transferable_asset) in assets {
     api_transferable_assets.push(ApiTransferableAsset {
       inscription_id: transferable_asset.inscription_id.to_string(),
       inscription_number: transferable_asset.inscription_number,
@@ -72,9 +137,12 @@ pub(crate) async fn brc20_transferable(
 
   api_transferable_assets.sort_by(|a, b| a.inscription_number.cmp(&b.inscription_number));
 
-  Ok(Json(ApiResponse::ok(ApiTransferableAssets {
-    inscriptions: api_transferable_assets,
-  })))
+  let (inscriptions, next_cursor) = page.paginate(api_transferable_assets)?;
+
+  Ok(Json(ApiResponse::page(
+    ApiTransferableAssets { inscriptions },
+    next_cursor,
+  )))
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -92,7 +160,8 @@ pub struct ApiTransferableAssets {
   get,
   path = "/api/v1/brc20/address/{address}/transferable",
   params(
-      ("address" = String, Path, description = "Address")
+      ("address" = String, Path, description = "Address or script hash"),
+      PageQuery
 ),
   responses(
     (status = 200, description = "Obtain account all transferable inscriptions.", body = BRC20Transferable),
@@ -103,37 +172,63 @@ pub struct ApiTransferableAssets {
 )]
 pub(crate) async fn brc20_all_transferable(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(account): Path<String>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiTransferableAssets> {
   log::debug!("rpc: get brc20_all_transferable: {account}");
 
-  let rtx = index.begin_read()?;
-  let chain = index.get_chain();
+  let script_key = utils::parse_and_validate_script_key_with_chain(&account, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  snapshot
+    .read(move |rtx| all_transferable(rtx, script_key, &page))
+    .await
+}
 
-  let script_key = utils::parse_and_validate_script_key_with_chain(&account, chain)
+/// Get all transferable inscriptions of the script pubkey.
+///
+/// Retrieve the transferable inscriptions of all tickers held by outputs with the script pubkey.
+#[utoipa::path(
+  get,
+  path = "/api/v1/brc20/script/{script_pubkey}/transferable",
+  params(
+      ("script_pubkey" = String, Path, description = "Hex encoded script pubkey"),
+      PageQuery
+),
+  responses(
+    (status = 200, description = "Obtain script pubkey all transferable inscriptions.", body = BRC20Transferable),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn brc20_script_all_transferable(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(script_pubkey): Path<String>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiTransferableAssets> {
+  log::debug!("rpc: get brc20_script_all_transferable: {script_pubkey}");
+
+  let script_key = utils::parse_script_key_from_script_pubkey(&script_pubkey, index.get_chain())
     .map_err(ApiError::bad_request)?;
 
-  let brc20_transferable_assets = rtx.brc20_get_all_transferable_by_address(script_key)?;
+  snapshot
+    .read(move |rtx| all_transferable(rtx, script_key, &page))
+    .await
+}
+
+fn all_transferable(
+  rtx: &Rtx,
+  script_key: ScriptKey,
+  page: &PageQuery,
+) -> ApiResult<ApiTransferableAssets> {
+  let brc20_transferable_assets = rtx.brc20_get_all_transferable_by_address(script_key.clone())?;
   log::debug!(
-    "rpc: get brc20_all_transferable: {account} {:?}",
+    "rpc: get brc20_all_transferable: {script_key} {:?}",
     brc20_transferable_assets
   );
 
-  let mut api_transferable_assets = Vec::new();
-  for (satpoint, transferable_asset) in brc20_transferable_assets {
-    api_transferable_assets.push(ApiTransferableAsset {
-      inscription_id: transferable_asset.inscription_id.to_string(),
-      inscription_number: transferable_asset.inscription_number,
-      amount: transferable_asset.amount.to_string(),
-      tick: transferable_asset.tick.as_str().to_string(),
-      owner: transferable_asset.owner.to_string(),
-      location: satpoint,
-    });
-  }
-
-  api_transferable_assets.sort_by(|a, b| a.inscription_number.cmp(&b.inscription_number));
-
-  Ok(Json(ApiResponse::ok(ApiTransferableAssets {
-    inscriptions: api_transferable_assets,
-  })))
+  transferable_page(brc20_transferable_assets, page)
 }
@@ -89,6 +89,10 @@ pub(crate) enum ApiError {
   /// Resource not found.
   #[schema(example = json!(&ApiError::internal("not found")))]
   NotFound(String) = 3,
+
+  /// The requested snapshot of the index has been released.
+  #[schema(example = json!(&ApiError::conflict("snapshot released")))]
+  Conflict(String) = 4,
 }
 
 impl ApiError {
@@ -97,6 +101,15 @@ impl ApiError {
       Self::Internal(_) => 1,
       Self::BadRequest(_) => 2,
       Self::NotFound(_) => 3,
+      Self::Conflict(_) => 4,
+    }
+  }
+
+  pub(crate) fn message(&self) -> &str {
+    match self {
+      Self::Internal(msg) | Self::BadRequest(msg) | Self::NotFound(msg) | Self::Conflict(msg) => {
+        msg
+      }
     }
   }
 
@@ -111,17 +124,17 @@ impl ApiError {
   pub(crate) fn bad_request<S: ToString>(message: S) -> Self {
     Self::BadRequest(message.to_string())
   }
+
+  pub(crate) fn conflict<S: ToString>(message: S) -> Self {
+    Self::Conflict(message.to_string())
+  }
 }
 impl Serialize for ApiError {
   fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
     let mut state = serializer.serialize_struct("ApiError", 2)?;
-    match self {
-      ApiError::Internal(msg) | ApiError::BadRequest(msg) | ApiError::NotFound(msg) => {
-        state.serialize_field("code", &self.code())?;
-        state.serialize_field("msg", &msg)?;
-        state.end()
-      }
-    }
+    state.serialize_field("code", &self.code())?;
+    state.serialize_field("msg", self.message())?;
+    state.end()
   }
 }
 
@@ -131,6 +144,7 @@ impl IntoResponse for ApiError {
       Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
       Self::BadRequest(_) => StatusCode::BAD_REQUEST,
       Self::NotFound(_) => StatusCode::NOT_FOUND,
+      Self::Conflict(_) => StatusCode::CONFLICT,
     };
 
     (status_code, axum::Json(self)).into_response()
@@ -160,5 +174,9 @@ mod tests {
     let api_error = ApiError::not_found("not found");
     let json = serde_json::to_string(&api_error).unwrap();
     assert_eq!(json, r#"{"code":3,"msg":"not found"}"#);
+
+    let api_error = ApiError::conflict("snapshot released");
+    let json = serde_json::to_string(&api_error).unwrap();
+    assert_eq!(json, r#"{"code":4,"msg":"snapshot released"}"#);
   }
 }
@@ -0,0 +1,378 @@
+use {
+  super::{
+    brc20::{ApiTxEvent, BRC20ApiError},
+    ord::ApiTxInscription,
+    *,
+  },
+  crate::{
+    index::event::IndexEvent,
+    okx::datastore::{
+      brc20::{self as brc20_store, OperationType},
+      ScriptKey,
+    },
+  },
+  axum::response::sse::{Event, KeepAlive, Sse},
+  futures::stream::{self, Stream},
+  std::convert::Infallible,
+  tokio::sync::broadcast::{self, error::RecvError, Receiver},
+  utoipa::{IntoParams, ToSchema},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct EventsQuery {
+  /// Only push BRC20 events of the ticker, inscription operations are skipped.
+  pub tick: Option<String>,
+  /// Only push events sent or received by the address.
+  pub address: Option<String>,
+  /// Only push BRC20 events of the operation type, one of `deploy`, `mint`, `inscribeTransfer` or `transfer`,
+  /// inscription operations are skipped.
+  pub op: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = events::BRC20Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiBRC20Event {
+  /// The height of the block that contains the event.
+  pub height: u32,
+  /// The hash of the block that contains the event.
+  pub block_hash: String,
+  /// The transaction ID that generated the event.
+  pub txid: String,
+  #[serde(flatten)]
+  #[schema(value_type = brc20::TxEvent)]
+  pub event: ApiTxEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = events::InscriptionEvent)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInscriptionEvent {
+  /// The height of the block that contains the operation.
+  pub height: u32,
+  /// The hash of the block that contains the operation.
+  pub block_hash: String,
+  /// The transaction ID of the operation.
+  pub txid: String,
+  #[serde(flatten)]
+  #[schema(value_type = ord::ApiTxInscription)]
+  pub inscription: ApiTxInscription,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = events::Rollback)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRollback {
+  /// The height of the block at which the reorg was detected.
+  pub height: u32,
+  /// The number of blocks that were reorganized.
+  pub depth: u32,
+  /// The number of blocks left in the index, indexing resumes at this height.
+  pub block_count: u32,
+}
+
+/// Pending payloads that have not been received by slow subscribers are dropped.
+const PAYLOAD_CHANNEL_CAPACITY: usize = 10_000;
+
+/// The channel the relay publishes the payloads of index events on.
+pub(crate) type EventPayloads = broadcast::Sender<Arc<EventPayload>>;
+
+pub(crate) fn event_payloads() -> EventPayloads {
+  broadcast::channel(PAYLOAD_CHANNEL_CAPACITY).0
+}
+
+/// A server-sent event built once for all subscribers, along with what the filters match on.
+#[derive(Debug)]
+pub(crate) struct EventPayload {
+  event: Event,
+  kind: PayloadKind,
+}
+
+#[derive(Debug)]
+enum PayloadKind {
+  BRC20 {
+    tick: Option<brc20_store::LowerTick>,
+    op: OperationType,
+    from: ScriptKey,
+    to: ScriptKey,
+  },
+  Inscription {
+    from: ScriptKey,
+    to: Option<ScriptKey>,
+  },
+  /// Pushed to every subscriber regardless of its filter.
+  Broadcast,
+}
+
+impl EventPayload {
+  fn new(event: IndexEvent) -> Result<Self, serde_json::Error> {
+    Ok(match event {
+      IndexEvent::BRC20Receipt {
+        height,
+        block_hash,
+        txid,
+        receipt,
+      } => Self {
+        kind: PayloadKind::BRC20 {
+          tick: receipt.tick().map(|tick| tick.to_lowercase()),
+          op: receipt.op.clone(),
+          from: receipt.from.clone(),
+          to: receipt.to.clone(),
+        },
+        event: Event::default().event("brc20").json_data(ApiBRC20Event {
+          height,
+          block_hash: block_hash.to_string(),
+          txid: txid.to_string(),
+          event: receipt.into(),
+        })?,
+      },
+      IndexEvent::InscriptionOperation {
+        height,
+        block_hash,
+        operation,
+      } => Self {
+        kind: PayloadKind::Inscription {
+          from: operation.from.clone(),
+          to: operation.to.clone(),
+        },
+        event: Event::default()
+          .event("inscription")
+          .json_data(ApiInscriptionEvent {
+            height,
+            block_hash: block_hash.to_string(),
+            txid: operation.txid.to_string(),
+            inscription: operation.into(),
+          })?,
+      },
+      IndexEvent::Rollback {
+        height,
+        depth,
+        block_count,
+      } => Self {
+        kind: PayloadKind::Broadcast,
+        event: Event::default().event("rollback").json_data(ApiRollback {
+          height,
+          depth,
+          block_count,
+        })?,
+      },
+    })
+  }
+
+  fn lagged(skipped: u64) -> Self {
+    Self {
+      kind: PayloadKind::Broadcast,
+      event: Event::default().event("lagged").data(skipped.to_string()),
+    }
+  }
+}
+
+/// Build the payloads of the index events as they are published, so that each one is serialized
+/// once however many clients are subscribed.
+pub(crate) async fn relay(mut receiver: Receiver<IndexEvent>, payloads: EventPayloads) {
+  loop {
+    let payload = match receiver.recv().await {
+      Ok(event) => match EventPayload::new(event) {
+        Ok(payload) => payload,
+        Err(err) => {
+          log::warn!("failed to serialize index event: {err}");
+          continue;
+        }
+      },
+      Err(RecvError::Lagged(skipped)) => EventPayload::lagged(skipped),
+      Err(RecvError::Closed) => return,
+    };
+
+    // an error only means that nobody is listening.
+    payloads.send(Arc::new(payload)).ok();
+  }
+}
+
+struct EventFilter {
+  tick: Option<brc20_store::LowerTick>,
+  script_key: Option<ScriptKey>,
+  op: Option<OperationType>,
+}
+
+impl EventFilter {
+  fn matches(&self, kind: &PayloadKind) -> bool {
+    match kind {
+      PayloadKind::BRC20 { tick, op, from, to } => {
+        self.op.as_ref().map_or(true, |filter| op == filter)
+          && self
+            .tick
+            .as_ref()
+            .map_or(true, |filter| tick.as_ref() == Some(filter))
+          && self
+            .script_key
+            .as_ref()
+            .map_or(true, |script_key| from == script_key || to == script_key)
+      }
+      // inscription operations have no ticker nor BRC20 operation type.
+      PayloadKind::Inscription { from, to } => {
+        self.tick.is_none()
+          && self.op.is_none()
+          && self.script_key.as_ref().map_or(true, |script_key| {
+            from == script_key || to.as_ref() == Some(script_key)
+          })
+      }
+      PayloadKind::Broadcast => true,
+    }
+  }
+}
+
+/// Subscribe to index events.
+///
+/// Push the BRC20 events and inscription operations of every block as soon as it has been committed
+/// to the index, as server-sent events named `brc20`, `inscription` and `rollback`. A `rollback`
+/// event is always pushed when blocks are removed by a reorg; clients that fall too far behind
+/// receive a `lagged` event with the number of dropped events and should resynchronize.
+#[utoipa::path(
+    get,
+    path = "/api/v1/events",
+    params(
+        EventsQuery
+  ),
+    responses(
+      (status = 200, description = "Stream of index events.", content_type = "text/event-stream", body = events::Rollback),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn events(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(payloads): Extension<EventPayloads>,
+  Query(query): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+  log::debug!("rpc: get events: {:?}", query);
+
+  let chain = index.get_chain();
+
+  let filter = EventFilter {
+    tick: query
+      .tick
+      .as_ref()
+      .map(|tick| {
+        brc20_store::Tick::from_str(tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))
+      })
+      .transpose()?
+      .map(|tick| tick.to_lowercase()),
+    script_key: query
+      .address
+      .as_ref()
+      .map(|address| {
+        utils::parse_and_validate_script_key_with_chain(address, chain)
+          .map_err(ApiError::bad_request)
+      })
+      .transpose()?,
+    op: query
+      .op
+      .as_ref()
+      .map(|op| {
+        OperationType::from_str(op)
+          .map_err(|_| ApiError::bad_request(format!("invalid operation type {op}")))
+      })
+      .transpose()?,
+  };
+
+  let receiver = payloads.subscribe();
+
+  let stream = stream::unfold((receiver, filter), |(mut receiver, filter)| async move {
+    let event = next_event(&mut receiver, &filter).await?;
+    Some((Ok(event), (receiver, filter)))
+  });
+
+  Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Wait for the next event that passes the filter, `None` once the relay has stopped.
+async fn next_event(
+  receiver: &mut Receiver<Arc<EventPayload>>,
+  filter: &EventFilter,
+) -> Option<Event> {
+  loop {
+    match receiver.recv().await {
+      Ok(payload) => {
+        if filter.matches(&payload.kind) {
+          return Some(payload.event.clone());
+        }
+      }
+      Err(RecvError::Lagged(skipped)) => {
+        return Some(Event::default().event("lagged").data(skipped.to_string()));
+      }
+      Err(RecvError::Closed) => return None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::{
+      index::event::event_channel,
+      okx::datastore::ord::{Action, InscriptionOp},
+      txid,
+    },
+    bitcoin::Address,
+  };
+
+  fn script_key(address: &str) -> ScriptKey {
+    ScriptKey::from_address(Address::from_str(address).unwrap().assume_checked())
+  }
+
+  #[test]
+  fn index_events_are_relayed_once_as_payloads() {
+    Runtime::new().unwrap().block_on(async {
+      let index_events = event_channel();
+      let payloads = event_payloads();
+      let mut receiver = payloads.subscribe();
+      tokio::spawn(relay(index_events.subscribe(), payloads.clone()));
+
+      let alice = script_key("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4");
+      let bob = script_key("bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l");
+
+      index_events
+        .send(IndexEvent::InscriptionOperation {
+          height: 1,
+          block_hash: BlockHash::all_zeros(),
+          operation: InscriptionOp {
+            txid: txid(1),
+            action: Action::Transfer,
+            sequence_number: 0,
+            inscription_number: Some(0),
+            inscription_id: InscriptionId {
+              txid: txid(1),
+              index: 0,
+            },
+            old_satpoint: SatPoint::from_str(&format!("{}:0:0", txid(1))).unwrap(),
+            new_satpoint: Some(SatPoint::from_str(&format!("{}:0:0", txid(2))).unwrap()),
+            from: alice.clone(),
+            to: Some(alice.clone()),
+          },
+        })
+        .unwrap();
+      index_events
+        .send(IndexEvent::Rollback {
+          height: 2,
+          depth: 1,
+          block_count: 1,
+        })
+        .unwrap();
+
+      let filter = |script_key: &ScriptKey, tick: Option<&str>| EventFilter {
+        tick: tick.map(|tick| brc20_store::Tick::from_str(tick).unwrap().to_lowercase()),
+        script_key: Some(script_key.clone()),
+        op: None,
+      };
+
+      let inscription = receiver.recv().await.unwrap();
+      assert!(filter(&alice, None).matches(&inscription.kind));
+      assert!(!filter(&bob, None).matches(&inscription.kind));
+      assert!(!filter(&alice, Some("ordi")).matches(&inscription.kind));
+
+      let rollback = receiver.recv().await.unwrap();
+      assert!(filter(&bob, Some("ordi")).matches(&rollback.kind));
+    });
+  }
+}
@@ -1,4 +1,5 @@
-use super::*;
+use super::{brc20::ApiAddressEvent, types::PageQuery, *};
+use crate::index::ReorgRecord;
 use axum::Json;
 use shadow_rs::shadow;
 use utoipa::{IntoParams, ToSchema};
@@ -58,38 +59,160 @@ pub struct NodeInfoQuery {
   )]
 pub(crate) async fn node_info(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Query(query): Query<NodeInfoQuery>,
 ) -> ApiResult<NodeInfo> {
   log::debug!("rpc: get node_info");
-  let rtx = index.begin_read()?;
-  let client = index.bitcoin_rpc_client()?;
+  snapshot
+    .read(move |rtx| {
+      let client = index.bitcoin_rpc_client()?;
 
-  let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
-    ApiError::Internal("Failed to retrieve the latest block from the database.".to_string())
-  })?;
+      let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
+        ApiError::Internal("Failed to retrieve the latest block from the database.".to_string())
+      })?;
 
-  let (chain_block_height, chain_block_hash) = match query.btc.unwrap_or_default() {
-    true => {
-      let chain_blockchain_info = client.get_blockchain_info().map_err(ApiError::internal)?;
-      (
-        Some(u32::try_from(chain_blockchain_info.blocks).unwrap()),
-        Some(chain_blockchain_info.best_block_hash),
-      )
+      let (chain_block_height, chain_block_hash) = match query.btc.unwrap_or_default() {
+        true => {
+          let chain_blockchain_info = client.get_blockchain_info().map_err(ApiError::internal)?;
+          (
+            Some(u32::try_from(chain_blockchain_info.blocks).unwrap()),
+            Some(chain_blockchain_info.best_block_hash),
+          )
+        }
+        false => (None, None),
+      };
+
+      Ok(Json(ApiResponse::ok(NodeInfo {
+        version: Some(build::PKG_VERSION.into()),
+        branch: Some(build::BRANCH.into()),
+        commit_hash: Some(build::SHORT_COMMIT.into()),
+        build_time: Some(build::BUILD_TIME.into()),
+        chain_info: ChainInfo {
+          network: Some(index.get_chain().to_string()),
+          ord_block_height: latest_height.0,
+          ord_block_hash: latest_blockhash.to_string(),
+          chain_block_height,
+          chain_block_hash: chain_block_hash.map(|hash| hash.to_string()),
+        },
+      })))
+    })
+    .await
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedBlock {
+  /// The height of the orphaned block.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The hash of the orphaned block.
+  pub block_hash: String,
+  /// The transactions of the block with inscription operations, which have been undone.
+  pub txids: Vec<String>,
+  /// The BRC20 events of the block, which have been invalidated.
+  #[schema(value_type = Vec<brc20::AddressEvent>)]
+  pub events: Vec<ApiAddressEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Reorg {
+  /// The id of the reorg, which increases with every handled reorg.
+  #[schema(format = "uint32")]
+  pub id: u32,
+  /// The height of the block at which the reorg was detected.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The number of blocks that were reorganized.
+  #[schema(format = "uint32")]
+  pub depth: u32,
+  /// Unix timestamp of the rollback, in seconds.
+  #[schema(format = "int64")]
+  pub timestamp: i64,
+  /// The number of blocks left in the index after the rollback.
+  #[schema(format = "uint32")]
+  pub block_count: u32,
+  /// The indexed blocks that are no longer part of the best chain.
+  #[schema(value_type = Vec<OrphanedBlock>)]
+  pub orphaned_blocks: Vec<OrphanedBlock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Reorgs {
+  #[schema(value_type = Vec<Reorg>)]
+  pub reorgs: Vec<Reorg>,
+  /// The total number of reorgs handled by the indexer.
+  #[schema(format = "uint64")]
+  pub total: usize,
+}
+
+impl From<(u32, ReorgRecord)> for Reorg {
+  fn from((id, reorg): (u32, ReorgRecord)) -> Self {
+    Self {
+      id,
+      height: reorg.height,
+      depth: reorg.depth,
+      timestamp: reorg.timestamp,
+      block_count: reorg.block_count,
+      orphaned_blocks: reorg
+        .orphaned_blocks
+        .into_iter()
+        .map(|block| OrphanedBlock {
+          height: block.height,
+          block_hash: block.block_hash.to_string(),
+          txids: block.txids.iter().map(|txid| txid.to_string()).collect(),
+          events: block
+            .receipts
+            .into_iter()
+            .flat_map(|(txid, receipts)| {
+              receipts.into_iter().map(move |receipt| ApiAddressEvent {
+                height: block.height,
+                txid: txid.to_string(),
+                event: receipt.into(),
+              })
+            })
+            .collect(),
+        })
+        .collect(),
     }
-    false => (None, None),
-  };
+  }
+}
+
+/// Retrieve the reorgs handled by the indexer.
+///
+/// List the detected reorgs, the most recent first, with the orphaned blocks and the BRC20 events they invalidated.
+#[utoipa::path(
+    get,
+    path = "/api/v1/node/reorgs",
+    params(
+        PageQuery
+  ),
+    responses(
+      (status = 200, description = "Obtain the handled reorgs.", body = NodeReorgs),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn node_reorgs(
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<Reorgs> {
+  log::debug!("rpc: get node_reorgs");
+
+  snapshot
+    .read(move |rtx| {
+      let total = rtx.reorg_count()?;
+      let (positions, next_cursor) = page.range(total)?;
+      let reorgs = page.arrange(rtx.reorgs(positions)?);
 
-  Ok(Json(ApiResponse::ok(NodeInfo {
-    version: Some(build::PKG_VERSION.into()),
-    branch: Some(build::BRANCH.into()),
-    commit_hash: Some(build::SHORT_COMMIT.into()),
-    build_time: Some(build::BUILD_TIME.into()),
-    chain_info: ChainInfo {
-      network: Some(index.get_chain().to_string()),
-      ord_block_height: latest_height.0,
-      ord_block_hash: latest_blockhash.to_string(),
-      chain_block_height,
-      chain_block_hash: chain_block_hash.map(|hash| hash.to_string()),
-    },
-  })))
+      Ok(Json(ApiResponse::page(
+        Reorgs {
+          reorgs: reorgs.into_iter().map(Reorg::from).collect(),
+          total,
+        },
+        next_cursor,
+      )))
+    })
+    .await
 }
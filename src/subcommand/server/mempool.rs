@@ -0,0 +1,138 @@
+use {
+  super::{brc20::ApiAddressEvent, ord::ApiTxInscriptions, types::PageQuery, *},
+  crate::index::mempool::Mempool,
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = mempool::Inscriptions)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMempoolInscriptions {
+  /// The height of the block the unconfirmed transactions were indexed at, the block following the
+  /// index tip.
+  pub height: u32,
+  #[schema(value_type = Vec<ord::ApiTxInscriptions>)]
+  pub txs: Vec<ApiTxInscriptions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = mempool::BRC20Events)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMempoolBRC20Events {
+  /// The height of the block the unconfirmed transactions were indexed at, the block following the
+  /// index tip.
+  pub height: u32,
+  #[schema(value_type = Vec<brc20::AddressEvent>)]
+  pub events: Vec<ApiAddressEvent>,
+}
+
+fn tracked_mempool(index: &Index) -> Result<Arc<Mempool>, ApiError> {
+  index
+    .mempool()
+    .ok_or_api_not_found("mempool is not tracked, start the server with --track-mempool")
+}
+
+/// Get the unconfirmed inscription actions.
+///
+/// Retrieve the inscription actions of the mempool transactions, one page of transactions at a
+/// time, as they would be if the transactions were mined in the next block.
+#[utoipa::path(
+  get,
+  path = "/api/v1/mempool/inscriptions",
+  params(
+      PageQuery
+),
+  responses(
+    (status = 200, description = "Obtain the unconfirmed inscription actions.", body = MempoolInscriptions),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn mempool_inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiMempoolInscriptions> {
+  log::debug!("rpc: get mempool_inscriptions");
+
+  let mempool = tracked_mempool(&index)?;
+
+  let mut tx_operations: Vec<(Txid, Vec<_>)> = Vec::new();
+  for operation in &mempool.operations {
+    match tx_operations.last_mut() {
+      Some((txid, operations)) if *txid == operation.txid => operations.push(operation.clone()),
+      _ => tx_operations.push((operation.txid, vec![operation.clone()])),
+    }
+  }
+
+  let (tx_operations, next_cursor) = page.paginate(tx_operations)?;
+
+  Ok(Json(ApiResponse::page(
+    ApiMempoolInscriptions {
+      height: mempool.height,
+      txs: tx_operations
+        .into_iter()
+        .map(|(txid, operations)| ApiTxInscriptions {
+          inscriptions: operations.into_iter().map(Into::into).collect(),
+          txid: txid.to_string(),
+        })
+        .collect(),
+    },
+    next_cursor,
+  )))
+}
+
+/// Get the unconfirmed BRC20 events of the address.
+///
+/// Retrieve the BRC20 events sent or received by the address in the mempool transactions, as they
+/// would be if the transactions were mined in the next block.
+#[utoipa::path(
+  get,
+  path = "/api/v1/mempool/brc20/address/{address}",
+  params(
+      ("address" = String, Path, description = "Address"),
+      PageQuery
+),
+  responses(
+    (status = 200, description = "Obtain the unconfirmed BRC20 events of the address.", body = MempoolBRC20Events),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn mempool_brc20_address_events(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+  Query(page): Query<PageQuery>,
+) -> ApiResult<ApiMempoolBRC20Events> {
+  log::debug!("rpc: get mempool_brc20_address_events: {}", address);
+
+  let mempool = tracked_mempool(&index)?;
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  let receipts = mempool
+    .receipts
+    .iter()
+    .filter(|(_, receipt)| receipt.from == script_key || receipt.to == script_key)
+    .collect::<Vec<_>>();
+
+  let (receipts, next_cursor) = page.paginate(receipts)?;
+
+  Ok(Json(ApiResponse::page(
+    ApiMempoolBRC20Events {
+      height: mempool.height,
+      events: receipts
+        .into_iter()
+        .map(|(txid, receipt)| ApiAddressEvent {
+          height: mempool.height,
+          txid: txid.to_string(),
+          event: receipt.clone().into(),
+        })
+        .collect(),
+    },
+    next_cursor,
+  )))
+}
@@ -0,0 +1,44 @@
+use {
+  super::*,
+  crate::metrics::{API_ERRORS, API_REQUEST_DURATION},
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+};
+
+/// Serve the indexer and API metrics in the Prometheus text exposition format.
+pub(super) async fn metrics() -> ServerResult<Response> {
+  Ok(
+    (
+      [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+      crate::metrics::encode()?,
+    )
+      .into_response(),
+  )
+}
+
+/// Record the latency of API requests, and the requests answered with an error status, by route.
+pub(super) async fn track<B>(
+  matched_path: Option<MatchedPath>,
+  request: Request<B>,
+  next: Next<B>,
+) -> Response {
+  let route = matched_path.map_or_else(
+    || request.uri().path().to_string(),
+    |matched_path| matched_path.as_str().to_string(),
+  );
+
+  let start = Instant::now();
+  let response = next.run(request).await;
+
+  API_REQUEST_DURATION
+    .with_label_values(&[&route])
+    .observe(start.elapsed().as_secs_f64());
+
+  let status = response.status();
+  if status.is_client_error() || status.is_server_error() {
+    API_ERRORS
+      .with_label_values(&[&route, status.as_str()])
+      .inc();
+  }
+
+  response
+}
@@ -0,0 +1,124 @@
+use {
+  super::{types::ScriptPubkey, *},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = names::Namespace)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiNamespace {
+  /// The namespace, which is the suffix of its names.
+  pub name: String,
+  /// The validation rule of the labels, `label` or `number`.
+  pub rule: String,
+  /// The height from which names of the namespace are indexed.
+  #[schema(format = "uint32")]
+  pub activation_height: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = names::Namespaces)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiNamespaces {
+  #[schema(value_type = Vec<names::Namespace>)]
+  pub namespaces: Vec<ApiNamespace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = names::Name)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiName {
+  /// The canonical name.
+  pub name: String,
+  /// The namespace of the name.
+  pub namespace: String,
+  /// The id of the first inscription of the name.
+  pub inscription_id: String,
+  /// The current owner of the inscription, none if it has been lost.
+  pub owner: Option<ScriptPubkey>,
+}
+
+/// Get the name namespaces.
+///
+/// Retrieve the first-is-first namespaces indexed by the node.
+#[utoipa::path(
+    get,
+    path = "/api/v1/names/namespaces",
+    responses(
+      (status = 200, description = "Obtain the indexed namespaces.", body = NameNamespaces),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn names_namespaces(
+  Extension(index): Extension<Arc<Index>>,
+) -> ApiResult<ApiNamespaces> {
+  log::debug!("rpc: get names_namespaces");
+
+  Ok(Json(ApiResponse::ok(ApiNamespaces {
+    namespaces: index
+      .name_namespaces()
+      .iter()
+      .map(|namespace| ApiNamespace {
+        name: namespace.name.clone(),
+        rule: namespace.rule.to_string(),
+        activation_height: namespace.activation_height,
+      })
+      .collect(),
+  })))
+}
+
+/// Resolve a name.
+///
+/// Retrieve the inscription that registered the name and its current owner. The name is
+/// normalized according to the rule of its namespace, e.g. `Alice.sats` resolves `alice.sats`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/names/resolve/{name}",
+    params(
+      ("name" = String, Path, description = "Name including the namespace suffix"),
+  ),
+    responses(
+      (status = 200, description = "Obtain the inscription and owner of the name.", body = NameName),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Name not registered.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn names_resolve(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Path(name): Path<String>,
+) -> ApiResult<ApiName> {
+  log::debug!("rpc: get names_resolve: {name}");
+
+  let (namespace, canonical) = index
+    .name_namespaces()
+    .iter()
+    .find_map(|namespace| {
+      namespace
+        .rule
+        .parse(name.as_bytes(), &namespace.name)
+        .map(|canonical| (namespace.name.clone(), canonical))
+    })
+    .ok_or_else(|| {
+      ApiError::bad_request(format!("{name} is not a name of an indexed namespace"))
+    })?;
+
+  snapshot
+    .read(move |rtx| {
+      let inscription_id = rtx
+        .ord_name_to_inscription_id(&canonical)?
+        .ok_or_api_not_found(format!("name {canonical} not found."))?;
+
+      let owner = rtx.inscription_owner(inscription_id, index.get_chain())?;
+
+      Ok(Json(ApiResponse::ok(ApiName {
+        name: canonical,
+        namespace,
+        inscription_id: inscription_id.to_string(),
+        owner: owner.map(Into::into),
+      })))
+    })
+    .await
+}
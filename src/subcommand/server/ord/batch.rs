@@ -0,0 +1,89 @@
+use {
+  super::{error::ApiError, *},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchInscriptionsRequest {
+  /// Inscription IDs.
+  pub ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOutPointsRequest {
+  /// Outpoints.
+  pub outpoints: Vec<String>,
+}
+
+/// Get many inscriptions.
+///
+/// Retrieve the information of each inscription id from the same snapshot of the index. The
+/// results are in the order of the request, each with its own code and message.
+#[utoipa::path(
+  post,
+  path = "/api/v1/ord/batch/inscription",
+  request_body = BatchInscriptionsRequest,
+  responses(
+    (status = 200, description = "Obtain the information of each inscription.", body = BatchOrdInscription),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_batch_inscription(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Json(req): Json<BatchInscriptionsRequest>,
+) -> ApiResult<Vec<ApiBatchItem<ApiInscription>>> {
+  log::debug!("rpc: post ord_batch_inscription: {:?}", req.ids);
+
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+      let client = index.bitcoin_rpc_client()?;
+      let index_transactions = index.has_transactions_index();
+
+      Ok(Json(ApiResponse::ok(batch(req.ids, |id| {
+        let id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
+        ord_get_inscription_by_id(id, rtx, &client, chain, index_transactions)
+      })?)))
+    })
+    .await
+}
+
+/// Get the inscriptions of many outpoints.
+///
+/// Retrieve the information of each outpoint from the same snapshot of the index. The results are
+/// in the order of the request, each with its own code and message.
+#[utoipa::path(
+  post,
+  path = "/api/v1/ord/batch/outpoint",
+  request_body = BatchOutPointsRequest,
+  responses(
+    (status = 200, description = "Obtain the information of each outpoint.", body = BatchOrdOutPointResult),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_batch_outpoint(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
+  Json(req): Json<BatchOutPointsRequest>,
+) -> ApiResult<Vec<ApiBatchItem<ApiOutPointResult>>> {
+  log::debug!("rpc: post ord_batch_outpoint: {:?}", req.outpoints);
+
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+      let client = index.bitcoin_rpc_client()?;
+      let index_transactions = index.has_transactions_index();
+
+      Ok(Json(ApiResponse::ok(batch(req.outpoints, |outpoint| {
+        let outpoint = OutPoint::from_str(&outpoint).map_err(ApiError::bad_request)?;
+        ord_get_outpoint(outpoint, rtx, &client, chain, index_transactions)
+      })?)))
+    })
+    .await
+}
@@ -75,18 +75,28 @@ pub struct ApiInscription {
 )]
 pub(crate) async fn ord_inscription_id(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(id): Path<String>,
 ) -> ApiResult<ApiInscription> {
   log::debug!("rpc: get ord_inscription_id: {id}");
 
-  let rtx = index.begin_read()?;
-  let chain = index.get_chain();
-  let client = index.bitcoin_rpc_client()?;
-  let index_transactions = index.has_transactions_index();
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+      let client = index.bitcoin_rpc_client()?;
+      let index_transactions = index.has_transactions_index();
 
-  let id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
+      let id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
 
-  ord_get_inscription_by_id(id, &rtx, client, chain, index_transactions)
+      Ok(Json(ApiResponse::ok(ord_get_inscription_by_id(
+        id,
+        rtx,
+        &client,
+        chain,
+        index_transactions,
+      )?)))
+    })
+    .await
 }
 
 // /ord/number/:number/inscription
@@ -106,33 +116,43 @@ pub(crate) async fn ord_inscription_id(
 )]
 pub(crate) async fn ord_inscription_number(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(number): Path<i32>,
 ) -> ApiResult<ApiInscription> {
   log::debug!("rpc: get ord_inscription_number: {number}");
 
-  let rtx = index.begin_read()?;
-  let chain = index.get_chain();
-  let client = index.bitcoin_rpc_client()?;
-  let index_transactions = index.has_transactions_index();
+  snapshot
+    .read(move |rtx| {
+      let chain = index.get_chain();
+      let client = index.bitcoin_rpc_client()?;
+      let index_transactions = index.has_transactions_index();
 
-  let inscription_id = Index::get_inscription_id_by_inscription_number_with_rtx(number, &rtx)?
-    .ok_or(OrdApiError::UnknownInscriptionNumber(number))?;
+      let inscription_id = Index::get_inscription_id_by_inscription_number_with_rtx(number, rtx)?
+        .ok_or(OrdApiError::UnknownInscriptionNumber(number))?;
 
-  ord_get_inscription_by_id(inscription_id, &rtx, client, chain, index_transactions)
+      Ok(Json(ApiResponse::ok(ord_get_inscription_by_id(
+        inscription_id,
+        rtx,
+        &client,
+        chain,
+        index_transactions,
+      )?)))
+    })
+    .await
 }
 
-fn ord_get_inscription_by_id(
+pub(crate) fn ord_get_inscription_by_id(
   inscription_id: InscriptionId,
   rtx: &Rtx,
-  client: Client,
+  client: &Client,
   chain: Chain,
   index_transactions: bool,
-) -> ApiResult<ApiInscription> {
+) -> Result<ApiInscription, ApiError> {
   let inscription_entry = Index::get_inscription_entry_with_rtx(inscription_id, rtx)?
     .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?;
 
   let tx =
-    Index::get_transaction_with_rtx(inscription_id.txid, rtx, &client, chain, index_transactions)?
+    Index::get_transaction_with_rtx(inscription_id.txid, rtx, client, chain, index_transactions)?
       .ok_or(OrdApiError::TransactionNotFound(inscription_id.txid))?;
 
   let inscription = ParsedEnvelope::from_transaction(&tx)
@@ -162,7 +182,7 @@ fn ord_get_inscription_by_id(
       Index::get_transaction_with_rtx(
         location_outpoint.txid,
         rtx,
-        &client,
+        client,
         chain,
         index_transactions,
       )?
@@ -176,7 +196,7 @@ fn ord_get_inscription_by_id(
       .nth(location_outpoint.vout.try_into().unwrap())
   };
 
-  Ok(Json(ApiResponse::ok(ApiInscription {
+  Ok(ApiInscription {
     id: inscription_id.to_string(),
     number: inscription_entry.inscription_number,
     content_type: inscription.content_type().map(str::to_string),
@@ -197,7 +217,7 @@ fn ord_get_inscription_by_id(
     collections: collections.iter().map(|c| c.to_string()).collect(),
     charms: charms.iter().map(|c| c.title().into()).collect(),
     sat: inscription_entry.sat.map(|s| s.0),
-  })))
+  })
 }
 
 fn decompress_encoding_body(inscription: &Inscription) -> Option<ApiContentEncoding> {
@@ -222,23 +242,26 @@ fn decompress_encoding_body(inscription: &Inscription) -> Option<ApiContentEncod
 
 // ord/debug/bitmap/district/:number
 pub(crate) async fn ord_debug_bitmap_district(
-  Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(number): Path<u32>,
 ) -> ApiResult<InscriptionId> {
   log::debug!("rpc: get ord_debug_bitmap_district: number:{}", number);
 
-  let rtx = index.begin_read()?;
-  let inscription_id = rtx
-    .ord_district_to_inscription_id(number)?
-    .ok_or_api_not_found(format!("district {number} not found."))?;
-
-  log::debug!(
-    "rpc: get ord_debug_bitmap_district: {:?} {:?}",
-    number,
-    inscription_id
-  );
-
-  Ok(Json(ApiResponse::ok(inscription_id)))
+  snapshot
+    .read(move |rtx| {
+      let inscription_id = rtx
+        .ord_district_to_inscription_id(number)?
+        .ok_or_api_not_found(format!("district {number} not found."))?;
+
+      log::debug!(
+        "rpc: get ord_debug_bitmap_district: {:?} {:?}",
+        number,
+        inscription_id
+      );
+
+      Ok(Json(ApiResponse::ok(inscription_id)))
+    })
+    .await
 }
 
 #[cfg(test)]
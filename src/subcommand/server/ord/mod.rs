@@ -1,10 +1,11 @@
 use super::*;
 
+mod batch;
 mod inscription;
 mod outpoint;
 mod transaction;
 
-pub(super) use {inscription::*, outpoint::*, transaction::*};
+pub(super) use {batch::*, inscription::*, outpoint::*, transaction::*};
 
 #[derive(Debug, thiserror::Error)]
 pub enum OrdApiError {
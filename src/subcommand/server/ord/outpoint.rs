@@ -1,6 +1,6 @@
 use {
   super::{error::ApiError, types::ScriptPubkey, *},
-  crate::okx::datastore::ScriptKey,
+  crate::{index::rtx::Rtx, okx::datastore::ScriptKey},
   axum::Json,
   utoipa::ToSchema,
 };
@@ -63,27 +63,47 @@ pub struct ApiOutpointInscriptions {
 )]
 pub(crate) async fn ord_outpoint(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(outpoint): Path<OutPoint>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get ord_outpoint: {outpoint}");
 
-  let rtx = index.begin_read()?;
+  let client = index.bitcoin_rpc_client()?;
 
+  snapshot
+    .read(move |rtx| {
+      Ok(Json(ApiResponse::ok(ord_get_outpoint(
+        outpoint,
+        rtx,
+        &client,
+        index.get_chain(),
+        index.has_transactions_index(),
+      )?)))
+    })
+    .await
+}
+
+pub(crate) fn ord_get_outpoint(
+  outpoint: OutPoint,
+  rtx: &Rtx,
+  client: &Client,
+  chain: Chain,
+  index_transactions: bool,
+) -> Result<ApiOutPointResult, ApiError> {
   let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
     OrdApiError::Internal("Failed to retrieve the latest block from the database.".to_string())
       .into()
   })?;
 
   let inscriptions_with_satpoints = rtx.inscriptions_on_output_with_satpoints(outpoint)?;
-  let chain = index.get_chain();
 
   // If there are no inscriptions on the output, return None and parsed block states.
   if inscriptions_with_satpoints.is_empty() {
-    return Ok(Json(ApiResponse::ok(ApiOutPointResult {
+    return Ok(ApiOutPointResult {
       result: None,
       latest_height: latest_height.n(),
       latest_blockhash: latest_blockhash.to_string(),
-    })));
+    });
   }
 
   let mut inscription_digests = Vec::with_capacity(inscriptions_with_satpoints.len());
@@ -99,16 +119,10 @@ pub(crate) async fn ord_outpoint(
   }
 
   // Get the txout from the database store or from an RPC request.
-  let vout = Index::fetch_vout(
-    &rtx,
-    &index.bitcoin_rpc_client()?,
-    outpoint,
-    chain,
-    index.has_transactions_index(),
-  )?
-  .ok_or(OrdApiError::TransactionNotFound(outpoint.txid))?;
+  let vout = Index::fetch_vout(rtx, client, outpoint, chain, index_transactions)?
+    .ok_or(OrdApiError::TransactionNotFound(outpoint.txid))?;
 
-  Ok(Json(ApiResponse::ok(ApiOutPointResult {
+  Ok(ApiOutPointResult {
     result: Some(ApiOutpointInscriptions {
       txid: outpoint.txid.to_string(),
       script_pub_key: vout.script_pubkey.to_asm_string(),
@@ -118,5 +132,5 @@ pub(crate) async fn ord_outpoint(
     }),
     latest_height: latest_height.n(),
     latest_blockhash: latest_blockhash.to_string(),
-  })))
+  })
 }
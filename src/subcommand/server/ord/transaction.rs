@@ -1,13 +1,11 @@
 use {
-  super::{error::ApiError, types::ScriptPubkey, *},
-  crate::{
-    index::rtx::Rtx,
-    okx::datastore::{
-      ord::{Action, InscriptionOp},
-      ScriptKey,
-    },
+  super::{
+    error::ApiError,
+    types::{PageQuery, ScriptPubkey},
+    *,
   },
-  axum::Json,
+  crate::okx::datastore::ord::{Action, InscriptionOp},
+  axum::{extract::Query, Json},
   utoipa::ToSchema,
 };
 
@@ -53,46 +51,17 @@ pub struct ApiTxInscription {
   pub to: Option<ScriptPubkey>,
 }
 
-impl ApiTxInscription {
-  pub(super) fn parse_from_operation(
-    operation: InscriptionOp,
-    rtx: &Rtx,
-    client: &Client,
-    chain: Chain,
-    index_transactions: bool,
-  ) -> Result<Self> {
-    let prevout = Index::fetch_vout(
-      rtx,
-      client,
-      operation.old_satpoint.outpoint,
-      chain,
-      index_transactions,
-    )?
-    .ok_or(OrdApiError::Internal(format!(
-      "Failed to get inscription prevout: {}",
-      operation.old_satpoint.outpoint
-    )))?;
-
-    let output = match operation.new_satpoint {
-      Some(new_satpoint) if new_satpoint.outpoint != unbound_outpoint() => Index::fetch_vout(
-        rtx,
-        client,
-        new_satpoint.outpoint,
-        chain,
-        index_transactions,
-      )?,
-      _ => None,
-    };
-
-    Ok(ApiTxInscription {
-      from: ScriptKey::from_script(&prevout.script_pubkey, chain).into(),
-      to: output.map(|v| ScriptKey::from_script(&v.script_pubkey, chain).into()),
+impl From<InscriptionOp> for ApiTxInscription {
+  fn from(operation: InscriptionOp) -> Self {
+    ApiTxInscription {
+      from: operation.from.into(),
+      to: operation.to.map(Into::into),
       action: operation.action.into(),
       inscription_number: operation.inscription_number,
       inscription_id: operation.inscription_id.to_string(),
       old_satpoint: operation.old_satpoint.to_string(),
       new_satpoint: operation.new_satpoint.map(|v| v.to_string()),
-    })
+    }
   }
 }
 
@@ -119,7 +88,8 @@ pub struct ApiBlockInscriptions {
   get,
   path = "/api/v1/ord/tx/{txid}/inscriptions",
   params(
-      ("txid" = String, Path, description = "transaction ID")
+      ("txid" = String, Path, description = "transaction ID"),
+      PageQuery
 ),
   responses(
     (status = 200, description = "Obtain inscription actions by txid", body = OrdTxInscriptions),
@@ -130,43 +100,41 @@ pub struct ApiBlockInscriptions {
 )]
 pub(crate) async fn ord_txid_inscriptions(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(txid): Path<String>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiTxInscriptions> {
   log::debug!("rpc: get ord_txid_inscriptions: {}", txid);
   let txid = Txid::from_str(&txid).map_err(ApiError::bad_request)?;
-  let rtx = index.begin_read()?;
-  let client = index.bitcoin_rpc_client()?;
-  let index_transactions = index.has_transactions_index();
+  snapshot
+    .read(move |rtx| {
+      let client = index.bitcoin_rpc_client()?;
 
-  let operations = Index::get_ord_inscription_operations(txid, &rtx, &client)?
-    .ok_or(OrdApiError::TransactionReceiptNotFound(txid))?;
-  log::debug!("rpc: get ord_txid_inscriptions: {:?}", operations);
+      let operations = Index::get_ord_inscription_operations(txid, rtx, &client)?
+        .ok_or(OrdApiError::TransactionReceiptNotFound(txid))?;
+      log::debug!("rpc: get ord_txid_inscriptions: {:?}", operations);
 
-  let mut api_tx_inscriptions = Vec::new();
-  for operation in operations.into_iter() {
-    let tx_inscription = ApiTxInscription::parse_from_operation(
-      operation,
-      &rtx,
-      &client,
-      index.get_chain(),
-      index_transactions,
-    )?;
-    api_tx_inscriptions.push(tx_inscription);
-  }
+      let (operations, next_cursor) = page.paginate(operations)?;
 
-  Ok(Json(ApiResponse::ok(ApiTxInscriptions {
-    inscriptions: api_tx_inscriptions,
-    txid: txid.to_string(),
-  })))
+      Ok(Json(ApiResponse::page(
+        ApiTxInscriptions {
+          inscriptions: operations.into_iter().map(Into::into).collect(),
+          txid: txid.to_string(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
 }
 
 // ord/block/:blockhash/inscriptions
-/// Retrieve the inscription actions from the given block.
+/// Retrieve the inscription actions from the given block, one page of transactions at a time.
 #[utoipa::path(
   get,
   path = "/api/v1/ord/block/{blockhash}/inscriptions",
   params(
-      ("blockhash" = String, Path, description = "block hash")
+      ("blockhash" = String, Path, description = "block hash"),
+      PageQuery
 ),
   responses(
     (status = 200, description = "Obtain inscription actions by blockhash", body = OrdBlockInscriptions),
@@ -177,46 +145,42 @@ pub(crate) async fn ord_txid_inscriptions(
 )]
 pub(crate) async fn ord_block_inscriptions(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(blockhash): Path<String>,
+  Query(page): Query<PageQuery>,
 ) -> ApiResult<ApiBlockInscriptions> {
   log::debug!("rpc: get ord_block_inscriptions: {}", blockhash);
 
   let blockhash = bitcoin::BlockHash::from_str(&blockhash).map_err(ApiError::bad_request)?;
-  let rtx = index.begin_read()?;
-  let client = index.bitcoin_rpc_client()?;
-  let index_transactions = index.has_transactions_index();
+  snapshot
+    .read(move |rtx| {
+      let client = index.bitcoin_rpc_client()?;
 
-  let block_operations = Index::get_ord_block_inscription_operations(blockhash, &rtx, &client)?;
-  log::debug!("rpc: get ord_block_inscriptions: {:?}", block_operations);
+      let block_operations = Index::get_ord_block_inscription_operations(blockhash, rtx, &client)?;
+      log::debug!("rpc: get ord_block_inscriptions: {:?}", block_operations);
 
-  let mut api_block_operations = Vec::new();
-  for (txid, tx_operations) in block_operations.into_iter() {
-    let mut api_tx_operations = Vec::new();
-    for operation in tx_operations.into_iter() {
-      let tx_inscription = ApiTxInscription::parse_from_operation(
-        operation,
-        &rtx,
-        &client,
-        index.get_chain(),
-        index_transactions,
-      )?;
-      api_tx_operations.push(tx_inscription);
-    }
-    api_block_operations.push(ApiTxInscriptions {
-      inscriptions: api_tx_operations,
-      txid: txid.to_string(),
-    });
-  }
+      let (block_operations, next_cursor) = page.paginate(block_operations)?;
 
-  Ok(Json(ApiResponse::ok(ApiBlockInscriptions {
-    block: api_block_operations,
-  })))
+      Ok(Json(ApiResponse::page(
+        ApiBlockInscriptions {
+          block: block_operations
+            .into_iter()
+            .map(|(txid, tx_operations)| ApiTxInscriptions {
+              inscriptions: tx_operations.into_iter().map(Into::into).collect(),
+              txid: txid.to_string(),
+            })
+            .collect(),
+        },
+        next_cursor,
+      )))
+    })
+    .await
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{txid, InscriptionId, SatPoint};
+  use crate::{okx::datastore::ScriptKey, txid, InscriptionId, SatPoint};
   use std::str::FromStr;
 
   #[test]
@@ -1,15 +1,24 @@
 use {
-  super::{info::NodeInfo, *},
+  super::{
+    info::{NodeInfo, Reorgs},
+    snapshot::{ApiSnapshot, SNAPSHOT},
+    *,
+  },
   utoipa::ToSchema,
 };
 #[derive(Default, Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[aliases(
   ApiBRC20Tick = ApiResponse<brc20::ApiTickInfo>,
   ApiBRC20AllTick = ApiResponse<brc20::ApiTickInfos>,
+  ApiBRC20TickHolders = ApiResponse<brc20::ApiTickHolders>,
   ApiBRC20Balance = ApiResponse<brc20::ApiBalance>,
   ApiBRC20AllBalance = ApiResponse<brc20::ApiBalances>,
   ApiBRC20TxEvents = ApiResponse<brc20::ApiTxEvents>,
   ApiBRC20BlockEvents = ApiResponse<brc20::ApiBlockEvents>,
+  ApiBRC20AddressEvents = ApiResponse<brc20::ApiAddressEvents>,
+  ApiBRC20TickEvents = ApiResponse<brc20::ApiTickEvents>,
+  ApiBRC20TickBurns = ApiResponse<brc20::ApiTickBurns>,
+  ApiBRC20BlockChecksum = ApiResponse<brc20::ApiBlockChecksum>,
   ApiBRC20Transferable = ApiResponse<brc20::ApiTransferableAssets>,
 
   WalletApiAvailableUnspentOutputs = ApiResponse<wallet::ApiAvailableUnspentOutputs>,
@@ -20,7 +29,23 @@ use {
   ApiOrdTxInscriptions = ApiResponse<ord::ApiTxInscriptions>,
   ApiOrdBlockInscriptions = ApiResponse<ord::ApiBlockInscriptions>,
 
-  Node = ApiResponse<NodeInfo>
+  BitmapDistrict = ApiResponse<bitmap::ApiDistrict>,
+  BitmapDistricts = ApiResponse<bitmap::ApiDistricts>,
+
+  MempoolInscriptions = ApiResponse<mempool::ApiMempoolInscriptions>,
+  MempoolBRC20Events = ApiResponse<mempool::ApiMempoolBRC20Events>,
+
+  NameNamespaces = ApiResponse<names::ApiNamespaces>,
+  NameName = ApiResponse<names::ApiName>,
+
+  BatchBRC20Balance = ApiResponse<Vec<BatchItemBRC20Balance>>,
+  BatchBRC20AllBalance = ApiResponse<Vec<BatchItemBRC20AllBalance>>,
+  BatchBRC20Tick = ApiResponse<Vec<BatchItemBRC20Tick>>,
+  BatchOrdInscription = ApiResponse<Vec<BatchItemOrdInscription>>,
+  BatchOrdOutPointResult = ApiResponse<Vec<BatchItemOrdOutPointResult>>,
+
+  Node = ApiResponse<NodeInfo>,
+  NodeReorgs = ApiResponse<Reorgs>
 )]
 pub(crate) struct ApiResponse<T: Serialize> {
   pub code: i32,
@@ -28,6 +53,13 @@ pub(crate) struct ApiResponse<T: Serialize> {
   #[schema(example = "ok")]
   pub msg: String,
   pub data: T,
+  /// The block of the index state the response was read at, absent before the first block.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[schema(value_type = Option<Snapshot>)]
+  pub block: Option<ApiSnapshot>,
+  /// The cursor of the next page of a list, absent on the last page.
+  #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
 }
 
 impl<T> ApiResponse<T>
@@ -35,10 +67,91 @@ where
   T: Serialize,
 {
   fn new(code: i32, msg: String, data: T) -> Self {
-    Self { code, msg, data }
+    Self {
+      code,
+      msg,
+      data,
+      block: SNAPSHOT.try_with(ApiSnapshot::clone).ok(),
+      next_cursor: None,
+    }
   }
 
   pub fn ok(data: T) -> Self {
     Self::new(0, "ok".to_string(), data)
   }
+
+  pub fn page(data: T, next_cursor: Option<String>) -> Self {
+    Self {
+      next_cursor,
+      ..Self::ok(data)
+    }
+  }
+}
+
+/// The result of one item of a batch request, with the code and message of the single item endpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[aliases(
+  BatchItemBRC20Balance = ApiBatchItem<brc20::ApiBalance>,
+  BatchItemBRC20AllBalance = ApiBatchItem<brc20::ApiBalances>,
+  BatchItemBRC20Tick = ApiBatchItem<brc20::ApiTickInfo>,
+  BatchItemOrdInscription = ApiBatchItem<ord::ApiInscription>,
+  BatchItemOrdOutPointResult = ApiBatchItem<ord::ApiOutPointResult>
+)]
+pub(crate) struct ApiBatchItem<T: Serialize> {
+  pub code: i32,
+  /// ok, or the error of the item.
+  #[schema(example = "ok")]
+  pub msg: String,
+  /// The result of the item, absent if it failed.
+  pub data: Option<T>,
+}
+
+impl<T> From<Result<T, ApiError>> for ApiBatchItem<T>
+where
+  T: Serialize,
+{
+  fn from(result: Result<T, ApiError>) -> Self {
+    match result {
+      Ok(data) => Self {
+        code: 0,
+        msg: "ok".to_string(),
+        data: Some(data),
+      },
+      Err(error) => Self {
+        code: error.code(),
+        msg: error.message().to_string(),
+        data: None,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn batch_items_keep_the_request_order() {
+    let items = batch(vec![1, 0, 2], |n: u32| {
+      if n == 0 {
+        Err(ApiError::not_found("zero not found"))
+      } else {
+        Ok(n)
+      }
+    })
+    .map_err(|error| error.message().to_string())
+    .unwrap();
+
+    assert_eq!(
+      serde_json::to_string(&items).unwrap(),
+      r#"[{"code":0,"msg":"ok","data":1},{"code":3,"msg":"zero not found","data":null},{"code":0,"msg":"ok","data":2}]"#
+    );
+
+    assert_eq!(
+      batch(vec![0; MAX_BATCH_SIZE + 1], Ok)
+        .unwrap_err()
+        .message(),
+      "batch of 1001 items exceeds the maximum of 1000"
+    );
+  }
 }
@@ -61,75 +61,81 @@ pub struct RaritySat {
 
 pub(crate) async fn sat_range_by_outpoint(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(outpoint): Path<OutPoint>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get sat_outpoint_sat_range: {outpoint}");
 
-  let rtx = index.begin_read()?;
-
-  let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
-    ApiError::internal("Failed to retrieve the latest block from the database.".to_string())
-  })?;
-
-  let sat_ranges = Index::list_sat_range(&rtx, outpoint, index.has_sat_index())?;
-
-  Ok(Json(ApiResponse::ok(ApiOutPointResult {
-    result: sat_ranges.map(|ranges| ApiSatRanges {
-      outpoint,
-      sat_ranges: ranges.into_iter().map(ApiSatRange::Sketchy).collect(),
-    }),
-    latest_height: latest_height.n(),
-    latest_blockhash: latest_blockhash.to_string(),
-  })))
+  snapshot
+    .read(move |rtx| {
+      let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
+        ApiError::internal("Failed to retrieve the latest block from the database.".to_string())
+      })?;
+
+      let sat_ranges = Index::list_sat_range(rtx, outpoint, index.has_sat_index())?;
+
+      Ok(Json(ApiResponse::ok(ApiOutPointResult {
+        result: sat_ranges.map(|ranges| ApiSatRanges {
+          outpoint,
+          sat_ranges: ranges.into_iter().map(ApiSatRange::Sketchy).collect(),
+        }),
+        latest_height: latest_height.n(),
+        latest_blockhash: latest_blockhash.to_string(),
+      })))
+    })
+    .await
 }
 
 pub(crate) async fn sat_range_with_rarity_by_outpoint(
   Extension(index): Extension<Arc<Index>>,
+  Extension(snapshot): Extension<Arc<Snapshot>>,
   Path(outpoint): Path<OutPoint>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get sat_outpoint_sat_range: {outpoint}");
 
-  let rtx = index.begin_read()?;
-
-  let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
-    ApiError::internal("Failed to retrieve the latest block from the database.".to_string())
-  })?;
-
-  let Some(sat_ranges) = Index::list_sat_range(&rtx, outpoint, index.has_sat_index())? else {
-    return Ok(Json(ApiResponse::ok(ApiOutPointResult {
-      result: None,
-      latest_height: latest_height.n(),
-      latest_blockhash: latest_blockhash.to_string(),
-    })));
-  };
-
-  let mut exact_sat_ranges = Vec::new();
-  let mut value = 0;
-  for sat_range in sat_ranges {
-    let rarity_sats = Index::calculate_rarity_for_sat_range(sat_range)
-      .into_iter()
-      .map(|(sat, rarity)| RaritySat {
-        sat,
-        offset: sat.0 - sat_range.0 + value,
-        rarity,
-      })
-      .collect();
-    exact_sat_ranges.push(ApiSatRange::ExactWithRarity {
-      first: sat_range.0,
-      last: sat_range.1,
-      rarity_sats,
-    });
-    value += sat_range.1 - sat_range.0;
-  }
-
-  Ok(Json(ApiResponse::ok(ApiOutPointResult {
-    result: Some(ApiSatRanges {
-      outpoint,
-      sat_ranges: exact_sat_ranges,
-    }),
-    latest_height: latest_height.n(),
-    latest_blockhash: latest_blockhash.to_string(),
-  })))
+  snapshot
+    .read(move |rtx| {
+      let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
+        ApiError::internal("Failed to retrieve the latest block from the database.".to_string())
+      })?;
+
+      let Some(sat_ranges) = Index::list_sat_range(rtx, outpoint, index.has_sat_index())? else {
+        return Ok(Json(ApiResponse::ok(ApiOutPointResult {
+          result: None,
+          latest_height: latest_height.n(),
+          latest_blockhash: latest_blockhash.to_string(),
+        })));
+      };
+
+      let mut exact_sat_ranges = Vec::new();
+      let mut value = 0;
+      for sat_range in sat_ranges {
+        let rarity_sats = Index::calculate_rarity_for_sat_range(sat_range)
+          .into_iter()
+          .map(|(sat, rarity)| RaritySat {
+            sat,
+            offset: sat.0 - sat_range.0 + value,
+            rarity,
+          })
+          .collect();
+        exact_sat_ranges.push(ApiSatRange::ExactWithRarity {
+          first: sat_range.0,
+          last: sat_range.1,
+          rarity_sats,
+        });
+        value += sat_range.1 - sat_range.0;
+      }
+
+      Ok(Json(ApiResponse::ok(ApiOutPointResult {
+        result: Some(ApiSatRanges {
+          outpoint,
+          sat_ranges: exact_sat_ranges,
+        }),
+        latest_height: latest_height.n(),
+        latest_blockhash: latest_blockhash.to_string(),
+      })))
+    })
+    .await
 }
 
 #[cfg(test)]
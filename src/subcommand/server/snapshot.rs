@@ -0,0 +1,296 @@
+use {
+  super::*,
+  crate::index::rtx::Rtx,
+  axum::{
+    extract::Query,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+  },
+  tokio::sync::{mpsc, oneshot},
+  utoipa::ToSchema,
+};
+
+tokio::task_local! {
+  /// The block of the index state the current API request is answered from.
+  pub(super) static SNAPSHOT: ApiSnapshot;
+}
+
+/// The committed block an API response was read at.
+///
+/// Pass the height or hash as the `at_block` query parameter of the following requests to make
+/// sure they are answered from the same state, e.g. while paging. The state of the most recent
+/// blocks is kept, once a block is too old its snapshot is released and those requests fail with
+/// code 4, so the client can start over at the latest block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = Snapshot)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApiSnapshot {
+  /// The height of the block.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The hash of the block.
+  pub hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotQuery {
+  at_block: Option<String>,
+}
+
+impl ApiSnapshot {
+  fn latest(rtx: &Rtx) -> Result<Option<Self>> {
+    Ok(rtx.latest_block()?.map(|(height, hash)| Self {
+      height: height.n(),
+      hash: hash.to_string(),
+    }))
+  }
+
+  fn is_at(&self, block: &str) -> bool {
+    match block.parse::<u32>() {
+      Ok(height) => self.height == height,
+      Err(_) => self.hash == block,
+    }
+  }
+}
+
+impl Display for ApiSnapshot {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{} {}", self.height, self.hash)
+  }
+}
+
+/// The number of recent blocks whose state is kept for pinned requests.
+pub(super) const MAX_SNAPSHOTS: usize = 16;
+
+/// How long the state of a block is kept for pinned requests.
+pub(super) const SNAPSHOT_LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+/// A request answered from the read transaction of a snapshot.
+type Job = Box<dyn FnOnce(&Rtx) + Send>;
+
+/// A read transaction of the index and the latest block it contains.
+///
+/// The read transaction borrows the database of the index, so it is owned by a thread of the
+/// snapshot which answers the requests pinned to it, each on a scoped thread so that they run
+/// concurrently. The thread drops the transaction and exits once the snapshot is dropped.
+pub(crate) struct Snapshot {
+  pub(crate) block: Option<ApiSnapshot>,
+  created: Instant,
+  jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl Snapshot {
+  fn new(index: Arc<Index>) -> Result<Self> {
+    let (jobs, mut receiver) = mpsc::unbounded_channel::<Job>();
+    let (block_sender, block_receiver) = std::sync::mpsc::sync_channel(1);
+
+    thread::Builder::new()
+      .name("snapshot".into())
+      .spawn(move || {
+        let rtx = match index.begin_read() {
+          Ok(rtx) => rtx,
+          Err(err) => {
+            block_sender.send(Err(err)).ok();
+            return;
+          }
+        };
+
+        block_sender.send(ApiSnapshot::latest(&rtx)).ok();
+
+        thread::scope(|scope| {
+          while let Some(job) = receiver.blocking_recv() {
+            let rtx = &rtx;
+            scope.spawn(move || job(rtx));
+          }
+        });
+      })?;
+
+    let block = block_receiver.recv()??;
+
+    Ok(Self {
+      block,
+      created: Instant::now(),
+      jobs,
+    })
+  }
+
+  /// Answer a request from the read transaction of the snapshot.
+  pub(crate) async fn read<T: Send + 'static>(
+    &self,
+    f: impl FnOnce(&Rtx) -> Result<T, ApiError> + Send + 'static,
+  ) -> Result<T, ApiError> {
+    let (sender, receiver) = oneshot::channel();
+
+    // responses are tagged with the block of the request, which is task local.
+    let block = SNAPSHOT.try_with(ApiSnapshot::clone).ok();
+
+    self
+      .jobs
+      .send(Box::new(move |rtx| {
+        let result = match block {
+          Some(block) => SNAPSHOT.sync_scope(block, || f(rtx)),
+          None => f(rtx),
+        };
+        sender.send(result).ok();
+      }))
+      .map_err(|_| ApiError::internal("snapshot thread exited"))?;
+
+    receiver
+      .await
+      .map_err(|_| ApiError::internal("snapshot request failed"))?
+  }
+
+  fn is_at(&self, block: &str) -> bool {
+    self
+      .block
+      .as_ref()
+      .map_or(false, |snapshot| snapshot.is_at(block))
+  }
+
+  /// Whether the snapshot is kept for pinned requests while the index is at `height`.
+  fn is_recent(&self, height: Option<u32>, now: Instant) -> bool {
+    let behind = match (&self.block, height) {
+      (Some(block), Some(height)) => height.saturating_sub(block.height),
+      _ => 0,
+    };
+    usize::try_from(behind).unwrap() < MAX_SNAPSHOTS
+      && now.saturating_duration_since(self.created) < SNAPSHOT_LIFETIME
+  }
+}
+
+/// The read transactions of the most recent blocks, so that all the requests pinned to one of
+/// them are answered from the same state.
+///
+/// redb can not reuse the pages freed after its oldest live read transaction, so snapshots are
+/// released once the index is `MAX_SNAPSHOTS` blocks ahead of them or after `SNAPSHOT_LIFETIME`,
+/// whether or not newer blocks are requested.
+pub(crate) struct Snapshots {
+  index: Arc<Index>,
+  recent: Mutex<VecDeque<Arc<Snapshot>>>,
+}
+
+impl Snapshots {
+  pub(crate) fn new(index: Arc<Index>) -> Self {
+    Self {
+      index,
+      recent: Mutex::new(VecDeque::new()),
+    }
+  }
+
+  /// The snapshot at `at_block`, or at the latest block if not given. None if the requested
+  /// block is not the latest one and its snapshot has been released.
+  pub(super) fn get(&self, at_block: Option<&str>) -> Result<Option<Arc<Snapshot>>> {
+    if let Some(at_block) = at_block {
+      let recent = self.recent.lock().unwrap();
+      if let Some(snapshot) = recent
+        .iter()
+        .rev()
+        .find(|snapshot| snapshot.is_at(at_block))
+      {
+        return Ok(Some(snapshot.clone()));
+      }
+    }
+
+    let latest = self.latest()?;
+
+    match at_block {
+      Some(at_block) if !latest.is_at(at_block) => Ok(None),
+      _ => Ok(Some(latest)),
+    }
+  }
+
+  /// The snapshot at the latest block, which is kept for pinned requests until it is released.
+  fn latest(&self) -> Result<Arc<Snapshot>> {
+    let block = ApiSnapshot::latest(&self.index.begin_read()?)?;
+    let now = Instant::now();
+    let height = block.as_ref().map(|block| block.height);
+
+    {
+      let mut recent = self.recent.lock().unwrap();
+      recent.retain(|snapshot| snapshot.is_recent(height, now));
+      if let Some(latest) = recent.back() {
+        if latest.block == block {
+          return Ok(latest.clone());
+        }
+      }
+    }
+
+    let snapshot = Arc::new(Snapshot::new(self.index.clone())?);
+
+    let mut recent = self.recent.lock().unwrap();
+
+    // another request may have taken the snapshot of the new block in the meantime.
+    if let Some(latest) = recent.back() {
+      if latest.block == snapshot.block {
+        return Ok(latest.clone());
+      }
+    }
+
+    if snapshot.block.is_some() {
+      recent.push_back(snapshot.clone());
+      while recent.len() > MAX_SNAPSHOTS {
+        recent.pop_front();
+      }
+    }
+
+    Ok(snapshot)
+  }
+
+  /// Release the snapshots that are too far behind the index or too old at `now`.
+  pub(super) fn release(&self, now: Instant) -> Result {
+    let height = self.index.block_height()?.map(|height| height.n());
+    self
+      .recent
+      .lock()
+      .unwrap()
+      .retain(|snapshot| snapshot.is_recent(height, now));
+    Ok(())
+  }
+
+  #[cfg(test)]
+  pub(super) fn len(&self) -> usize {
+    self.recent.lock().unwrap().len()
+  }
+
+  fn released(&self, at_block: &str) -> ApiError {
+    let latest = self
+      .recent
+      .lock()
+      .unwrap()
+      .back()
+      .and_then(|snapshot| snapshot.block.clone());
+    ApiError::conflict(format!(
+      "snapshot at block {at_block} has been released, the index is at block {}",
+      latest.map_or("none".to_string(), |latest| latest.to_string())
+    ))
+  }
+}
+
+/// Answer the API request from a snapshot of the index, the one at the block requested with
+/// `at_block` if present, or else the one at the latest block. Handlers read the index with
+/// `Snapshot::read` of the `Snapshot` extension.
+pub(super) async fn snapshot<B>(
+  Extension(snapshots): Extension<Arc<Snapshots>>,
+  mut request: Request<B>,
+  next: Next<B>,
+) -> Response {
+  let at_block = match Query::<SnapshotQuery>::try_from_uri(request.uri()) {
+    Ok(Query(query)) => query.at_block,
+    Err(err) => return ApiError::bad_request(err).into_response(),
+  };
+
+  let snapshot = match snapshots.get(at_block.as_deref()) {
+    Ok(Some(snapshot)) => snapshot,
+    Ok(None) => return snapshots.released(&at_block.unwrap()).into_response(),
+    Err(err) => return ApiError::from(err).into_response(),
+  };
+
+  let block = snapshot.block.clone();
+  request.extensions_mut().insert(snapshot);
+
+  match block {
+    Some(block) => SNAPSHOT.scope(block, next.run(request)).await,
+    None => next.run(request).await,
+  }
+}
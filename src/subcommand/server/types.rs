@@ -1,6 +1,10 @@
 use super::*;
 use crate::okx::datastore::ScriptKey;
-use utoipa::ToSchema;
+use std::ops::Range;
+use utoipa::{IntoParams, ToSchema};
+
+const DEFAULT_PAGE_LIMIT: usize = 100;
+const MAX_PAGE_LIMIT: usize = 1000;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -26,9 +30,178 @@ impl From<ScriptKey> for ScriptPubkey {
     }
   }
 }
+
+/// The order of the items of a list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+  /// The natural order of the list, e.g. ascending inscription numbers.
+  #[default]
+  Asc,
+  /// The reverse order of the list.
+  Desc,
+}
+
+/// The page of a list endpoint, shared by all of them.
+///
+/// The cursor of the next page is returned as `nextCursor` in the response and is absent on the
+/// last page. Pin the requests with `at_block` to page through a consistent state.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct PageQuery {
+  /// Maximum number of items returned, at most 1000. All the items are returned by default, except
+  /// by the endpoints listing events, burns, holders, districts or reorgs which return 100.
+  pub limit: Option<usize>,
+  /// The `nextCursor` returned by the previous page.
+  pub cursor: Option<String>,
+  /// The order of the items, `asc` by default or `desc`.
+  #[param(value_type = Option<SortOrder>)]
+  pub order: Option<SortOrder>,
+}
+
+impl PageQuery {
+  /// The requested number of items, if any.
+  pub(crate) fn limit(&self) -> Result<Option<usize>, ApiError> {
+    match self.limit {
+      Some(0) => Err(ApiError::bad_request("limit must be at least 1")),
+      limit => Ok(limit.map(|limit| limit.min(MAX_PAGE_LIMIT))),
+    }
+  }
+
+  /// The number of items on the page of a list that is read from the index one page at a time.
+  pub(crate) fn page_limit(&self) -> Result<usize, ApiError> {
+    Ok(self.limit()?.unwrap_or(DEFAULT_PAGE_LIMIT))
+  }
+
+  /// Fail unless the items are requested in their natural order, for lists whose cursor is a key
+  /// of the index that can only be walked forward.
+  pub(crate) fn natural_order(&self) -> Result<(), ApiError> {
+    match self.order.unwrap_or_default() {
+      SortOrder::Asc => Ok(()),
+      SortOrder::Desc => Err(ApiError::bad_request("order desc is not supported")),
+    }
+  }
+
+  fn offset(&self) -> Result<usize, ApiError> {
+    self.cursor.as_ref().map_or(Ok(0), |cursor| {
+      cursor
+        .parse()
+        .map_err(|_| ApiError::bad_request(format!("invalid cursor {cursor}")))
+    })
+  }
+
+  /// The positions of the items of the page in a list of `len` items, and the cursor of the next
+  /// page. For lists that are read from the index one page at a time, see `page_limit`. Read the
+  /// items at the positions in their natural order and put them in the requested one with
+  /// `arrange`.
+  pub(crate) fn range(&self, len: usize) -> Result<(Range<usize>, Option<String>), ApiError> {
+    self.window(len, self.page_limit()?)
+  }
+
+  /// Put the items read from `range` in the requested order.
+  pub(crate) fn arrange<T>(&self, mut items: Vec<T>) -> Vec<T> {
+    if self.order.unwrap_or_default() == SortOrder::Desc {
+      items.reverse();
+    }
+    items
+  }
+
+  /// The items of the page and the cursor of the next page, for lists that are read whole. All the
+  /// items are returned unless a limit is requested.
+  pub(crate) fn paginate<T>(&self, items: Vec<T>) -> Result<(Vec<T>, Option<String>), ApiError> {
+    let (range, next_cursor) = self.window(items.len(), usize::MAX)?;
+    Ok((
+      self.arrange(
+        items
+          .into_iter()
+          .skip(range.start)
+          .take(range.len())
+          .collect(),
+      ),
+      next_cursor,
+    ))
+  }
+
+  fn window(
+    &self,
+    len: usize,
+    default_limit: usize,
+  ) -> Result<(Range<usize>, Option<String>), ApiError> {
+    let offset = self.offset()?.min(len);
+    let limit = self.limit()?.unwrap_or(default_limit);
+    let end = offset.saturating_add(limit).min(len);
+
+    let range = match self.order.unwrap_or_default() {
+      SortOrder::Asc => offset..end,
+      SortOrder::Desc => len - end..len - offset,
+    };
+
+    Ok((range, (end < len).then(|| end.to_string())))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+
+  #[test]
+  fn paginate() {
+    let page = |limit, cursor: Option<&str>, order| {
+      PageQuery {
+        limit,
+        cursor: cursor.map(str::to_string),
+        order,
+      }
+      .paginate((0..5).collect::<Vec<u32>>())
+      .map_err(|error| error.message().to_string())
+    };
+
+    assert_eq!(page(None, None, None), Ok((vec![0, 1, 2, 3, 4], None)));
+    assert_eq!(
+      page(Some(2), None, None),
+      Ok((vec![0, 1], Some("2".into())))
+    );
+    assert_eq!(
+      page(Some(2), Some("2"), None),
+      Ok((vec![2, 3], Some("4".into())))
+    );
+    assert_eq!(page(Some(2), Some("4"), None), Ok((vec![4], None)));
+    assert_eq!(page(Some(2), Some("9"), None), Ok((vec![], None)));
+    assert_eq!(
+      page(Some(2), Some("2"), Some(SortOrder::Desc)),
+      Ok((vec![2, 1], Some("4".into())))
+    );
+    assert_eq!(
+      page(Some(0), None, None),
+      Err("limit must be at least 1".to_string())
+    );
+    assert_eq!(
+      page(None, Some("x"), None),
+      Err("invalid cursor x".to_string())
+    );
+  }
+
+  #[test]
+  fn range() {
+    let page = |limit, cursor: Option<&str>, order| {
+      PageQuery {
+        limit,
+        cursor: cursor.map(str::to_string),
+        order,
+      }
+      .range(250)
+      .map_err(|error| error.message().to_string())
+    };
+
+    assert_eq!(page(None, None, None), Ok((0..100, Some("100".into()))));
+    assert_eq!(page(None, Some("200"), None), Ok((200..250, None)));
+    assert_eq!(
+      page(Some(10), Some("5"), Some(SortOrder::Desc)),
+      Ok((235..245, Some("15".into())))
+    );
+    assert_eq!(page(Some(5000), None, None), Ok((0..250, None)));
+  }
+
   #[test]
   fn serialize_script_pubkey() {
     let script_pubkey: ScriptPubkey = ScriptKey::from_script(
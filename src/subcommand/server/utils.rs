@@ -20,3 +20,55 @@ pub(crate) fn parse_and_validate_script_key_with_chain(
     Err(anyhow!("invalid script key: {}", key))
   }
 }
+
+/// Parse the script key of outputs with the hex encoded script pubkey, which also covers scripts
+/// without an address such as P2PK, bare multisig and OP_RETURN.
+pub(crate) fn parse_script_key_from_script_pubkey(
+  script_pubkey: &str,
+  chain: Chain,
+) -> Result<ScriptKey> {
+  let script = ScriptBuf::from_hex(script_pubkey)
+    .map_err(|_| anyhow!("invalid script pubkey: {}", script_pubkey))?;
+  Ok(ScriptKey::from_script(&script, chain))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn script_key_from_script_pubkey() {
+    let address = "bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4";
+    let script_pubkey = Address::from_str(address).unwrap().payload.script_pubkey();
+    assert_eq!(
+      parse_script_key_from_script_pubkey(&script_pubkey.to_hex_string(), Chain::Mainnet).unwrap(),
+      parse_and_validate_script_key_with_chain(address, Chain::Mainnet).unwrap()
+    );
+
+    let burn = script::Builder::new()
+      .push_opcode(opcodes::all::OP_RETURN)
+      .into_script();
+    assert_eq!(
+      parse_script_key_from_script_pubkey(&burn.to_hex_string(), Chain::Mainnet).unwrap(),
+      ScriptKey::ScriptHash {
+        script_hash: burn.script_hash(),
+        is_op_return: true,
+      }
+    );
+
+    let multisig = script::Builder::new()
+      .push_opcode(opcodes::all::OP_PUSHNUM_1)
+      .push_slice([2; 33])
+      .push_opcode(opcodes::all::OP_PUSHNUM_1)
+      .push_opcode(opcodes::all::OP_CHECKMULTISIG)
+      .into_script();
+    assert_eq!(
+      parse_script_key_from_script_pubkey(&multisig.to_hex_string(), Chain::Mainnet)
+        .unwrap()
+        .to_string(),
+      multisig.script_hash().to_string()
+    );
+
+    assert!(parse_script_key_from_script_pubkey("0x6a", Chain::Mainnet).is_err());
+  }
+}
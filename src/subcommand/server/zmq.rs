@@ -0,0 +1,101 @@
+use {
+  super::*,
+  std::sync::mpsc::{SyncSender, TrySendError},
+  zeromq::{Socket, SocketRecv, SubSocket, ZmqMessage},
+};
+
+/// Bitcoin Core sends no heartbeats and the socket does not report a dropped publisher, so the
+/// subscription is renewed when no block has been announced for this long.
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(60);
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Notify `sender` of every block announced on the `hashblock` and `rawblock` topics of the Bitcoin
+/// Core ZMQ publisher at `url`. The index thread keeps polling in between, which covers the blocks
+/// announced while the socket is down.
+pub(crate) async fn block_notifications(url: String, sender: SyncSender<()>) {
+  loop {
+    if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      return;
+    }
+
+    match subscribe(&url).await {
+      Ok(mut socket) => loop {
+        match tokio::time::timeout(RESUBSCRIBE_INTERVAL, socket.recv()).await {
+          Ok(Ok(message)) => {
+            log::debug!("ZMQ {} notification", topic(&message));
+            match sender.try_send(()) {
+              // an update is already pending and will pick up the block
+              Ok(()) | Err(TrySendError::Full(())) => {}
+              Err(TrySendError::Disconnected(())) => return,
+            }
+          }
+          Ok(Err(err)) => {
+            log::warn!(
+              "Receiving ZMQ block notifications from {url}: {err}, polling for new blocks"
+            );
+            break;
+          }
+          Err(_) => break,
+        }
+      },
+      Err(err) => {
+        log::warn!(
+          "Subscribing to ZMQ block notifications at {url}: {err}, polling for new blocks"
+        );
+        tokio::time::sleep(RECONNECT_DELAY).await;
+      }
+    }
+  }
+}
+
+async fn subscribe(url: &str) -> Result<SubSocket> {
+  let mut socket = SubSocket::new();
+  socket.connect(url).await?;
+  socket.subscribe("hashblock").await?;
+  socket.subscribe("rawblock").await?;
+  Ok(socket)
+}
+
+fn topic(message: &ZmqMessage) -> String {
+  message
+    .get(0)
+    .map(|topic| String::from_utf8_lossy(topic).into_owned())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    std::sync::mpsc,
+    zeromq::{PubSocket, SocketSend},
+  };
+
+  #[test]
+  fn published_blocks_are_notified() {
+    Runtime::new().unwrap().block_on(async {
+      let mut publisher = PubSocket::new();
+      let endpoint = publisher.bind("tcp://127.0.0.1:0").await.unwrap();
+
+      let (sender, receiver) = mpsc::sync_channel(1);
+      tokio::spawn(block_notifications(endpoint.to_string(), sender));
+
+      // the subscription is only effective once the subscriber is connected
+      for _ in 0..100 {
+        let mut message = ZmqMessage::from("hashblock");
+        message.push_back(vec![0; 32].into());
+        message.push_back(vec![0; 4].into());
+        publisher.send(message).await.unwrap();
+
+        if receiver.try_recv().is_ok() {
+          return;
+        }
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+      }
+
+      panic!("block notification not received");
+    });
+  }
+}
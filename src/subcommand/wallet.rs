@@ -15,12 +15,14 @@ use {
 };
 
 pub mod balance;
+pub mod brc20;
 pub mod cardinals;
 pub mod create;
 pub mod etch;
 pub mod inscribe;
 pub mod inscriptions;
 pub mod outputs;
+pub mod psbt;
 pub mod receive;
 mod restore;
 pub mod sats;
@@ -40,10 +42,14 @@ pub(crate) struct Wallet {
 pub(crate) enum Subcommand {
   #[command(about = "Get wallet balance")]
   Balance,
+  #[command(subcommand, about = "Transfer BRC20 tokens and list BRC20 balances")]
+  Brc20(brc20::Brc20),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Create rune")]
   Etch(etch::Etch),
+  #[command(about = "Finalize and broadcast externally signed PSBTs")]
+  FinalizePsbt(psbt::FinalizePsbt),
   #[command(about = "Create inscription")]
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
@@ -68,8 +74,10 @@ impl Wallet {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self.subcommand {
       Subcommand::Balance => balance::run(self.name, options),
+      Subcommand::Brc20(brc20) => brc20.run(self.name, options),
       Subcommand::Create(create) => create.run(self.name, options),
       Subcommand::Etch(etch) => etch.run(self.name, options),
+      Subcommand::FinalizePsbt(finalize_psbt) => finalize_psbt.run(self.name, options),
       Subcommand::Inscribe(inscribe) => inscribe.run(self.name, options),
       Subcommand::Inscriptions => inscriptions::run(self.name, options),
       Subcommand::Receive => receive::run(self.name, options),
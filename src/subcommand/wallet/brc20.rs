@@ -0,0 +1,137 @@
+use {
+  super::*,
+  crate::okx::{
+    datastore::{brc20 as brc20_store, ScriptKey},
+    protocol::brc20::Num,
+  },
+};
+
+pub mod balance;
+pub mod transfer;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Brc20 {
+  #[command(about = "List BRC20 balances of the wallet")]
+  Balance,
+  #[command(about = "Transfer BRC20 tokens from the wallet")]
+  Transfer(transfer::Transfer),
+}
+
+impl Brc20 {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    ensure!(
+      options.enable_index_brc20,
+      "`ord wallet brc20` requires index created with `--enable-index-brc20` flag",
+    );
+
+    match self {
+      Self::Balance => balance::run(wallet, options),
+      Self::Transfer(transfer) => transfer.run(wallet, options),
+    }
+  }
+}
+
+/// The script keys holding the unspent outputs of the wallet.
+fn wallet_script_keys(
+  client: &Client,
+  unspent_outputs: &BTreeMap<OutPoint, Amount>,
+  chain: Chain,
+) -> Result<Vec<ScriptKey>> {
+  let mut scripts = client
+    .list_unspent(None, None, None, None, None)?
+    .into_iter()
+    .map(|utxo| (OutPoint::new(utxo.txid, utxo.vout), utxo.script_pub_key))
+    .collect::<HashMap<OutPoint, ScriptBuf>>();
+
+  let mut script_keys = Vec::new();
+  let mut seen = HashSet::new();
+
+  for outpoint in unspent_outputs.keys() {
+    // locked outputs are not listed as unspent.
+    let script = match scripts.remove(outpoint) {
+      Some(script) => script,
+      None => {
+        client
+          .get_raw_transaction(&outpoint.txid, None)?
+          .output
+          .into_iter()
+          .nth(outpoint.vout.try_into().unwrap())
+          .ok_or_else(|| anyhow!("output {outpoint} not found"))?
+          .script_pubkey
+      }
+    };
+
+    let script_key = ScriptKey::from_script(&script, chain);
+    if seen.insert(script_key.clone()) {
+      script_keys.push(script_key);
+    }
+  }
+
+  Ok(script_keys)
+}
+
+/// Parse a decimal `amount` of a ticker with `decimal` decimals into its integer representation.
+fn parse_amount(amount: &str, decimal: u8) -> Result<u128> {
+  let num = Num::from_str(amount).map_err(|err| anyhow!("invalid amount {amount}: {err}"))?;
+
+  ensure!(
+    num.scale() <= i64::from(decimal),
+    "amount {amount} has more than {decimal} decimals"
+  );
+
+  let amount = num
+    .checked_mul(
+      &Num::from(10u64)
+        .checked_powu(u64::from(decimal))
+        .map_err(|err| anyhow!("{err}"))?,
+    )
+    .and_then(|num| num.checked_to_u128())
+    .map_err(|err| anyhow!("invalid amount {amount}: {err}"))?;
+
+  ensure!(amount > 0, "amount must be greater than zero");
+
+  Ok(amount)
+}
+
+/// Format the integer representation of an `amount` with `decimal` decimals.
+fn format_amount(amount: u128, decimal: u8) -> String {
+  let base = 10u128.pow(decimal.into());
+  let fraction = amount % base;
+
+  if fraction == 0 {
+    return (amount / base).to_string();
+  }
+
+  format!(
+    "{}.{}",
+    amount / base,
+    format!("{fraction:0width$}", width = usize::from(decimal)).trim_end_matches('0')
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn amounts() {
+    assert_eq!(parse_amount("10", 18).unwrap(), 10 * 10u128.pow(18));
+    assert_eq!(parse_amount("1.5", 2).unwrap(), 150);
+    assert_eq!(parse_amount("7", 0).unwrap(), 7);
+    assert_eq!(
+      parse_amount("1.55", 1).unwrap_err().to_string(),
+      "amount 1.55 has more than 1 decimals"
+    );
+    assert_eq!(
+      parse_amount("0", 18).unwrap_err().to_string(),
+      "amount must be greater than zero"
+    );
+    assert!(parse_amount("foo", 18).is_err());
+
+    assert_eq!(format_amount(10 * 10u128.pow(18), 18), "10");
+    assert_eq!(format_amount(150, 2), "1.5");
+    assert_eq!(format_amount(105, 2), "1.05");
+    assert_eq!(format_amount(5, 2), "0.05");
+    assert_eq!(format_amount(7, 0), "7");
+  }
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  pub tick: String,
+  pub overall_balance: String,
+  pub transferable_balance: String,
+  pub available_balance: String,
+}
+
+pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+  index.update()?;
+
+  let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+  let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+  let rtx = index.begin_read()?;
+
+  let mut balances: BTreeMap<String, (brc20_store::Tick, u128, u128)> = BTreeMap::new();
+  for script_key in wallet_script_keys(&client, &unspent_outputs, options.chain())? {
+    for balance in rtx.brc20_get_all_balance_by_address(script_key)? {
+      let entry = balances
+        .entry(balance.tick.to_lowercase().as_str().to_string())
+        .or_insert((balance.tick.clone(), 0, 0));
+      entry.1 += balance.overall_balance;
+      entry.2 += balance.transferable_balance;
+    }
+  }
+
+  let mut outputs = Vec::new();
+  for (tick, overall, transferable) in balances.into_values() {
+    let decimal = rtx
+      .brc20_get_tick_info(&tick)?
+      .ok_or_else(|| anyhow!("BRC20 token {} not found", tick.as_str()))?
+      .decimal;
+
+    outputs.push(Output {
+      tick: tick.as_str(),
+      overall_balance: format_amount(overall, decimal),
+      transferable_balance: format_amount(transferable, decimal),
+      available_balance: format_amount(overall - transferable, decimal),
+    });
+  }
+
+  Ok(Box::new(outputs))
+}
@@ -0,0 +1,181 @@
+use {
+  super::*,
+  crate::subcommand::wallet::{inscribe::Inscribe, send::Send},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Transfer {
+  #[arg(long, help = "Transfer tokens of <TICK>.")]
+  tick: String,
+  #[arg(long, help = "Transfer <AMOUNT> tokens.")]
+  amount: String,
+  #[arg(long, help = "Send the tokens to <TO>.")]
+  to: Address<NetworkUnchecked>,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub inscription: InscriptionId,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reveal: Option<Txid>,
+  pub transaction: Txid,
+}
+
+impl Transfer {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let to = self.to.clone().require_network(options.chain().network())?;
+
+    let (inscription, reveal) = self.transferable(&wallet, &options, &to)?;
+
+    let sent = Send {
+      address: self.to,
+      outgoing: Outgoing::InscriptionId(inscription),
+      fee_rate: self.fee_rate,
+      postage: None,
+      psbt: false,
+    }
+    .send(wallet, options)?;
+
+    Ok(Box::new(Output {
+      inscription,
+      reveal,
+      transaction: sent.transaction,
+    }))
+  }
+
+  /// Pick a transferable inscription of the amount held by the wallet, or inscribe one and wait
+  /// until it is confirmed. Returns the reveal transaction when a new one was inscribed.
+  ///
+  /// The tokens of the recipient are never used, since the wallet may own its address.
+  fn transferable(
+    &self,
+    wallet: &str,
+    options: &Options,
+    to: &Address,
+  ) -> Result<(InscriptionId, Option<Txid>)> {
+    let index = Index::open(options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet.to_string(), options)?;
+
+    let chain = options.chain();
+
+    let tick = brc20_store::Tick::from_str(&self.tick).map_err(|err| anyhow!("{err}"))?;
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let recipient = ScriptKey::from_script(&to.script_pubkey(), chain);
+
+    let script_keys = wallet_script_keys(&client, &unspent_outputs, chain)?
+      .into_iter()
+      .filter(|script_key| *script_key != recipient)
+      .collect::<Vec<ScriptKey>>();
+
+    let owner = {
+      let rtx = index.begin_read()?;
+
+      let token = rtx
+        .brc20_get_tick_info(&tick)?
+        .ok_or_else(|| anyhow!("BRC20 token {} not found", self.tick))?;
+
+      let amount = parse_amount(&self.amount, token.decimal)?;
+
+      for script_key in &script_keys {
+        for (satpoint, transferable) in
+          rtx.brc20_get_tick_transferable_by_address(&tick, script_key.clone())?
+        {
+          if transferable.amount == amount && unspent_outputs.contains_key(&satpoint.outpoint) {
+            return Ok((transferable.inscription_id, None));
+          }
+        }
+      }
+
+      let mut owner = None;
+      for script_key in script_keys {
+        let ScriptKey::Address(address) = &script_key else {
+          continue;
+        };
+
+        let available = rtx
+          .brc20_get_balance_by_address(&tick, script_key.clone())?
+          .map_or(0, |balance| {
+            balance.overall_balance - balance.transferable_balance
+          });
+
+        if available >= amount {
+          owner = Some(address.clone().require_network(chain.network())?);
+          break;
+        }
+      }
+
+      owner.ok_or_else(|| {
+        anyhow!(
+          "no address of the wallet has an available {} balance of {}",
+          self.tick,
+          self.amount
+        )
+      })?
+    };
+
+    let inscription = Inscription::from_content(
+      chain,
+      "text/plain;charset=utf-8".into(),
+      serde_json::json!({
+        "p": "brc-20",
+        "op": "transfer",
+        "tick": self.tick,
+        "amt": self.amount,
+      })
+      .to_string(),
+      None,
+      None,
+      None,
+      None,
+    )?;
+
+    let reveal =
+      Inscribe::inscribe_with_wallet(inscription, owner, self.fee_rate, chain, &index, &client)?
+        .reveal;
+
+    let inscription_id = InscriptionId {
+      txid: reveal,
+      index: 0,
+    };
+
+    eprintln!("Waiting for transferable inscription {inscription_id} to be confirmed...");
+
+    loop {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        bail!("interrupted before transferable inscription {inscription_id} was confirmed");
+      }
+
+      thread::sleep(Duration::from_secs(1));
+
+      index.update()?;
+
+      if index.get_inscription_entry(inscription_id)?.is_none() {
+        continue;
+      }
+
+      let result = index
+        .begin_read()?
+        .brc20_transaction_id_to_transaction_receipt(reveal)?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|receipt| receipt.inscription_id == inscription_id)
+        .map(|receipt| receipt.result);
+
+      return match result {
+        Some(Ok(_)) => Ok((inscription_id, Some(reveal))),
+        Some(Err(err)) => Err(anyhow!(
+          "transferable inscription {inscription_id} is invalid: {err}"
+        )),
+        None => Err(anyhow!(
+          "inscription {inscription_id} is not a BRC20 transfer"
+        )),
+      };
+    }
+  }
+}
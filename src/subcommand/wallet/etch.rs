@@ -12,12 +12,19 @@ pub(crate) struct Etch {
   supply: Decimal,
   #[clap(long, help = "Set currency symbol to <SYMBOL>.")]
   symbol: char,
+  #[clap(
+    long,
+    help = "Don't sign or broadcast the transaction, output a PSBT to sign externally and pass to `ord wallet finalize-psbt` instead."
+  )]
+  psbt: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Output {
   pub rune: SpacedRune,
   pub transaction: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub psbt: Option<String>,
 }
 
 impl Etch {
@@ -118,6 +125,19 @@ impl Etch {
 
     let unsigned_transaction = fund_raw_transaction(&client, self.fee_rate, &unfunded_transaction)?;
 
+    if self.psbt {
+      let unsigned_transaction: Transaction =
+        consensus::encode::deserialize(&unsigned_transaction)?;
+
+      let psbt = psbt::create(&client, &unsigned_transaction, &BTreeMap::new())?;
+
+      return Ok(Box::new(Output {
+        rune: self.rune,
+        transaction: unsigned_transaction.txid(),
+        psbt: Some(psbt::encode(&psbt)),
+      }));
+    }
+
     let signed_transaction = client
       .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
       .hex;
@@ -127,6 +147,7 @@ impl Etch {
     Ok(Box::new(Output {
       rune: self.rune,
       transaction,
+      psbt: None,
     }))
   }
 }
@@ -31,17 +31,23 @@ pub struct InscriptionInfo {
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub commit: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub commit_psbt: Option<String>,
   pub inscriptions: Vec<InscriptionInfo>,
   pub parent: Option<InscriptionId>,
   pub reveal: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reveal_psbt: Option<String>,
   pub total_fees: u64,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct OutputForOutsideSign {
   pub unsigned_commit_raw_tx_hex: String,
+  pub commit_psbt: String,
   pub inscription: InscriptionInfo,
   pub signed_reveal_raw_tx_hex: String,
+  pub reveal_psbt: String,
   pub total_fees: u64,
 }
 
@@ -85,6 +91,12 @@ pub(crate) struct Inscribe {
   pub(crate) destination: Option<Address<NetworkUnchecked>>,
   #[arg(long, help = "Don't sign or broadcast transactions.")]
   pub(crate) dry_run: bool,
+  #[arg(
+    long,
+    help = "Don't sign or broadcast transactions, output PSBTs to sign externally and pass to `ord wallet finalize-psbt` instead.",
+    conflicts_with = "dry_run"
+  )]
+  pub(crate) psbt: bool,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
   pub(crate) fee_rate: FeeRate,
   #[arg(long, help = "Inscribe sat with contents of <FILE>.")]
@@ -212,21 +224,51 @@ impl Inscribe {
       self.satpoint
     };
 
+    Ok(Box::new(
+      Batch {
+        commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
+        destinations,
+        dry_run: self.dry_run,
+        inscriptions,
+        mode,
+        no_backup: self.no_backup,
+        no_limit: self.no_limit,
+        parent_info,
+        postage,
+        psbt: self.psbt,
+        reinscribe: self.reinscribe,
+        reveal_fee_rate: self.fee_rate,
+        satpoint,
+      }
+      .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)?,
+    ))
+  }
+
+  /// Inscribe `inscription` on a cardinal output of the wallet and send it to `destination`.
+  pub(crate) fn inscribe_with_wallet(
+    inscription: Inscription,
+    destination: Address,
+    fee_rate: FeeRate,
+    chain: Chain,
+    index: &Index,
+    client: &Client,
+  ) -> Result<Output> {
+    let utxos = get_unspent_outputs(client, index)?;
+
+    let locked_utxos = get_locked_outputs(client)?;
+
+    let runic_utxos = index.get_runic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?;
+
     Batch {
-      commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
-      destinations,
-      dry_run: self.dry_run,
-      inscriptions,
-      mode,
-      no_backup: self.no_backup,
-      no_limit: self.no_limit,
-      parent_info,
-      postage,
-      reinscribe: self.reinscribe,
-      reveal_fee_rate: self.fee_rate,
-      satpoint,
+      commit_fee_rate: fee_rate,
+      destinations: vec![destination],
+      inscriptions: vec![inscription],
+      mode: Mode::SeparateOutputs,
+      postage: TARGET_POSTAGE,
+      reveal_fee_rate: fee_rate,
+      ..Default::default()
     }
-    .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)
+    .inscribe(chain, index, client, &locked_utxos, runic_utxos, &utxos)
   }
 
   fn parse_metadata(cbor: Option<PathBuf>, json: Option<PathBuf>) -> Result<Option<Vec<u8>>> {
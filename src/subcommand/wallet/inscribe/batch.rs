@@ -10,6 +10,7 @@ pub(super) struct Batch {
   pub(super) no_limit: bool,
   pub(super) parent_info: Option<ParentInfo>,
   pub(super) postage: Amount,
+  pub(super) psbt: bool,
   pub(super) reinscribe: bool,
   pub(super) reveal_fee_rate: FeeRate,
   pub(super) satpoint: Option<SatPoint>,
@@ -27,6 +28,7 @@ impl Default for Batch {
       no_limit: false,
       parent_info: None,
       postage: Amount::from_sat(10_000),
+      psbt: false,
       reinscribe: false,
       reveal_fee_rate: 1.0.try_into().unwrap(),
       satpoint: None,
@@ -43,7 +45,7 @@ impl Batch {
     locked_utxos: &BTreeSet<OutPoint>,
     runic_utxos: BTreeSet<OutPoint>,
     utxos: &BTreeMap<OutPoint, Amount>,
-  ) -> SubcommandResult {
+  ) -> Result<super::Output> {
     let wallet_inscriptions = index.get_inscriptions(utxos)?;
 
     let commit_tx_change = [
@@ -62,12 +64,39 @@ impl Batch {
       )?;
 
     if self.dry_run {
-      return Ok(Box::new(self.output(
+      return Ok(self.output(
         commit_tx.txid(),
         reveal_tx.txid(),
         total_fees,
         self.inscriptions.clone(),
-      )));
+      ));
+    }
+
+    if self.psbt {
+      let commit_psbt = psbt::create(client, &commit_tx, &BTreeMap::new())?;
+
+      let mut reveal_prevouts = psbt::outputs(&commit_tx);
+
+      if let Some(parent_info) = &self.parent_info {
+        reveal_prevouts.insert(parent_info.location.outpoint, parent_info.tx_out.clone());
+      }
+
+      let reveal_psbt = psbt::create(client, &reveal_tx, &reveal_prevouts)?;
+
+      if !self.no_backup {
+        backup_recovery_key(index, recovery_key_pair, chain.network())?;
+      }
+
+      return Ok(super::Output {
+        commit_psbt: Some(psbt::encode(&commit_psbt)),
+        reveal_psbt: Some(psbt::encode(&reveal_psbt)),
+        ..self.output(
+          commit_tx.txid(),
+          reveal_tx.txid(),
+          total_fees,
+          self.inscriptions.clone(),
+        )
+      });
     }
 
     let signed_commit_tx = client
@@ -114,12 +143,7 @@ impl Batch {
       }
     };
 
-    Ok(Box::new(self.output(
-      commit,
-      reveal,
-      total_fees,
-      self.inscriptions.clone(),
-    )))
+    Ok(self.output(commit, reveal, total_fees, self.inscriptions.clone()))
   }
 
   fn output(
@@ -169,7 +193,9 @@ impl Batch {
 
     super::Output {
       commit,
+      commit_psbt: None,
       reveal,
+      reveal_psbt: None,
       total_fees,
       parent: self.parent_info.clone().map(|info| info.id),
       inscriptions: inscriptions_output,
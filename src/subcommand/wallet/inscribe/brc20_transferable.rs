@@ -55,18 +55,30 @@ impl Brc20Transferable {
     }
 
     Ok(Box::new(self.output_for_outside_sign(
+      &index,
       unsigned_commit_tx,
       reveal_tx,
       total_fees,
-    )))
+    )?))
   }
 
   fn output_for_outside_sign(
     &self,
+    index: &Index,
     unsigned_commit_tx: Transaction,
     signed_reveal_tx: Transaction,
     total_fees: u64,
-  ) -> OutputForOutsideSign {
+  ) -> Result<OutputForOutsideSign> {
+    let client = index.bitcoin_rpc_client()?;
+
+    let commit_psbt = psbt::create(&client, &unsigned_commit_tx, &BTreeMap::new())?;
+
+    let reveal_psbt = psbt::create(
+      &client,
+      &signed_reveal_tx,
+      &psbt::outputs(&unsigned_commit_tx),
+    )?;
+
     let reveal_tx_id = signed_reveal_tx.txid();
     let inscriptions_output = InscriptionInfo {
       id: InscriptionId {
@@ -82,12 +94,14 @@ impl Brc20Transferable {
       },
     };
 
-    OutputForOutsideSign {
+    Ok(OutputForOutsideSign {
       unsigned_commit_raw_tx_hex: unsigned_commit_tx.raw_hex(),
+      commit_psbt: psbt::encode(&commit_psbt),
       signed_reveal_raw_tx_hex: signed_reveal_tx.raw_hex(),
+      reveal_psbt: psbt::encode(&reveal_psbt),
       total_fees,
       inscription: inscriptions_output,
-    }
+    })
   }
 
   pub(crate) fn create_batch_inscription_transactions(
@@ -0,0 +1,517 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{
+    psbt::Psbt,
+    secp256k1::{Message, Secp256k1, XOnlyPublicKey},
+    sighash::{Prevouts, SighashCache},
+    taproot::{self, ControlBlock, LeafVersion, TapLeafHash},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct FinalizePsbt {
+  #[arg(help = "Finalize and broadcast the externally signed base64 <PSBTS>, in the order given.")]
+  psbts: Vec<String>,
+  #[arg(long, help = "Don't broadcast the finalized transactions.")]
+  dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub transactions: Vec<Txid>,
+}
+
+impl FinalizePsbt {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    ensure!(!self.psbts.is_empty(), "no PSBT to finalize");
+
+    let transactions = self
+      .psbts
+      .iter()
+      .map(|psbt| finalize(decode(psbt)?))
+      .collect::<Result<Vec<Transaction>>>()?;
+
+    if self.dry_run {
+      return Ok(Box::new(Output {
+        transactions: transactions.iter().map(Transaction::txid).collect(),
+      }));
+    }
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let mut txids = Vec::new();
+    for transaction in &transactions {
+      txids.push(client.send_raw_transaction(transaction)?);
+    }
+
+    Ok(Box::new(Output {
+      transactions: txids,
+    }))
+  }
+}
+
+/// Build a PSBT from `tx` and have the wallet fill in the outputs it spends and the origins of its
+/// keys, which external signers need.
+///
+/// `prevouts` are the spent outputs the wallet doesn't know about, like the commit output spent by
+/// an inscription reveal. Taproot script path witnesses already present in `tx`, like the one of
+/// the reveal input, are turned into the internal key, the tapscript leaf and the signature of the
+/// input, any other witness is kept as the final witness of the input.
+pub(crate) fn create(
+  client: &Client,
+  tx: &Transaction,
+  prevouts: &BTreeMap<OutPoint, TxOut>,
+) -> Result<Psbt> {
+  let psbt = client.wallet_process_psbt(
+    &encode(&unsigned(tx, prevouts)?),
+    Some(false),
+    None,
+    Some(true),
+  )?;
+
+  let psbt = decode(&psbt.psbt)?;
+
+  for (input, txin) in psbt.inputs.iter().zip(&psbt.unsigned_tx.input) {
+    ensure!(
+      input.witness_utxo.is_some() || input.non_witness_utxo.is_some(),
+      "output {} spent by PSBT not found",
+      txin.previous_output
+    );
+  }
+
+  Ok(psbt)
+}
+
+fn unsigned(tx: &Transaction, prevouts: &BTreeMap<OutPoint, TxOut>) -> Result<Psbt> {
+  let mut unsigned_tx = tx.clone();
+  for input in &mut unsigned_tx.input {
+    input.script_sig = ScriptBuf::new();
+    input.witness = Witness::new();
+  }
+
+  let mut psbt = Psbt::from_unsigned_tx(unsigned_tx)?;
+
+  for (input, txin) in psbt.inputs.iter_mut().zip(&tx.input) {
+    input.witness_utxo = prevouts.get(&txin.previous_output).cloned();
+
+    if txin.witness.is_empty() {
+      continue;
+    }
+
+    match script_path_spend(&txin.witness) {
+      Some((signature, script, control_block, public_key)) => {
+        let leaf_hash = TapLeafHash::from_script(&script, LeafVersion::TapScript);
+        input.tap_internal_key = Some(control_block.internal_key);
+        input
+          .tap_script_sigs
+          .insert((public_key, leaf_hash), signature);
+        input
+          .tap_scripts
+          .insert(control_block, (script, LeafVersion::TapScript));
+      }
+      None => input.final_script_witness = Some(txin.witness.clone()),
+    }
+  }
+
+  Ok(psbt)
+}
+
+/// The outputs of `tx`, which are spent by transactions built on top of it.
+pub(crate) fn outputs(tx: &Transaction) -> BTreeMap<OutPoint, TxOut> {
+  let txid = tx.txid();
+  tx.output
+    .iter()
+    .enumerate()
+    .map(|(vout, output)| {
+      (
+        OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        },
+        output.clone(),
+      )
+    })
+    .collect()
+}
+
+pub(crate) fn encode(psbt: &Psbt) -> String {
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+pub fn decode(psbt: &str) -> Result<Psbt> {
+  Ok(Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt.trim())
+      .context("PSBT is not valid base64")?,
+  )?)
+}
+
+/// Check the signatures of the inputs of `psbt`, build their final witnesses and extract its
+/// transaction.
+pub(crate) fn finalize(mut psbt: Psbt) -> Result<Transaction> {
+  let spent = psbt
+    .inputs
+    .iter()
+    .zip(&psbt.unsigned_tx.input)
+    .map(|(input, txin)| {
+      input
+        .witness_utxo
+        .clone()
+        .or_else(|| {
+          input.non_witness_utxo.as_ref().and_then(|tx| {
+            tx.output
+              .get(usize::try_from(txin.previous_output.vout).unwrap())
+              .cloned()
+          })
+        })
+        .ok_or_else(|| anyhow!("output {} spent by PSBT not found", txin.previous_output))
+    })
+    .collect::<Result<Vec<TxOut>>>()?;
+
+  let secp256k1 = Secp256k1::verification_only();
+  let mut sighash_cache = SighashCache::new(psbt.unsigned_tx.clone());
+
+  for (vin, input) in psbt.inputs.iter_mut().enumerate() {
+    if input.final_script_witness.is_some() || input.final_script_sig.is_some() {
+      continue;
+    }
+
+    let invalid = || anyhow!("input {vin} of PSBT has an invalid signature");
+
+    let witness = if let Some(signature) = input.tap_key_sig {
+      let output_key = spent[vin]
+        .script_pubkey
+        .is_v1_p2tr()
+        .then(|| XOnlyPublicKey::from_slice(&spent[vin].script_pubkey.as_bytes()[2..]).ok())
+        .flatten()
+        .ok_or_else(invalid)?;
+
+      let sighash = sighash_cache.taproot_key_spend_signature_hash(
+        vin,
+        &Prevouts::All(&spent),
+        signature.hash_ty,
+      )?;
+
+      secp256k1
+        .verify_schnorr(
+          &signature.sig,
+          &Message::from_slice(sighash.as_ref())?,
+          &output_key,
+        )
+        .map_err(|_| invalid())?;
+
+      Witness::from_slice(&[signature.to_vec()])
+    } else if let Some((public_key, leaf_hash, signature, script, control_block)) = input
+      .tap_scripts
+      .iter()
+      .find_map(|(control_block, (script, version))| {
+        let leaf_hash = TapLeafHash::from_script(script, *version);
+        input
+          .tap_script_sigs
+          .iter()
+          .find(|((_, hash), _)| *hash == leaf_hash)
+          .map(|((public_key, leaf_hash), signature)| {
+            (public_key, *leaf_hash, signature, script, control_block)
+          })
+      })
+    {
+      let sighash = sighash_cache.taproot_script_spend_signature_hash(
+        vin,
+        &Prevouts::All(&spent),
+        leaf_hash,
+        signature.hash_ty,
+      )?;
+
+      secp256k1
+        .verify_schnorr(
+          &signature.sig,
+          &Message::from_slice(sighash.as_ref())?,
+          public_key,
+        )
+        .map_err(|_| invalid())?;
+
+      Witness::from_slice(&[
+        signature.to_vec(),
+        script.to_bytes(),
+        control_block.serialize(),
+      ])
+    } else if let Some((public_key, signature)) = input.partial_sigs.iter().next() {
+      let script_code = spent[vin]
+        .script_pubkey
+        .p2wpkh_script_code()
+        .ok_or_else(invalid)?;
+
+      let sighash = sighash_cache.segwit_signature_hash(
+        vin,
+        &script_code,
+        spent[vin].value,
+        signature.hash_ty,
+      )?;
+
+      secp256k1
+        .verify_ecdsa(
+          &Message::from_slice(sighash.as_ref())?,
+          &signature.sig,
+          &public_key.inner,
+        )
+        .map_err(|_| invalid())?;
+
+      Witness::from_slice(&[signature.to_vec(), public_key.to_bytes()])
+    } else {
+      bail!("input {vin} of PSBT is not signed");
+    };
+
+    *input = bitcoin::psbt::Input {
+      witness_utxo: input.witness_utxo.take(),
+      non_witness_utxo: input.non_witness_utxo.take(),
+      final_script_witness: Some(witness),
+      ..Default::default()
+    };
+  }
+
+  Ok(psbt.extract_tx())
+}
+
+fn script_path_spend(
+  witness: &Witness,
+) -> Option<(taproot::Signature, ScriptBuf, ControlBlock, XOnlyPublicKey)> {
+  if witness.len() != 3 {
+    return None;
+  }
+
+  let signature = taproot::Signature::from_slice(witness.nth(0)?).ok()?;
+  let script = ScriptBuf::from(witness.nth(1)?.to_vec());
+  let control_block = ControlBlock::decode(witness.nth(2)?).ok()?;
+
+  let public_key = match script.instructions().next()?.ok()? {
+    bitcoin::script::Instruction::PushBytes(bytes) => {
+      XOnlyPublicKey::from_slice(bytes.as_bytes()).ok()?
+    }
+    bitcoin::script::Instruction::Op(_) => return None,
+  };
+
+  Some((signature, script, control_block, public_key))
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{
+      key::{TapTweak, UntweakedKeyPair},
+      secp256k1::{rand, All},
+      sighash::TapSighashType,
+      taproot::TaprootBuilder,
+    },
+  };
+
+  fn sign(
+    secp256k1: &Secp256k1<All>,
+    key_pair: &UntweakedKeyPair,
+    tx: &Transaction,
+    prevouts: &[TxOut],
+    vin: usize,
+    leaf_hash: Option<TapLeafHash>,
+  ) -> taproot::Signature {
+    let mut sighash_cache = SighashCache::new(tx);
+    let prevouts = Prevouts::All(prevouts);
+
+    let (sighash, key_pair) = match leaf_hash {
+      Some(leaf_hash) => (
+        sighash_cache
+          .taproot_script_spend_signature_hash(vin, &prevouts, leaf_hash, TapSighashType::Default)
+          .unwrap(),
+        *key_pair,
+      ),
+      None => (
+        sighash_cache
+          .taproot_key_spend_signature_hash(vin, &prevouts, TapSighashType::Default)
+          .unwrap(),
+        key_pair.tap_tweak(secp256k1, None).to_inner(),
+      ),
+    };
+
+    taproot::Signature {
+      sig: secp256k1.sign_schnorr(&Message::from_slice(sighash.as_ref()).unwrap(), &key_pair),
+      hash_ty: TapSighashType::Default,
+    }
+  }
+
+  #[test]
+  fn script_path_spends_round_trip_through_psbts() {
+    let secp256k1 = Secp256k1::new();
+    let key_pair = UntweakedKeyPair::new(&secp256k1, &mut rand::thread_rng());
+    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+
+    let wallet_key_pair = UntweakedKeyPair::new(&secp256k1, &mut rand::thread_rng());
+    let (wallet_public_key, _parity) = XOnlyPublicKey::from_keypair(&wallet_key_pair);
+
+    let script = ScriptBuf::builder()
+      .push_slice(public_key.serialize())
+      .push_opcode(bitcoin::opcodes::all::OP_CHECKSIG)
+      .into_script();
+    let leaf_hash = TapLeafHash::from_script(&script, LeafVersion::TapScript);
+
+    let spend_info = TaprootBuilder::new()
+      .add_leaf(0, script.clone())
+      .unwrap()
+      .finalize(&secp256k1, public_key)
+      .unwrap();
+
+    let control_block = spend_info
+      .control_block(&(script.clone(), LeafVersion::TapScript))
+      .unwrap();
+
+    let commit = OutPoint {
+      txid: txid(1),
+      vout: 0,
+    };
+    let wallet = OutPoint {
+      txid: txid(2),
+      vout: 1,
+    };
+
+    let spent = vec![
+      TxOut {
+        value: 20_000,
+        script_pubkey: ScriptBuf::new_v1_p2tr_tweaked(spend_info.output_key()),
+      },
+      TxOut {
+        value: 20_000,
+        script_pubkey: ScriptBuf::new_v1_p2tr(&secp256k1, wallet_public_key, None),
+      },
+    ];
+
+    let mut tx = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![
+        TxIn {
+          previous_output: commit,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        },
+        TxIn {
+          previous_output: wallet,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        },
+      ],
+      output: vec![TxOut {
+        value: 10_000,
+        script_pubkey: ScriptBuf::new(),
+      }],
+    };
+
+    let signature = sign(&secp256k1, &key_pair, &tx, &spent, 0, Some(leaf_hash));
+    tx.input[0].witness = Witness::from_slice(&[
+      signature.to_vec(),
+      script.to_bytes(),
+      control_block.serialize(),
+    ]);
+
+    // the wallet fills in the output spent by its input.
+    let psbt = decode(&encode(
+      &unsigned(&tx, &[(commit, spent[0].clone())].into_iter().collect()).unwrap(),
+    ))
+    .unwrap();
+
+    assert_eq!(psbt.inputs[0].tap_internal_key, Some(public_key));
+    assert_eq!(
+      psbt.inputs[0].tap_scripts.get(&control_block),
+      Some(&(script.clone(), LeafVersion::TapScript))
+    );
+    assert_eq!(psbt.inputs[1].witness_utxo, None);
+    assert!(psbt.unsigned_tx.input[0].witness.is_empty());
+
+    assert_eq!(
+      finalize(psbt.clone()).unwrap_err().to_string(),
+      format!("output {wallet} spent by PSBT not found")
+    );
+
+    let mut psbt = psbt;
+    psbt.inputs[1].witness_utxo = Some(spent[1].clone());
+
+    assert_eq!(
+      finalize(psbt.clone()).unwrap_err().to_string(),
+      "input 1 of PSBT is not signed"
+    );
+
+    let mut signed = psbt.clone();
+    signed.inputs[1].tap_key_sig = Some(signature);
+    assert_eq!(
+      finalize(signed).unwrap_err().to_string(),
+      "input 1 of PSBT has an invalid signature"
+    );
+
+    let signature = sign(&secp256k1, &wallet_key_pair, &tx, &spent, 1, None);
+    let mut signed = psbt;
+    signed.inputs[1].tap_key_sig = Some(signature);
+
+    let finalized = finalize(signed).unwrap();
+    assert_eq!(finalized.input[0].witness, tx.input[0].witness);
+    assert_eq!(
+      finalized.input[1].witness,
+      Witness::from_slice(&[signature.to_vec()])
+    );
+    assert_eq!(finalized.txid(), tx.txid());
+  }
+
+  #[test]
+  fn script_path_signatures_are_checked() {
+    let secp256k1 = Secp256k1::new();
+    let key_pair = UntweakedKeyPair::new(&secp256k1, &mut rand::thread_rng());
+    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+
+    let script = ScriptBuf::builder()
+      .push_slice(public_key.serialize())
+      .push_opcode(bitcoin::opcodes::all::OP_CHECKSIG)
+      .into_script();
+
+    let spend_info = TaprootBuilder::new()
+      .add_leaf(0, script.clone())
+      .unwrap()
+      .finalize(&secp256k1, public_key)
+      .unwrap();
+
+    let control_block = spend_info
+      .control_block(&(script.clone(), LeafVersion::TapScript))
+      .unwrap();
+
+    let commit = OutPoint {
+      txid: txid(1),
+      vout: 0,
+    };
+
+    let spent = TxOut {
+      value: 20_000,
+      script_pubkey: ScriptBuf::new_v1_p2tr_tweaked(spend_info.output_key()),
+    };
+
+    let tx = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: commit,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::from_slice(&[
+          [1; 64].as_slice(),
+          script.as_bytes(),
+          &control_block.serialize(),
+        ]),
+      }],
+      output: Vec::new(),
+    };
+
+    assert_eq!(
+      finalize(unsigned(&tx, &[(commit, spent)].into_iter().collect()).unwrap())
+        .unwrap_err()
+        .to_string(),
+      "input 0 of PSBT has an invalid signature"
+    );
+  }
+}
@@ -2,24 +2,35 @@ use {super::*, crate::subcommand::wallet::transaction_builder::Target};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Send {
-  address: Address<NetworkUnchecked>,
-  outgoing: Outgoing,
+  pub(crate) address: Address<NetworkUnchecked>,
+  pub(crate) outgoing: Outgoing,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
-  fee_rate: FeeRate,
+  pub(crate) fee_rate: FeeRate,
   #[arg(
     long,
     help = "Target amount of postage to include with sent inscriptions. Default `10000sat`"
   )]
   pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Don't sign or broadcast the transaction, output a PSBT to sign externally and pass to `ord wallet finalize-psbt` instead."
+  )]
+  pub(crate) psbt: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub transaction: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub psbt: Option<String>,
 }
 
 impl Send {
   pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    Ok(Box::new(self.send(wallet, options)?))
+  }
+
+  pub(crate) fn send(self, wallet: String, options: Options) -> Result<Output> {
     let address = self
       .address
       .clone()
@@ -45,14 +56,38 @@ impl Send {
     let satpoint = match self.outgoing {
       Outgoing::Amount(amount) => {
         Self::lock_non_cardinal_outputs(&client, &inscriptions, &runic_outputs, unspent_outputs)?;
-        let transaction = Self::send_amount(&client, amount, address, self.fee_rate)?;
-        return Ok(Box::new(Output { transaction }));
+
+        if !self.psbt {
+          let transaction = Self::send_amount(&client, amount, address, self.fee_rate)?;
+          return Ok(Output {
+            transaction,
+            psbt: None,
+          });
+        }
+
+        let unfunded_transaction = Transaction {
+          version: 2,
+          lock_time: LockTime::ZERO,
+          input: Vec::new(),
+          output: vec![TxOut {
+            script_pubkey: address.script_pubkey(),
+            value: amount.to_sat(),
+          }],
+        };
+
+        let unsigned_transaction = consensus::encode::deserialize(&fund_raw_transaction(
+          &client,
+          self.fee_rate,
+          &unfunded_transaction,
+        )?)?;
+
+        return Self::sign_and_send(&client, unsigned_transaction, self.psbt);
       }
       Outgoing::InscriptionId(id) => index
         .get_inscription_satpoint_by_id(id)?
         .ok_or_else(|| anyhow!("inscription {id} not found"))?,
       Outgoing::Rune { decimal, rune } => {
-        let transaction = Self::send_runes(
+        let unsigned_transaction = Self::send_runes(
           address,
           chain,
           &client,
@@ -64,7 +99,7 @@ impl Send {
           runic_outputs,
           unspent_outputs,
         )?;
-        return Ok(Box::new(Output { transaction }));
+        return Self::sign_and_send(&client, unsigned_transaction, self.psbt);
       }
       Outgoing::SatPoint(satpoint) => {
         for inscription_satpoint in inscriptions.keys() {
@@ -106,13 +141,33 @@ impl Send {
     )
     .build_transaction()?;
 
+    Self::sign_and_send(&client, unsigned_transaction, self.psbt)
+  }
+
+  fn sign_and_send(
+    client: &Client,
+    unsigned_transaction: Transaction,
+    psbt: bool,
+  ) -> Result<Output> {
+    if psbt {
+      let psbt = psbt::create(client, &unsigned_transaction, &BTreeMap::new())?;
+
+      return Ok(Output {
+        transaction: unsigned_transaction.txid(),
+        psbt: Some(psbt::encode(&psbt)),
+      });
+    }
+
     let signed_tx = client
       .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
       .hex;
 
     let txid = client.send_raw_transaction(&signed_tx)?;
 
-    Ok(Box::new(Output { transaction: txid }))
+    Ok(Output {
+      transaction: txid,
+      psbt: None,
+    })
   }
 
   fn lock_non_cardinal_outputs(
@@ -174,7 +229,7 @@ impl Send {
     spaced_rune: SpacedRune,
     runic_outputs: BTreeSet<OutPoint>,
     unspent_outputs: BTreeMap<OutPoint, Amount>,
-  ) -> Result<Txid> {
+  ) -> Result<Transaction> {
     ensure!(
       index.has_rune_index(),
       "sending runes with `ord send` requires index created with `--index-runes` flag",
@@ -261,12 +316,10 @@ impl Send {
       ],
     };
 
-    let unsigned_transaction = fund_raw_transaction(client, fee_rate, &unfunded_transaction)?;
-
-    let signed_transaction = client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
-      .hex;
-
-    Ok(client.send_raw_transaction(&signed_transaction)?)
+    Ok(consensus::encode::deserialize(&fund_raw_transaction(
+      client,
+      fee_rate,
+      &unfunded_transaction,
+    )?)?)
   }
 }
@@ -3,9 +3,13 @@ use {super::*, boilerplate::Boilerplate};
 pub(crate) use {
   block::{BlockHtml, BlockJson},
   blocks::BlocksHtml,
+  brc20_receipts::Brc20ReceiptsHtml,
+  brc20_token::Brc20TokenHtml,
+  brc20_tokens::Brc20TokensHtml,
   children::{ChildrenHtml, ChildrenJson},
   clock::ClockSvg,
   collections::CollectionsHtml,
+  district::DistrictHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
@@ -30,9 +34,13 @@ pub(crate) use {
 
 pub mod block;
 mod blocks;
+mod brc20_receipts;
+mod brc20_token;
+mod brc20_tokens;
 mod children;
 mod clock;
 pub mod collections;
+mod district;
 mod home;
 mod iframe;
 mod input;
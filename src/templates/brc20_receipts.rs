@@ -0,0 +1,102 @@
+use {
+  super::*,
+  crate::okx::datastore::brc20::{
+    Event, InscribeTransferEvent, MintEvent, Receipt, Tick, TransferEvent,
+  },
+};
+
+/// BRC20 events section of the transaction and inscription pages.
+#[derive(Boilerplate, Default)]
+pub(crate) struct Brc20ReceiptsHtml {
+  pub(crate) receipts: Vec<(Txid, Receipt)>,
+  /// Decimals of the lowercase tickers of the receipts.
+  pub(crate) decimals: BTreeMap<String, u8>,
+}
+
+impl Brc20ReceiptsHtml {
+  fn amount(&self, tick: &Tick, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self
+        .decimals
+        .get(tick.to_lowercase().as_str())
+        .copied()
+        .unwrap_or_default(),
+      symbol: None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::okx::datastore::{
+      brc20::{BRC20Error, OperationType},
+      ScriptKey,
+    },
+  };
+
+  #[test]
+  fn display() {
+    let script_key = ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet);
+    let receipt = Receipt {
+      inscription_id: InscriptionId {
+        txid: Txid::all_zeros(),
+        index: 0,
+      },
+      inscription_number: 0,
+      old_satpoint: SatPoint::default(),
+      new_satpoint: SatPoint::default(),
+      op: OperationType::Mint,
+      from: script_key.clone(),
+      to: script_key,
+      result: Ok(Event::Mint(MintEvent {
+        tick: Tick::from_str("ORDI").unwrap(),
+        amount: 1_500,
+        msg: None,
+      })),
+    };
+
+    assert_regex_match!(
+      Brc20ReceiptsHtml {
+        receipts: vec![
+          (Txid::all_zeros(), receipt.clone()),
+          (
+            Txid::all_zeros(),
+            Receipt {
+              result: Err(BRC20Error::TickNotFound("abcd".into())),
+              ..receipt
+            }
+          ),
+        ],
+        decimals: [("ordi".to_string(), 3)].into_iter().collect(),
+      },
+      "<h2>BRC20 Events</h2>
+<ul>
+  <li>
+    <dl>
+      <dt>operation</dt><dd>mint</dd>
+      <dt>transaction</dt><dd><a class=monospace href=/tx/0{64}>0{64}</a></dd>
+      <dt>inscription</dt><dd><a class=monospace href=/inscription/0{64}i0>0{64}i0</a></dd>
+      <dt>from</dt><dd class=monospace>[[:xdigit:]]{40}</dd>
+      <dt>to</dt><dd class=monospace>[[:xdigit:]]{40}</dd>
+      <dt>ticker</dt><dd><a href=\"/brc20/ORDI\">ORDI</a></dd>
+      <dt>amount</dt><dd>1.5</dd>
+    </dl>
+  </li>
+  <li>
+    <dl>
+      <dt>operation</dt><dd>mint</dd>
+      <dt>transaction</dt><dd><a class=monospace href=/tx/0{64}>0{64}</a></dd>
+      <dt>inscription</dt><dd><a class=monospace href=/inscription/0{64}i0>0{64}i0</a></dd>
+      <dt>from</dt><dd class=monospace>[[:xdigit:]]{40}</dd>
+      <dt>to</dt><dd class=monospace>[[:xdigit:]]{40}</dd>
+      <dt>error</dt><dd>tick: abcd not found</dd>
+    </dl>
+  </li>
+</ul>
+"
+    );
+  }
+}
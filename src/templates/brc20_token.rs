@@ -0,0 +1,132 @@
+use {
+  super::*,
+  crate::okx::datastore::{
+    brc20::{Balance, Tick, TokenInfo},
+    ScriptKey,
+  },
+};
+
+#[derive(Boilerplate)]
+pub(crate) struct Brc20TokenHtml {
+  pub(crate) token: TokenInfo,
+  pub(crate) holders: Vec<(ScriptKey, Balance)>,
+}
+
+impl Brc20TokenHtml {
+  /// Path of the page of `tick`, which may contain any character.
+  pub(crate) fn path(tick: &Tick) -> String {
+    format!(
+      "/brc20/{}",
+      percent_encoding::utf8_percent_encode(&tick.as_str(), percent_encoding::NON_ALPHANUMERIC)
+    )
+  }
+
+  fn amount(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self.token.decimal,
+      symbol: None,
+    }
+  }
+
+  fn minted_percentage(&self) -> String {
+    if self.token.supply == 0 {
+      return "0%".into();
+    }
+
+    format!(
+      "{:.2}%",
+      self.token.minted as f64 / self.token.supply as f64 * 100.0
+    )
+  }
+}
+
+impl PageContent for Brc20TokenHtml {
+  fn title(&self) -> String {
+    format!("BRC20 {}", self.token.tick)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::okx::datastore::brc20::Tick};
+
+  #[test]
+  fn path_is_percent_encoded() {
+    assert_eq!(
+      Brc20TokenHtml::path(&Tick::from_str("ordi").unwrap()),
+      "/brc20/ordi"
+    );
+    assert_eq!(
+      Brc20TokenHtml::path(&Tick::from_str("a\"/?").unwrap()),
+      "/brc20/a%22%2F%3F"
+    );
+    assert_eq!(
+      Brc20TokenHtml::path(&Tick::from_str("🔥").unwrap()),
+      "/brc20/%F0%9F%94%A5"
+    );
+  }
+
+  #[test]
+  fn display() {
+    let script_key = ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet);
+
+    assert_regex_match!(
+      Brc20TokenHtml {
+        token: TokenInfo {
+          tick: Tick::from_str("ordi").unwrap(),
+          inscription_id: InscriptionId {
+            txid: Txid::all_zeros(),
+            index: 0,
+          },
+          inscription_number: 0,
+          supply: 21_000_000_000,
+          burned_supply: 0,
+          minted: 5_250_000_000,
+          limit_per_mint: 1_000_000,
+          decimal: 3,
+          deploy_by: script_key.clone(),
+          is_self_mint: false,
+          deployed_number: 10,
+          deployed_timestamp: 0,
+          latest_mint_number: 12,
+          holders: 1,
+        },
+        holders: vec![(
+          script_key,
+          Balance {
+            tick: Tick::from_str("ordi").unwrap(),
+            overall_balance: 5_250_000_000,
+            transferable_balance: 0,
+          }
+        )],
+      },
+      "<h1>BRC20 ordi</h1>
+<dl>
+  <dt>supply</dt>
+  <dd>21000000</dd>
+  <dt>minted</dt>
+  <dd>5250000 \\(25.00%\\)</dd>
+  <dt>limit per mint</dt>
+  <dd>1000</dd>
+  <dt>decimals</dt>
+  <dd>3</dd>
+  <dt>holders</dt>
+  <dd>1</dd>
+  <dt>deployer</dt>
+  <dd class=monospace>[[:xdigit:]]{40}</dd>
+  <dt>deploy inscription</dt>
+  <dd><a class=monospace href=/inscription/0{64}i0>0{64}i0</a></dd>
+  <dt>deploy height</dt>
+  <dd><a href=/block/10>10</a></dd>
+  <dt>deploy timestamp</dt>
+  <dd><time>1970-01-01 00:00:00 UTC</time></dd>
+</dl>
+<h2>Top Holders</h2>
+<ul>
+  <li><span class=monospace>[[:xdigit:]]{40}</span> 5250000</li>
+</ul>
+"
+    );
+  }
+}
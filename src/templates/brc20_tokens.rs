@@ -0,0 +1,53 @@
+use {super::*, crate::okx::datastore::brc20::TokenInfo};
+
+#[derive(Boilerplate)]
+pub(crate) struct Brc20TokensHtml {
+  pub(crate) tokens: Vec<TokenInfo>,
+}
+
+impl PageContent for Brc20TokensHtml {
+  fn title(&self) -> String {
+    "BRC20 Tokens".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::okx::datastore::{brc20::Tick, ScriptKey},
+  };
+
+  #[test]
+  fn display() {
+    assert_eq!(
+      Brc20TokensHtml {
+        tokens: vec![TokenInfo {
+          tick: Tick::from_str("ordi").unwrap(),
+          inscription_id: InscriptionId {
+            txid: Txid::all_zeros(),
+            index: 0,
+          },
+          inscription_number: 0,
+          supply: 0,
+          burned_supply: 0,
+          minted: 0,
+          limit_per_mint: 0,
+          decimal: 18,
+          deploy_by: ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet),
+          is_self_mint: false,
+          deployed_number: 0,
+          deployed_timestamp: 0,
+          latest_mint_number: 0,
+          holders: 0,
+        }],
+      }
+      .to_string(),
+      "<h1>BRC20 Tokens</h1>
+<ul>
+  <li><a href=\"/brc20/ordi\">ordi</a></li>
+</ul>
+"
+    );
+  }
+}
@@ -0,0 +1,72 @@
+use {super::*, crate::okx::datastore::ScriptKey};
+
+#[derive(Boilerplate)]
+pub(crate) struct DistrictHtml {
+  pub(crate) number: u32,
+  pub(crate) blockhash: Option<BlockHash>,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) owner: Option<ScriptKey>,
+}
+
+impl PageContent for DistrictHtml {
+  fn title(&self) -> String {
+    format!("District {}", self.number)
+  }
+
+  fn preview_image_url(&self) -> Option<Trusted<String>> {
+    Some(Trusted(format!("/content/{}", self.inscription_id)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      DistrictHtml {
+        number: 840000,
+        blockhash: Some(blockhash(0)),
+        inscription_id: inscription_id(1),
+        owner: Some(ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet)),
+      },
+      "<h1>District 840000</h1>
+<iframe .* src=/preview/1{64}i1></iframe>
+<dl>
+  <dt>block</dt>
+  <dd><a href=/block/840000>840000</a></dd>
+  <dt>block hash</dt>
+  <dd><a class=monospace href=/block/0{64}>0{64}</a></dd>
+  <dt>inscription</dt>
+  <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+  <dt>owner</dt>
+  <dd class=monospace>[[:xdigit:]]{40}</dd>
+</dl>
+"
+    );
+  }
+
+  #[test]
+  fn lost() {
+    assert_regex_match!(
+      DistrictHtml {
+        number: 0,
+        blockhash: None,
+        inscription_id: inscription_id(1),
+        owner: None,
+      },
+      "<h1>District 0</h1>
+<iframe .* src=/preview/1{64}i1></iframe>
+<dl>
+  <dt>block</dt>
+  <dd><a href=/block/0>0</a></dd>
+  <dt>inscription</dt>
+  <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+  <dt>owner</dt>
+  <dd>lost</dd>
+</dl>
+"
+    );
+  }
+}
@@ -2,6 +2,7 @@ use super::*;
 
 #[derive(Boilerplate, Default)]
 pub(crate) struct InscriptionHtml {
+  pub(crate) brc20_receipts: Brc20ReceiptsHtml,
   pub(crate) chain: Chain,
   pub(crate) children: Vec<InscriptionId>,
   pub(crate) genesis_fee: u64,
@@ -3,6 +3,7 @@ use super::*;
 #[derive(Boilerplate)]
 pub(crate) struct TransactionHtml {
   pub(crate) blockhash: Option<BlockHash>,
+  pub(crate) brc20_receipts: Brc20ReceiptsHtml,
   pub(crate) chain: Chain,
   pub(crate) etching: Option<SpacedRune>,
   pub(crate) inscription_count: u32,
@@ -48,6 +49,7 @@ mod tests {
     pretty_assert_eq!(
       TransactionHtml {
         blockhash: None,
+        brc20_receipts: Default::default(),
         chain: Chain::Mainnet,
         etching: None,
         inscription_count: 0,
@@ -111,6 +113,7 @@ mod tests {
     assert_regex_match!(
       TransactionHtml {
         blockhash: Some(blockhash(0)),
+        brc20_receipts: Default::default(),
         chain: Chain::Mainnet,
         etching: None,
         inscription_count: 0,
@@ -23,7 +23,7 @@ use {
     GetTransactionResultDetail, GetTransactionResultDetailCategory, GetWalletInfoResult,
     ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListTransactionResult,
     ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletTxInfo,
+    Timestamp, WalletProcessPsbtResult, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -131,6 +131,7 @@ pub struct TransactionTemplate<'a> {
   pub inputs: &'a [(usize, usize, usize, Witness)],
   pub op_return: Option<ScriptBuf>,
   pub op_return_index: Option<usize>,
+  pub op_return_value: Option<u64>,
   pub output_values: &'a [u64],
   pub outputs: usize,
 }
@@ -184,6 +185,7 @@ impl<'a> Default for TransactionTemplate<'a> {
       inputs: &[],
       op_return: None,
       op_return_index: None,
+      op_return_value: None,
       output_values: &[],
       outputs: 1,
     }
@@ -1,11 +1,14 @@
 use {
   super::*,
+  base64::Engine,
   bitcoin::{
+    bip32::{DerivationPath, Fingerprint},
     consensus::Decodable,
-    secp256k1::{rand, KeyPair, Secp256k1, XOnlyPublicKey},
+    psbt::Psbt,
+    secp256k1::{rand, KeyPair, Secp256k1, SecretKey, XOnlyPublicKey},
     Witness,
   },
-  std::io::Cursor,
+  std::{io::Cursor, str::FromStr},
 };
 
 pub(crate) struct Server {
@@ -358,6 +361,61 @@ impl Api for Server {
     )
   }
 
+  fn wallet_process_psbt(
+    &self,
+    psbt: String,
+    sign: Option<bool>,
+    _sighash_type: Option<String>,
+    bip32derivs: Option<bool>,
+  ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
+    assert_eq!(sign, Some(false), "signing PSBTs is not supported");
+
+    let mut psbt = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(psbt)
+        .unwrap(),
+    )
+    .unwrap();
+
+    // every wallet output is derived from the same key.
+    let secp256k1 = Secp256k1::new();
+    let key_pair = KeyPair::from_secret_key(&secp256k1, &SecretKey::from_slice(&[1; 32]).unwrap());
+    let (internal_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+    let key_source = (
+      Fingerprint::from([1; 4]),
+      DerivationPath::from_str("m/86'/1'/0'/0/0").unwrap(),
+    );
+
+    let state = self.state();
+    for (input, txin) in psbt.inputs.iter_mut().zip(&psbt.unsigned_tx.input) {
+      let outpoint = txin.previous_output;
+      if !state.utxos.contains_key(&outpoint) {
+        continue;
+      }
+
+      input.witness_utxo = state
+        .transactions
+        .get(&outpoint.txid)
+        .and_then(|tx| tx.output.get(usize::try_from(outpoint.vout).unwrap()))
+        .cloned();
+
+      if bip32derivs.unwrap_or(true) {
+        input.tap_internal_key = Some(internal_key);
+        input
+          .tap_key_origins
+          .insert(internal_key, (Vec::new(), key_source.clone()));
+        input
+          .bip32_derivation
+          .insert(key_pair.public_key(), key_source.clone());
+      }
+    }
+
+    Ok(WalletProcessPsbtResult {
+      psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      complete: false,
+    })
+  }
+
   fn send_raw_transaction(&self, tx: String) -> Result<String, jsonrpc_core::Error> {
     let tx: Transaction = deserialize(&hex::decode(tx).unwrap()).unwrap();
     self.state.lock().unwrap().mempool.push(tx.clone());
@@ -475,6 +533,10 @@ impl Api for Server {
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(self.state().mempool.iter().map(Transaction::txid).collect())
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -506,7 +568,12 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match self.state().transactions.get(&txid) {
+      let state = self.state();
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -541,7 +608,15 @@ impl Api for Server {
           label: None,
           redeem_script: None,
           witness_script: None,
-          script_pub_key: ScriptBuf::new(),
+          script_pub_key: state
+            .transactions
+            .get(&outpoint.txid)
+            .map(|tx| {
+              tx.output[usize::try_from(outpoint.vout).unwrap()]
+                .script_pubkey
+                .clone()
+            })
+            .unwrap_or_default(),
           amount,
           confirmations: 0,
           spendable: true,
@@ -150,15 +150,17 @@ impl State {
       });
     }
 
+    let op_return_value = template.op_return_value.unwrap_or_default();
+
     let value_per_output = if template.outputs > 0 {
-      (total_value - template.fee) / template.outputs as u64
+      (total_value - template.fee - op_return_value) / template.outputs as u64
     } else {
       0
     };
 
     if template.outputs > 0 {
       assert_eq!(
-        value_per_output * template.outputs as u64 + template.fee,
+        value_per_output * template.outputs as u64 + template.fee + op_return_value,
         total_value
       );
     }
@@ -183,7 +185,7 @@ impl State {
       tx.output.insert(
         template.op_return_index.unwrap_or(tx.output.len()),
         TxOut {
-          value: 0,
+          value: op_return_value,
           script_pubkey,
         },
       );
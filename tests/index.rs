@@ -116,3 +116,60 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn brc20_checksum_is_rolled_over_every_block() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(2);
+
+  let checksums = CommandBuilder::new("--enable-index-brc20 index brc20-checksum --from 0 --to 10")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Vec<ord::subcommand::index::brc20_checksum::BlockChecksum>>();
+
+  assert_eq!(
+    checksums
+      .iter()
+      .map(|checksum| checksum.height)
+      .collect::<Vec<u32>>(),
+    vec![0, 1, 2]
+  );
+  assert_eq!(
+    checksums[0].checksum,
+    "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925"
+  );
+  assert_ne!(checksums[1].checksum, checksums[2].checksum);
+}
+
+#[test]
+fn brc20_checksum_requires_ordered_range() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("--enable-index-brc20 index brc20-checksum --from 2 --to 1")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: --from 2 is greater than --to 1\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn compact_outputs_leaves_recorded_blocks_to_the_updater() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index {} index compact-outputs",
+      index_path.display()
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::compact_outputs::Output>(),
+    ord::subcommand::index::compact_outputs::Output {
+      blocks: 0,
+      pruned: 0
+    }
+  );
+}
@@ -1,6 +1,7 @@
 use super::*;
 
 mod balance;
+mod brc20;
 mod cardinals;
 mod create;
 mod inscribe;
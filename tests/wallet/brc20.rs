@@ -0,0 +1,213 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{
+    brc20::{balance::Output as Balance, transfer::Output as Transfer},
+    receive,
+  },
+  std::time::Instant,
+};
+
+fn inscribe_brc20(
+  rpc_server: &test_bitcoincore_rpc::Handle,
+  destination: &str,
+  content: &str,
+) -> Inscribe {
+  let output = CommandBuilder::new(format!(
+    "wallet inscribe --fee-rate 1 --file brc20.txt --destination {destination}"
+  ))
+  .write("brc20.txt", content)
+  .rpc_server(rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  output
+}
+
+fn balances(rpc_server: &test_bitcoincore_rpc::Handle) -> Vec<Balance> {
+  CommandBuilder::new("--enable-index-brc20 wallet brc20 balance")
+    .rpc_server(rpc_server)
+    .run_and_deserialize_output()
+}
+
+fn balance(tick: &str, overall: &str, transferable: &str, available: &str) -> Balance {
+  Balance {
+    tick: tick.into(),
+    overall_balance: overall.into(),
+    transferable_balance: transferable.into(),
+    available_balance: available.into(),
+  }
+}
+
+#[test]
+fn brc20_requires_brc20_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("wallet brc20 balance")
+    .rpc_server(&rpc_server)
+    .expected_stderr(
+      "error: `ord wallet brc20` requires index created with `--enable-index-brc20` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn brc20_tokens_can_be_transferred() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let holder = CommandBuilder::new("wallet receive")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<receive::Output>()
+    .address
+    .assume_checked()
+    .to_string();
+
+  assert_eq!(balances(&rpc_server), Vec::new());
+
+  inscribe_brc20(
+    &rpc_server,
+    &holder,
+    r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"1000"}"#,
+  );
+  inscribe_brc20(
+    &rpc_server,
+    &holder,
+    r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+  );
+
+  assert_eq!(
+    balances(&rpc_server),
+    vec![balance("ordi", "1000", "0", "1000")]
+  );
+
+  let existing = inscribe_brc20(
+    &rpc_server,
+    &holder,
+    r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"10"}"#,
+  );
+
+  assert_eq!(
+    balances(&rpc_server),
+    vec![balance("ordi", "1000", "10", "990")]
+  );
+
+  let output = CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 transfer --tick ordi --amount 10 --fee-rate 1 --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Transfer>();
+
+  assert_eq!(output.inscription, existing.inscriptions[0].id);
+  assert_eq!(output.reveal, None);
+  assert_eq!(rpc_server.mempool().len(), 1);
+  assert_eq!(rpc_server.mempool()[0].txid(), output.transaction);
+
+  rpc_server.mine_blocks(1);
+
+  // the test wallet also owns the output of the recipient, whose 10 available tokens must not be
+  // used.
+  let builder = CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 transfer --tick ordi --amount 5 --fee-rate 1 --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server);
+
+  let mut child = builder.command().spawn().unwrap();
+
+  let deadline = Instant::now() + Duration::from_secs(30);
+  while rpc_server.mempool().len() < 2 {
+    if Instant::now() > deadline {
+      child.kill().unwrap();
+      panic!(
+        "transferable inscription was not broadcast: {:?}",
+        child.wait_with_output()
+      );
+    }
+    thread::sleep(Duration::from_millis(100));
+  }
+
+  rpc_server.mine_blocks(1);
+
+  let output = child.wait_with_output().unwrap();
+  assert!(output.status.success(), "{output:?}");
+  let output = serde_json::from_slice::<Transfer>(&output.stdout).unwrap();
+
+  let reveal = output.reveal.unwrap();
+  assert_eq!(
+    output.inscription,
+    InscriptionId {
+      txid: reveal,
+      index: 0
+    }
+  );
+  assert_eq!(rpc_server.mempool().len(), 1);
+  assert_eq!(rpc_server.mempool()[0].txid(), output.transaction);
+
+  rpc_server.mine_blocks(1);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &["--enable-index-brc20"]);
+
+  let overall_balance = |address: &str| {
+    ord_server
+      .json_request(format!("/api/v1/brc20/tick/ordi/address/{address}/balance"))
+      .json::<serde_json::Value>()
+      .unwrap()["data"]["overallBalance"]
+      .as_str()
+      .unwrap()
+      .to_string()
+  };
+
+  assert_eq!(overall_balance(&holder), "985000000000000000000");
+  assert_eq!(
+    overall_balance("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+    "15000000000000000000"
+  );
+}
+
+#[test]
+fn brc20_transfer_requires_available_balance() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let holder = CommandBuilder::new("wallet receive")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<receive::Output>()
+    .address
+    .assume_checked()
+    .to_string();
+
+  inscribe_brc20(
+    &rpc_server,
+    &holder,
+    r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"1000"}"#,
+  );
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 transfer --tick ordi --amount 10 --fee-rate 1 --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: no address of the wallet has an available ordi balance of 10\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  inscribe_brc20(
+    &rpc_server,
+    &holder,
+    r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+  );
+
+  // the tokens of the recipient are not available to transfer to it.
+  CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet brc20 transfer --tick ordi --amount 10 --fee-rate 1 --to {holder}",
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: no address of the wallet has an available ordi balance of 10\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(rpc_server.mempool().is_empty());
+}
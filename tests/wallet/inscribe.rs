@@ -1800,3 +1800,50 @@ fn server_can_decompress_brotli() {
   assert_eq!(response.status(), StatusCode::OK);
   assert_eq!(response.bytes().unwrap().deref(), [0; 350_000]);
 }
+
+#[test]
+fn inscribe_with_psbt_flag_outputs_psbts_to_sign_externally() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --psbt --file degenerate.png --fee-rate 1")
+    .write("degenerate.png", [1; 520])
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert!(rpc_server.mempool().is_empty());
+
+  let decode = |psbt: &str| ord::subcommand::wallet::psbt::decode(psbt).unwrap();
+
+  let commit_psbt = output.commit_psbt.unwrap();
+  let commit = decode(&commit_psbt);
+  assert_eq!(commit.unsigned_tx.txid(), output.commit);
+  for input in &commit.inputs {
+    assert!(input.witness_utxo.is_some());
+    let internal_key = input.tap_internal_key.unwrap();
+    assert!(input.tap_key_origins.contains_key(&internal_key));
+    assert_eq!(input.bip32_derivation.len(), 1);
+  }
+
+  let reveal_psbt = output.reveal_psbt.unwrap();
+  let reveal = decode(&reveal_psbt);
+  assert_eq!(reveal.unsigned_tx.txid(), output.reveal);
+  assert!(reveal.inputs[0].witness_utxo.is_some());
+  assert!(reveal.inputs[0].tap_internal_key.is_some());
+  assert_eq!(reveal.inputs[0].tap_scripts.len(), 1);
+  assert_eq!(reveal.inputs[0].tap_script_sigs.len(), 1);
+
+  CommandBuilder::new(format!("wallet finalize-psbt {commit_psbt}"))
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: input 0 of PSBT is not signed\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  let finalized = CommandBuilder::new(format!("wallet finalize-psbt --dry-run {reveal_psbt}"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::wallet::psbt::Output>();
+
+  assert_eq!(finalized.transactions, vec![output.reveal]);
+  assert!(rpc_server.mempool().is_empty());
+}
@@ -954,3 +954,30 @@ fn sending_rune_does_not_send_inscription() {
   .stderr_regex("error:.*")
   .run_and_extract_stdout();
 }
+
+#[test]
+fn send_with_psbt_flag_does_not_broadcast() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --psbt --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert!(rpc_server.mempool().is_empty());
+
+  let psbt = ord::subcommand::wallet::psbt::decode(&output.psbt.unwrap()).unwrap();
+
+  assert_eq!(psbt.unsigned_tx.txid(), output.transaction);
+  assert_eq!(psbt.inputs[0].witness_utxo.as_ref().unwrap().value, 10_000);
+  let internal_key = psbt.inputs[0].tap_internal_key.unwrap();
+  assert!(psbt.inputs[0].tap_key_origins.contains_key(&internal_key));
+  assert_eq!(psbt.inputs[0].bip32_derivation.len(), 1);
+}